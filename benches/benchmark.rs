@@ -1,7 +1,10 @@
 use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+#[cfg(feature = "parallel")]
+use mst_kruskal_variants::ParallelSortKruskal;
 use mst_kruskal_variants::{
-    FilterKruskal, GraphMatrix, GraphStars, Kruskal, QuickSortKruskal, SkewedFilterKruskal,
-    StarQuickSortKruskal,
+    BoruvkaFilterKruskal, BucketKruskal, Chazelle, DaryHeap, DensePrim, Edge, FilterKruskal,
+    FilterKruskalPlus, GraphMatrix, GraphStars, Kruskal, PivotStrategy, QuickSortKruskal,
+    RadixKruskal, SkewedFilterKruskal, SortKruskal, SparsePrim, StarQuickSortKruskal,
 };
 use rand::rngs::StdRng;
 use rand::SeedableRng;
@@ -33,23 +36,12 @@ fn kruskal_comparison_benchmark(c: &mut Criterion) {
             0.0
         };
 
-        // Same seed for both graph generators to ensure the graphs
-        // represent the same topology.
-        let mut rng_stars = StdRng::seed_from_u64(42);
-        let mut rng_matrix = StdRng::seed_from_u64(42);
-
-        let graph_stars =
-            GraphStars::<usize>::new_random(0..v, p, weight_min, weight_max, true, &mut rng_stars)
+        let mut rng = StdRng::seed_from_u64(42);
+        let graph_matrix =
+            GraphMatrix::<usize>::new_random(0..v, p, weight_min, weight_max, true, &mut rng)
                 .unwrap();
-        let graph_matrix = GraphMatrix::<usize>::new_random(
-            0..v,
-            p,
-            weight_min,
-            weight_max,
-            true,
-            &mut rng_matrix,
-        )
-        .unwrap();
+        // Same topology on both representations.
+        let graph_stars = GraphStars::from(&graph_matrix);
 
         let input_str = format!("{}-v-{}-e", v, e);
 
@@ -59,7 +51,19 @@ fn kruskal_comparison_benchmark(c: &mut Criterion) {
             |b, g| {
                 b.iter_batched(
                     || Kruskal::new(g),
-                    |mut algo| black_box(algo.run()),
+                    |algo| black_box(algo.run()),
+                    BatchSize::SmallInput,
+                );
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("DaryHeap4", &input_str),
+            &graph_matrix,
+            |b, g| {
+                b.iter_batched(
+                    || Kruskal::<DaryHeap<Edge, 4>>::with_heap(g),
+                    |algo| black_box(algo.run()),
                     BatchSize::SmallInput,
                 );
             },
@@ -71,7 +75,7 @@ fn kruskal_comparison_benchmark(c: &mut Criterion) {
             |b, g| {
                 b.iter_batched(
                     || (QuickSortKruskal::new(g), StdRng::seed_from_u64(SEED)),
-                    |(mut algo, mut rng)| black_box(algo.run(&mut rng)),
+                    |(algo, mut rng)| black_box(algo.run(&mut rng)),
                     BatchSize::SmallInput,
                 );
             },
@@ -83,7 +87,31 @@ fn kruskal_comparison_benchmark(c: &mut Criterion) {
             |b, g| {
                 b.iter_batched(
                     || (FilterKruskal::new(g), StdRng::seed_from_u64(SEED)),
-                    |(mut algo, mut rng)| black_box(algo.run(&mut rng)),
+                    |(algo, mut rng)| black_box(algo.run(&mut rng)),
+                    BatchSize::SmallInput,
+                );
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("FilterPlus", &input_str),
+            &graph_matrix,
+            |b, g| {
+                b.iter_batched(
+                    || (FilterKruskalPlus::new(g), StdRng::seed_from_u64(SEED)),
+                    |(algo, mut rng)| black_box(algo.run(&mut rng)),
+                    BatchSize::SmallInput,
+                );
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("BoruvkaFilter", &input_str),
+            &graph_matrix,
+            |b, g| {
+                b.iter_batched(
+                    || (BoruvkaFilterKruskal::new(g), StdRng::seed_from_u64(SEED)),
+                    |(algo, mut rng)| black_box(algo.run(&mut rng)),
                     BatchSize::SmallInput,
                 );
             },
@@ -95,7 +123,80 @@ fn kruskal_comparison_benchmark(c: &mut Criterion) {
             |b, g| {
                 b.iter_batched(
                     || (SkewedFilterKruskal::new(g), StdRng::seed_from_u64(SEED)),
-                    |(mut algo, mut rng)| black_box(algo.run(&mut rng)),
+                    |(algo, mut rng)| black_box(algo.run(&mut rng)),
+                    BatchSize::SmallInput,
+                );
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("Bucket", &input_str),
+            &graph_matrix,
+            |b, g| {
+                b.iter_batched(
+                    || BucketKruskal::new(g),
+                    |algo| black_box(algo.run()),
+                    BatchSize::SmallInput,
+                );
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("Radix", &input_str),
+            &graph_matrix,
+            |b, g| {
+                b.iter_batched(
+                    || RadixKruskal::new(g),
+                    |algo| black_box(algo.run()),
+                    BatchSize::SmallInput,
+                );
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("DensePrim", &input_str),
+            &graph_matrix,
+            |b, g| {
+                b.iter_batched(
+                    || DensePrim::new(g),
+                    |algo| black_box(algo.run()),
+                    BatchSize::SmallInput,
+                );
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("Chazelle", &input_str),
+            &graph_matrix,
+            |b, g| {
+                b.iter_batched(
+                    || Chazelle::new(g),
+                    |algo| black_box(algo.run()),
+                    BatchSize::SmallInput,
+                );
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("Sort", &input_str),
+            &graph_matrix,
+            |b, g| {
+                b.iter_batched(
+                    || SortKruskal::new(g),
+                    |algo| black_box(algo.run()),
+                    BatchSize::SmallInput,
+                );
+            },
+        );
+
+        #[cfg(feature = "parallel")]
+        group.bench_with_input(
+            BenchmarkId::new("ParallelSort", &input_str),
+            &graph_matrix,
+            |b, g| {
+                b.iter_batched(
+                    || ParallelSortKruskal::new(g),
+                    |algo| black_box(algo.run()),
                     BatchSize::SmallInput,
                 );
             },
@@ -106,15 +207,191 @@ fn kruskal_comparison_benchmark(c: &mut Criterion) {
             &graph_stars,
             |b, g| {
                 b.iter_batched(
-                    || StarQuickSortKruskal::new(g),
-                    |mut algo| black_box(algo.run()),
+                    || (StarQuickSortKruskal::new(g), StdRng::seed_from_u64(SEED)),
+                    |(algo, mut rng)| black_box(algo.run(&mut rng)),
                     BatchSize::SmallInput,
                 );
             },
         );
+
+        group.bench_with_input(
+            BenchmarkId::new("SparsePrim", &input_str),
+            &graph_stars,
+            |b, g| {
+                b.iter_batched(
+                    || SparsePrim::new(g),
+                    |algo| black_box(algo.run()),
+                    BatchSize::SmallInput,
+                );
+            },
+        );
+    }
+    group.finish();
+}
+
+fn pivot_strategy_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pivot-strategies");
+
+    group.sample_size(10);
+
+    let (v, e) = (2_000, 10_000);
+    let p = e as f64 / (v * (v - 1) / 2) as f64;
+    const SEED: u64 = 0;
+
+    let graph_matrix = GraphMatrix::<usize>::new_random_seeded(0..v, p, 1, 1000, true, 42).unwrap();
+    let graph_stars = GraphStars::from(&graph_matrix);
+
+    let strategies = [
+        ("First", PivotStrategy::First),
+        ("Random", PivotStrategy::Random),
+        ("MedianOfThree", PivotStrategy::MedianOfThree),
+        ("Ninther", PivotStrategy::Ninther),
+        ("SkewedMinOf5", PivotStrategy::SkewedMinOf(5)),
+    ];
+
+    for (name, pivot) in strategies {
+        group.bench_with_input(BenchmarkId::new("Filter", name), &graph_matrix, |b, g| {
+            b.iter_batched(
+                || {
+                    (
+                        FilterKruskal::with_pivot(g, pivot),
+                        StdRng::seed_from_u64(SEED),
+                    )
+                },
+                |(algo, mut rng)| black_box(algo.run(&mut rng)),
+                BatchSize::SmallInput,
+            );
+        });
+        group.bench_with_input(BenchmarkId::new("StarQS", name), &graph_stars, |b, g| {
+            b.iter_batched(
+                || {
+                    (
+                        StarQuickSortKruskal::with_pivot(g, pivot),
+                        StdRng::seed_from_u64(SEED),
+                    )
+                },
+                |(algo, mut rng)| black_box(algo.run(&mut rng)),
+                BatchSize::SmallInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+fn three_way_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("three-way-partition");
+
+    group.sample_size(10);
+
+    let (v, e) = (2_000, 100_000);
+    let p = e as f64 / (v * (v - 1) / 2) as f64;
+    const SEED: u64 = 0;
+
+    // Few distinct weights, where the three-way split pays off, and many.
+    for weight_max in [3, 1000] {
+        let graph_matrix =
+            GraphMatrix::<usize>::new_random_seeded(0..v, p, 1, weight_max, true, 42).unwrap();
+
+        for three_way in [false, true] {
+            let input_str = format!("{}-max-weight-three-way-{}", weight_max, three_way);
+            group.bench_with_input(
+                BenchmarkId::new("Filter", &input_str),
+                &graph_matrix,
+                |b, g| {
+                    b.iter_batched(
+                        || {
+                            let mut algo = FilterKruskal::new(g);
+                            algo.set_three_way(three_way);
+                            (algo, StdRng::seed_from_u64(SEED))
+                        },
+                        |(algo, mut rng)| black_box(algo.run(&mut rng)),
+                        BatchSize::SmallInput,
+                    );
+                },
+            );
+            group.bench_with_input(
+                BenchmarkId::new("QuickSort", &input_str),
+                &graph_matrix,
+                |b, g| {
+                    b.iter_batched(
+                        || {
+                            let mut algo = QuickSortKruskal::new(g);
+                            algo.set_three_way(three_way);
+                            (algo, StdRng::seed_from_u64(SEED))
+                        },
+                        |(algo, mut rng)| black_box(algo.run(&mut rng)),
+                        BatchSize::SmallInput,
+                    );
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+// Sweep behind the thresholds of `auto::select`: dense Prim against the
+// sorting variants over the edge density, with a weight range narrower and
+// wider than the number of edges.
+fn auto_threshold_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("auto-thresholds");
+
+    group.sample_size(10);
+
+    let v = 1_500;
+    const SEED: u64 = 0;
+
+    for p in [0.05, 0.2, 0.4, 0.6, 0.7, 0.8, 0.85, 0.9, 0.95] {
+        let max_edges = v * (v - 1) / 2;
+        // Weight range from a 1024th of the edges to four times the edges.
+        for range_per_edge in [1.0 / 1024.0, 1.0 / 64.0, 0.25, 4.0] {
+            let weight_max = ((range_per_edge * p * max_edges as f64) as usize).max(2);
+            let graph_matrix =
+                GraphMatrix::<usize>::new_random_seeded(0..v, p, 1, weight_max, true, 42).unwrap();
+            let input_str = format!("{}-density-{}-range-per-edge", p, range_per_edge);
+
+            group.bench_with_input(
+                BenchmarkId::new("DensePrim", &input_str),
+                &graph_matrix,
+                |b, g| {
+                    b.iter_batched(
+                        || DensePrim::new(g),
+                        |algo| black_box(algo.run()),
+                        BatchSize::SmallInput,
+                    );
+                },
+            );
+            group.bench_with_input(
+                BenchmarkId::new("Bucket", &input_str),
+                &graph_matrix,
+                |b, g| {
+                    b.iter_batched(
+                        || BucketKruskal::new(g),
+                        |algo| black_box(algo.run()),
+                        BatchSize::SmallInput,
+                    );
+                },
+            );
+            group.bench_with_input(
+                BenchmarkId::new("Filter", &input_str),
+                &graph_matrix,
+                |b, g| {
+                    b.iter_batched(
+                        || (FilterKruskal::new(g), StdRng::seed_from_u64(SEED)),
+                        |(algo, mut rng)| black_box(algo.run(&mut rng)),
+                        BatchSize::SmallInput,
+                    );
+                },
+            );
+        }
     }
     group.finish();
 }
 
-criterion_group!(benches, kruskal_comparison_benchmark);
+criterion_group!(
+    benches,
+    kruskal_comparison_benchmark,
+    pivot_strategy_benchmark,
+    three_way_benchmark,
+    auto_threshold_benchmark
+);
 criterion_main!(benches);
@@ -0,0 +1,64 @@
+/*
+ * C interface of the mst_kruskal_variants crate (built with `--features ffi`).
+ * Keep in sync with `src/ffi.rs`.
+ */
+#ifndef MST_KRUSKAL_VARIANTS_H
+#define MST_KRUSKAL_VARIANTS_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define MST_OK 0
+#define MST_ERR_NULL_POINTER -1
+#define MST_ERR_VERTEX_OUT_OF_BOUNDS -2
+#define MST_ERR_UNKNOWN_ALGORITHM -3
+#define MST_ERR_DISCONNECTED -4
+#define MST_ERR_BUFFER_TOO_SMALL -5
+#define MST_ERR_OTHER -6
+#define MST_ERR_COST_OVERFLOW -7
+
+#define MST_KRUSKAL 0
+#define MST_QUICK_SORT_KRUSKAL 1
+#define MST_FILTER_KRUSKAL 2
+#define MST_SKEWED_FILTER_KRUSKAL 3
+#define MST_STAR_QUICK_SORT_KRUSKAL 4
+#define MST_BUCKET_KRUSKAL 5
+#define MST_RADIX_KRUSKAL 6
+#define MST_DENSE_PRIM 7
+#define MST_BORUVKA_FILTER_KRUSKAL 8
+#define MST_FILTER_KRUSKAL_PLUS 9
+#define MST_SPARSE_PRIM 10
+#define MST_CHAZELLE 11
+#define MST_SORT_KRUSKAL 12
+
+typedef struct MstGraph MstGraph;
+
+typedef struct MstEdge {
+    size_t from;
+    size_t to;
+    size_t weight;
+} MstEdge;
+
+MstGraph *mst_graph_new(size_t num_vertices);
+
+void mst_graph_free(MstGraph *graph);
+
+int32_t mst_graph_add_edge(MstGraph *graph, size_t from, size_t to, size_t cost);
+
+int32_t mst_run(const MstGraph *graph,
+                uint32_t algorithm,
+                uint64_t seed,
+                MstEdge *out_edges,
+                size_t capacity,
+                size_t *out_len,
+                size_t *out_cost);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* MST_KRUSKAL_VARIANTS_H */
@@ -0,0 +1,227 @@
+// # Algorithm selection
+//
+// Runtime selection of a variant, used by the language bindings to run
+// any algorithm on a plain edge list.
+use crate::certificate::Certificate;
+use crate::error::GraphError;
+use crate::graph::{Edge, Graph};
+use crate::graph_matrix::GraphMatrix;
+use crate::graph_stars::{EdgePolicy, GraphStars};
+use crate::mst_result::MstResult;
+use crate::partition::PivotStrategy;
+use crate::rng::SplitMix64;
+use crate::{
+    BoruvkaFilterKruskal, BucketKruskal, Chazelle, DensePrim, FilterKruskal, FilterKruskalPlus,
+    Kruskal, QuickSortKruskal, RadixKruskal, SkewedFilterKruskal, SortKruskal, SparsePrim,
+    StarQuickSortKruskal,
+};
+use rand_core::Rng;
+use std::fmt;
+use std::str::FromStr;
+
+// The available Kruskal variants.
+// # Note: With the `serde` feature variants are (de)serialized by `name`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Algorithm {
+    Kruskal,
+    QuickSortKruskal,
+    FilterKruskal,
+    SkewedFilterKruskal,
+    StarQuickSortKruskal,
+    BucketKruskal,
+    RadixKruskal,
+    DensePrim,
+    BoruvkaFilterKruskal,
+    FilterKruskalPlus,
+    SparsePrim,
+    Chazelle,
+    SortKruskal,
+}
+
+impl Algorithm {
+    pub const ALL: [Algorithm; 13] = [
+        Algorithm::Kruskal,
+        Algorithm::QuickSortKruskal,
+        Algorithm::FilterKruskal,
+        Algorithm::SkewedFilterKruskal,
+        Algorithm::StarQuickSortKruskal,
+        Algorithm::BucketKruskal,
+        Algorithm::RadixKruskal,
+        Algorithm::DensePrim,
+        Algorithm::BoruvkaFilterKruskal,
+        Algorithm::FilterKruskalPlus,
+        Algorithm::SparsePrim,
+        Algorithm::Chazelle,
+        Algorithm::SortKruskal,
+    ];
+
+    // Returns the snake_case name accepted by `from_str`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Algorithm::Kruskal => "kruskal",
+            Algorithm::QuickSortKruskal => "quick_sort_kruskal",
+            Algorithm::FilterKruskal => "filter_kruskal",
+            Algorithm::SkewedFilterKruskal => "skewed_filter_kruskal",
+            Algorithm::StarQuickSortKruskal => "star_quick_sort_kruskal",
+            Algorithm::BucketKruskal => "bucket_kruskal",
+            Algorithm::RadixKruskal => "radix_kruskal",
+            Algorithm::DensePrim => "dense_prim",
+            Algorithm::BoruvkaFilterKruskal => "boruvka_filter_kruskal",
+            Algorithm::FilterKruskalPlus => "filter_kruskal_plus",
+            Algorithm::SparsePrim => "sparse_prim",
+            Algorithm::Chazelle => "chazelle",
+            Algorithm::SortKruskal => "sort_kruskal",
+        }
+    }
+
+    // Builds the representation the variant works on from an edge list and
+    // runs it, `rng` is only used by the randomized variants.
+    // # Note: Self-loops are ignored, parallel edges collapse to the
+    // #       cheapest so that every variant sees the same graph.
+    pub fn run_on_edges<R: Rng + ?Sized>(
+        &self,
+        num_vertices: usize,
+        edges: &[Edge],
+        rng: &mut R,
+    ) -> Result<MstResult, GraphError> {
+        self.run_tuned(num_vertices, edges, None, None, rng)
+    }
+
+    // Returns the pivot strategy the variant uses by default, None if it
+    // doesn't partition.
+    pub fn default_pivot(&self) -> Option<PivotStrategy> {
+        match self {
+            Algorithm::QuickSortKruskal
+            | Algorithm::StarQuickSortKruskal
+            | Algorithm::FilterKruskal
+            | Algorithm::BoruvkaFilterKruskal
+            | Algorithm::FilterKruskalPlus => Some(PivotStrategy::Random),
+            Algorithm::SkewedFilterKruskal => Some(PivotStrategy::SkewedMinOf(5)),
+            _ => None,
+        }
+    }
+
+    // Same as `run_on_edges` with the pivot strategy of the partitioning
+    // variants and the Kruskal threshold of Filter Kruskal overridden when
+    // given, the other variants ignoring them.
+    pub(crate) fn run_tuned<R: Rng + ?Sized>(
+        &self,
+        num_vertices: usize,
+        edges: &[Edge],
+        pivot: Option<PivotStrategy>,
+        kruskal_threshold: Option<usize>,
+        rng: &mut R,
+    ) -> Result<MstResult, GraphError> {
+        if let Some(edge) = edges
+            .iter()
+            .find(|e| e.from >= num_vertices || e.to >= num_vertices)
+        {
+            return Err(GraphError::VertexOutOfBounds {
+                vertex: edge.from.max(edge.to),
+                num_vertices,
+            });
+        }
+        let loopless = edges.iter().filter(|e| e.from != e.to);
+        let pivot = pivot
+            .or(self.default_pivot())
+            .unwrap_or(PivotStrategy::Random);
+
+        if let Algorithm::StarQuickSortKruskal | Algorithm::SparsePrim = self {
+            let mut graph = GraphStars::with_edge_policy(EdgePolicy::KeepMin);
+            for id in 0..num_vertices {
+                graph.add_vertex(id);
+            }
+            for edge in loopless {
+                graph.add_edge(edge.from, edge.to, edge.weight)?;
+            }
+            return match self {
+                Algorithm::SparsePrim => SparsePrim::new(&graph).run(),
+                _ => {
+                    let mut algo = StarQuickSortKruskal::from_graph(graph);
+                    algo.set_pivot(pivot);
+                    algo.run(rng)
+                }
+            };
+        }
+
+        let mut graph = GraphMatrix::new_from_collection(0..num_vertices);
+        for edge in loopless {
+            if graph
+                .edge_weight(edge.from, edge.to)
+                .is_none_or(|w| edge.weight < w)
+            {
+                graph.add_edge(edge.from, edge.to, edge.weight)?;
+            }
+        }
+        match self {
+            Algorithm::Kruskal => Kruskal::new(&graph).run(),
+            Algorithm::QuickSortKruskal => QuickSortKruskal::with_pivot(&graph, pivot).run(rng),
+            Algorithm::FilterKruskal => {
+                let mut algo = FilterKruskal::with_pivot(&graph, pivot);
+                if let Some(threshold) = kruskal_threshold {
+                    algo.set_kruskal_threshold(threshold);
+                }
+                algo.run(rng)
+            }
+            Algorithm::SkewedFilterKruskal => {
+                SkewedFilterKruskal::with_pivot(&graph, pivot).run(rng)
+            }
+            Algorithm::BucketKruskal => BucketKruskal::new(&graph).run(),
+            Algorithm::RadixKruskal => RadixKruskal::new(&graph).run(),
+            Algorithm::DensePrim => DensePrim::new(&graph).run(),
+            Algorithm::BoruvkaFilterKruskal => {
+                BoruvkaFilterKruskal::with_pivot(&graph, pivot).run(rng)
+            }
+            Algorithm::FilterKruskalPlus => FilterKruskalPlus::with_pivot(&graph, pivot).run(rng),
+            Algorithm::Chazelle => Chazelle::new(&graph).run(),
+            Algorithm::SortKruskal => SortKruskal::new(&graph).run(),
+            Algorithm::StarQuickSortKruskal | Algorithm::SparsePrim => unreachable!(),
+        }
+    }
+
+    // Same as `run_on_edges` with a `SplitMix64` seeded with `seed`.
+    // # Example: Algorithm::FilterKruskal.run_seeded(n, &edges, 42)?;
+    pub fn run_seeded(
+        &self,
+        num_vertices: usize,
+        edges: &[Edge],
+        seed: u64,
+    ) -> Result<MstResult, GraphError> {
+        self.run_on_edges(num_vertices, edges, &mut SplitMix64::new(seed))
+    }
+
+    // Runs the variant like `run_on_edges` and also returns the certificate
+    // of the tree, see `check_certificate`.
+    pub fn run_certified<R: Rng + ?Sized>(
+        &self,
+        num_vertices: usize,
+        edges: &[Edge],
+        rng: &mut R,
+    ) -> Result<(MstResult, Certificate), GraphError> {
+        let tree = self.run_on_edges(num_vertices, edges, rng)?;
+        let certificate = Certificate::new(num_vertices, edges, &tree);
+        Ok((tree, certificate))
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = GraphError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Algorithm::ALL
+            .into_iter()
+            .find(|algorithm| algorithm.name() == name)
+            .ok_or_else(|| GraphError::UnknownAlgorithm(name.to_string()))
+    }
+}
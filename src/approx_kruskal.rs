@@ -0,0 +1,142 @@
+// # Approximate Kruskal
+//
+// Approximate minimum spanning tree for graphs too large to hold in memory:
+// the MSF of a random sample of the edges is patched into a spanning tree
+// with the edges crossing its components.
+use crate::constants::*;
+use crate::error::GraphError;
+use crate::graph::Edge;
+use crate::mst_result::{to_cost, MstResult};
+use crate::rng;
+use crate::union_find::UnionFind;
+use rand_core::Rng;
+
+// Approximate tree with the data needed to judge its quality.
+#[derive(Clone, Debug)]
+pub struct ApproximateMst {
+    pub mst: MstResult,
+    // Lower bound on the optimal cost (first Borůvka round).
+    pub lower_bound: Cost,
+    pub sampled_edges: usize,
+    pub patch_edges: usize,
+}
+
+impl ApproximateMst {
+    // Upper bound on cost / optimal cost, 1.0 means the tree is optimal.
+    pub fn ratio_bound(&self) -> f64 {
+        if self.lower_bound == 0 {
+            return if self.mst.cost == 0 {
+                1.0
+            } else {
+                f64::INFINITY
+            };
+        }
+        self.mst.cost as f64 / self.lower_bound as f64
+    }
+}
+
+pub struct ApproximateKruskal {
+    num_vertices: usize,
+    sample_rate: f64,
+}
+
+impl ApproximateKruskal {
+    // Constructs the algorithm for a graph of `num_vertices` vertices where
+    // each edge is sampled with probability `sample_rate`.
+    pub fn new(num_vertices: usize, sample_rate: f64) -> Result<Self, GraphError> {
+        if !(0.0..=1.0).contains(&sample_rate) {
+            return Err(GraphError::InvalidProbability(sample_rate));
+        }
+        Ok(ApproximateKruskal {
+            num_vertices,
+            sample_rate,
+        })
+    }
+
+    // Runs the algorithm, `edges` is called once per pass (twice) so the
+    // edge set can be streamed from disk instead of held in memory.
+    // # Example: algo.run(|| graph.all_edges(), &mut rng)
+    // # Note: Memory is O(n + p·m + crossing edges).
+    pub fn run<E, I, R>(&self, edges: E, rng: &mut R) -> Result<ApproximateMst, GraphError>
+    where
+        E: Fn() -> I,
+        I: IntoIterator<Item = Edge>,
+        R: Rng + ?Sized,
+    {
+        let n = self.num_vertices;
+
+        // First pass: sample the edges and find the lightest edge of each
+        // vertex, ties broken by `Edge::key` so they form a forest.
+        let mut sample = Vec::new();
+        let mut lightest: Vec<Option<Edge>> = vec![None; n];
+        for edge in edges() {
+            if edge.from >= n || edge.to >= n {
+                return Err(GraphError::VertexOutOfBounds {
+                    vertex: edge.from.max(edge.to),
+                    num_vertices: n,
+                });
+            }
+            if edge.from == edge.to {
+                continue;
+            }
+            for v in [edge.from, edge.to] {
+                if lightest[v].is_none_or(|l| edge.key() < l.key()) {
+                    lightest[v] = Some(edge);
+                }
+            }
+            if rng::unit(rng) < self.sample_rate {
+                sample.push(edge);
+            }
+        }
+        let lower_bound = boruvka_lower_bound(&lightest);
+        let sampled_edges = sample.len();
+
+        let mut forest = kruskal_forest(n, sample);
+        let mut components = UnionFind::new(n);
+        for edge in &forest {
+            components.union(edge.from, edge.to);
+        }
+
+        // Second pass: keep the edges crossing the sampled forest's components.
+        let mut patch_edges = 0;
+        for edge in edges() {
+            if components.find(edge.from) != components.find(edge.to) {
+                forest.push(edge);
+                patch_edges += 1;
+            }
+        }
+
+        let tree = kruskal_forest(n, forest);
+        let cost = tree.iter().map(|e| e.weight as CostSum).sum();
+        Ok(ApproximateMst {
+            mst: MstResult::from_forest(n, tree, cost)?,
+            lower_bound: to_cost(lower_bound)?,
+            sampled_edges,
+            patch_edges,
+        })
+    }
+}
+
+// Cost of the distinct lightest incident edges, a subset of a MST.
+fn boruvka_lower_bound(lightest: &[Option<Edge>]) -> CostSum {
+    let mut chosen: Vec<_> = lightest.iter().flatten().map(Edge::key).collect();
+    chosen.sort_unstable();
+    chosen.dedup();
+    chosen.iter().map(|&(weight, _, _)| weight as CostSum).sum()
+}
+
+// Minimum spanning forest of an edge list by sorting.
+fn kruskal_forest(num_vertices: usize, mut edges: Vec<Edge>) -> Vec<Edge> {
+    edges.sort_unstable_by_key(Edge::key);
+    let mut union_find = UnionFind::new(num_vertices);
+    let mut forest = Vec::new();
+    for edge in edges {
+        if forest.len() + 1 >= num_vertices {
+            break;
+        }
+        if union_find.union(edge.from, edge.to) {
+            forest.push(edge);
+        }
+    }
+    forest
+}
@@ -0,0 +1,67 @@
+// # Auto
+//
+// Dispatch to the variant expected to be fastest for a given graph, looking
+// only at n, m and the weight range.
+//
+// The thresholds come from the `auto-thresholds` group of the benchmarks,
+// release runs on G(1500, p) graphs for p in 0.05..0.95 and weight ranges
+// from m/1024 to 4m: the O(n^2) dense Prim overtakes Filter Kruskal from 85%
+// density whatever the weights, and counting sort only wins on the sparse
+// graphs (5% density), Filter Kruskal discarding most of the heavy edges of
+// the denser ones without sorting them.
+use crate::algorithm::Algorithm;
+use crate::error::GraphError;
+use crate::graph::Graph;
+use crate::graph_matrix::GraphMatrix;
+use crate::mst_result::MstResult;
+use crate::rng::SplitMix64;
+use crate::{BucketKruskal, DensePrim, FilterKruskal};
+
+// Edge density (m over n(n-1)/2) from which dense Prim is chosen.
+pub const DENSE_PRIM_DENSITY: f64 = 0.85;
+
+// Edge density under which the bucket sort is chosen.
+pub const BUCKET_DENSITY: f64 = 0.1;
+
+// Maximum number of possible weights per edge for the bucket sort.
+pub const BUCKET_RANGE_PER_EDGE: usize = 4;
+
+// Seed of the pivot choices, so that automatic runs are reproducible.
+const SEED: u64 = 0;
+
+// Returns the variant `minimum_spanning_tree` runs on this graph.
+pub fn select(graph: &GraphMatrix<usize>) -> Algorithm {
+    let n = graph.num_vertices();
+    let edges = graph.cached_edges();
+    let m = graph.num_edges();
+
+    let max_edges = n * n.saturating_sub(1) / 2;
+    let density = if max_edges > 0 {
+        m as f64 / max_edges as f64
+    } else {
+        0.0
+    };
+    if density >= DENSE_PRIM_DENSITY {
+        return Algorithm::DensePrim;
+    }
+
+    let min_cost = edges.iter().map(|e| e.weight).min();
+    let max_cost = edges.iter().map(|e| e.weight).max();
+    if let (Some(min_cost), Some(max_cost)) = (min_cost, max_cost)
+        && density < BUCKET_DENSITY
+        && max_cost - min_cost < m.saturating_mul(BUCKET_RANGE_PER_EDGE)
+    {
+        return Algorithm::BucketKruskal;
+    }
+
+    Algorithm::FilterKruskal
+}
+
+// Computes the minimum spanning tree with the variant picked by `select`.
+pub fn minimum_spanning_tree(graph: &GraphMatrix<usize>) -> Result<MstResult, GraphError> {
+    match select(graph) {
+        Algorithm::DensePrim => DensePrim::new(graph).run(),
+        Algorithm::BucketKruskal => BucketKruskal::new(graph).run(),
+        _ => FilterKruskal::new(graph).run(&mut SplitMix64::new(SEED)),
+    }
+}
@@ -0,0 +1,162 @@
+// # Borůvka Filter Kruskal
+//
+// Hybrid running Borůvka rounds until the components shrink by
+// `BORUVKA_SHRINK_FACTOR`, then Filter Kruskal on the contracted graph.
+use crate::constants::*;
+use crate::contraction::contract_edges;
+use crate::error::GraphError;
+use crate::filter_kruskal::{filter_kruskal, DEFAULT_KRUSKAL_THRESHOLD};
+use crate::graph::{Edge, Graph};
+use crate::graph_matrix::GraphMatrix;
+use crate::memory::HeapBytes;
+use crate::mst_result::MstResult;
+use crate::partition::PivotStrategy;
+use crate::reuse::{Reusable, TreeBuffer};
+use crate::rng::SplitMix64;
+use crate::union_find::UnionFind;
+use rand_core::Rng;
+
+// Borůvka rounds stop once the vertices outnumber the components this much.
+pub const BORUVKA_SHRINK_FACTOR: usize = 4;
+
+pub struct BoruvkaFilterKruskal {
+    num_vertices: usize,
+    edges: Vec<Edge>,
+    pivot: PivotStrategy,
+    union_find: UnionFind,
+    tree: TreeBuffer,
+}
+
+impl BoruvkaFilterKruskal {
+    // Constructs the algorithm structures with random pivots.
+    pub fn new(graph: &GraphMatrix<usize>) -> Self {
+        Self::with_pivot(graph, PivotStrategy::Random)
+    }
+
+    // Constructs the algorithm structures, `pivot` is used by the Filter
+    // Kruskal phase.
+    pub fn with_pivot(graph: &GraphMatrix<usize>, pivot: PivotStrategy) -> Self {
+        let num_vertices = graph.num_vertices();
+        BoruvkaFilterKruskal {
+            num_vertices,
+            edges: graph.all_edges(),
+            pivot,
+            union_find: UnionFind::new(num_vertices),
+            tree: TreeBuffer::default(),
+        }
+    }
+
+    // Joins every component to another through its cheapest outgoing edge,
+    // returning the number of tree edges added.
+    // # Note: Cheapest is by `Edge::key`, a total order, so no cycle forms.
+    fn boruvka_round(&mut self, keep_edges: bool) -> usize {
+        // Roots don't change while scanning, find them once per vertex.
+        let roots: Vec<usize> = (0..self.num_vertices)
+            .map(|v| self.union_find.find(v))
+            .collect();
+        let mut cheapest: Vec<Option<Edge>> = vec![None; self.num_vertices];
+        // Drop the edges inside a component while scanning.
+        self.edges.retain(|edge| {
+            let (a, b) = (roots[edge.from], roots[edge.to]);
+            if a == b {
+                return false;
+            }
+            for root in [a, b] {
+                if cheapest[root].is_none_or(|c| edge.key() < c.key()) {
+                    cheapest[root] = Some(*edge);
+                }
+            }
+            true
+        });
+
+        let mut added = 0;
+        for edge in cheapest.into_iter().flatten() {
+            // Both endpoints' components may have chosen the same edge.
+            if self.union_find.union(edge.from, edge.to) {
+                self.tree.add(edge, keep_edges);
+                added += 1;
+            }
+        }
+        added
+    }
+
+    // Runs the Borůvka rounds and the Filter Kruskal phase, returning the
+    // number of tree edges found. The edges themselves are only kept if
+    // `keep_edges` is set.
+    fn grow<R: Rng + ?Sized>(&mut self, rng: &mut R, keep_edges: bool) -> usize {
+        let mut count = 0;
+        let mut components = self.num_vertices;
+        while components * BORUVKA_SHRINK_FACTOR > self.num_vertices {
+            let added = self.boruvka_round(keep_edges);
+            if added == 0 {
+                break;
+            }
+            count += added;
+            components -= added;
+        }
+
+        let components = contract_edges(self.num_vertices, &mut self.edges, &mut self.union_find);
+        // Only `components - 1` edges are left to find.
+        let (added, cost) = filter_kruskal(
+            &mut self.edges,
+            components,
+            &mut self.union_find,
+            keep_edges.then_some(&mut self.tree.edges),
+            self.pivot,
+            DEFAULT_KRUSKAL_THRESHOLD,
+            false,
+            rng,
+        );
+        self.tree.cost += cost;
+        count + added
+    }
+
+    // Runs the algorithm on another graph, reusing the buffers of the
+    // previous run, see `Reusable`.
+    pub fn run_on<R: Rng + ?Sized>(
+        &mut self,
+        graph: &GraphMatrix<usize>,
+        rng: &mut R,
+    ) -> Result<MstResult, GraphError> {
+        self.reset_with(graph);
+        self.grow(rng, true);
+        self.tree.result(self.num_vertices)
+    }
+
+    // Returns an estimate of the heap bytes held by the algorithm: the edge
+    // buffer, the union-find and the tree.
+    pub fn memory_footprint(&self) -> usize {
+        self.edges.heap_bytes() + self.union_find.memory_footprint() + self.tree.memory_footprint()
+    }
+
+    // Runs the algorithm and returns a set of edges representing the minimum
+    // spanning tree and its associated total cost, consuming the algorithm
+    // (see `Reusable`).
+    pub fn run<R: Rng + ?Sized>(mut self, rng: &mut R) -> Result<MstResult, GraphError> {
+        self.grow(rng, true);
+        self.tree.into_result(self.num_vertices)
+    }
+
+    // Runs the algorithm and returns only the total cost of the minimum
+    // spanning tree, its edges are never stored.
+    pub fn run_cost<R: Rng + ?Sized>(mut self, rng: &mut R) -> Result<Cost, GraphError> {
+        let count = self.grow(rng, false);
+        self.tree.total(self.num_vertices, count)
+    }
+
+    // Same as `run` with a `SplitMix64` seeded with `seed`, for reproducible
+    // runs without picking a generator.
+    pub fn run_seeded(self, seed: u64) -> Result<MstResult, GraphError> {
+        self.run(&mut SplitMix64::new(seed))
+    }
+}
+
+impl Reusable<&GraphMatrix<usize>> for BoruvkaFilterKruskal {
+    fn reset_with(&mut self, graph: &GraphMatrix<usize>) {
+        self.num_vertices = graph.num_vertices();
+        self.edges.clear();
+        self.edges.extend_from_slice(graph.cached_edges());
+        self.union_find.reset(self.num_vertices);
+        self.tree.clear();
+    }
+}
@@ -0,0 +1,187 @@
+// # Bucket Kruskal
+//
+// Implementation of the Kruskal algorithm sorting the edges with a counting
+// sort over the weight range, O(m + C) for C distinct possible costs.
+// Wider ranges fall back to a comparison sort, O(m log m).
+use crate::constants::*;
+use crate::error::GraphError;
+use crate::graph::{sort_ties, Edge, Graph};
+use crate::graph_matrix::GraphMatrix;
+use crate::memory::HeapBytes;
+use crate::mst_result::MstResult;
+use crate::reuse::{Reusable, TreeBuffer};
+use crate::union_find::UnionFind;
+
+// Weight ranges wider than this many buckets per edge are sorted by
+// comparison instead, the buckets would cost more than the sort.
+pub const MAX_BUCKETS_PER_EDGE: usize = 4;
+
+pub struct BucketKruskal {
+    num_vertices: usize,
+    edges: Vec<Edge>,
+    min_cost: Cost,
+    max_cost: Cost,
+    union_find: UnionFind,
+    tree: TreeBuffer,
+}
+
+impl BucketKruskal {
+    // Constructs the algorithm structures detecting the weight range
+    // with a scan of the edges.
+    pub fn new(graph: &GraphMatrix<usize>) -> Self {
+        let edges = graph.all_edges();
+        let min_cost = edges.iter().map(|e| e.weight).min().unwrap_or(ZERO_COST);
+        let max_cost = edges.iter().map(|e| e.weight).max().unwrap_or(ZERO_COST);
+        Self::from_parts(graph.num_vertices(), edges, min_cost, max_cost)
+    }
+
+    // Constructs the algorithm structures with a known weight range.
+    // # Note: Every edge is still checked against the range, O(m).
+    pub fn with_cost_range(
+        graph: &GraphMatrix<usize>,
+        min_cost: Cost,
+        max_cost: Cost,
+    ) -> Result<Self, GraphError> {
+        if min_cost > max_cost {
+            return Err(GraphError::InvalidCostRange {
+                min: min_cost,
+                max: max_cost,
+            });
+        }
+        let edges = graph.all_edges();
+        if let Some(edge) = edges
+            .iter()
+            .find(|e| e.weight < min_cost || e.weight > max_cost)
+        {
+            return Err(GraphError::CostOutOfRange {
+                cost: edge.weight,
+                min: min_cost,
+                max: max_cost,
+            });
+        }
+        Ok(Self::from_parts(
+            graph.num_vertices(),
+            edges,
+            min_cost,
+            max_cost,
+        ))
+    }
+
+    fn from_parts(num_vertices: usize, edges: Vec<Edge>, min_cost: Cost, max_cost: Cost) -> Self {
+        BucketKruskal {
+            num_vertices,
+            edges,
+            min_cost,
+            max_cost,
+            union_find: UnionFind::new(num_vertices),
+            tree: TreeBuffer::default(),
+        }
+    }
+
+    // Counting sort of the edges by weight, ties sorted by `Edge::key`.
+    // # Note: Falls back to a comparison sort when the range is wider than
+    // #       `MAX_BUCKETS_PER_EDGE` buckets per edge (or `usize::MAX`).
+    fn bucket_sort(&mut self) {
+        let max_buckets = self.edges.len().saturating_mul(MAX_BUCKETS_PER_EDGE);
+        let Some(num_buckets) = (self.max_cost - self.min_cost)
+            .checked_add(1)
+            .filter(|&n| n <= max_buckets)
+        else {
+            self.edges.sort_unstable_by_key(Edge::key);
+            return;
+        };
+
+        // Bucket sizes, then turned into each bucket's starting position
+        let mut starts = vec![0; num_buckets];
+        for edge in &self.edges {
+            starts[edge.weight - self.min_cost] += 1;
+        }
+        let mut pos = 0;
+        for start in starts.iter_mut() {
+            let size = *start;
+            *start = pos;
+            pos += size;
+        }
+
+        let mut sorted = vec![Edge::new(0, 0, 0); self.edges.len()];
+        for edge in &self.edges {
+            let bucket = edge.weight - self.min_cost;
+            sorted[starts[bucket]] = *edge;
+            starts[bucket] += 1;
+        }
+        sort_ties(&mut sorted);
+        self.edges = sorted;
+    }
+
+    // Sorts and scans the edges, returning the number of tree edges found.
+    // The edges themselves are only kept if `keep_edges` is set.
+    fn grow(&mut self, keep_edges: bool) -> usize {
+        if !self.edges.is_empty() {
+            self.bucket_sort();
+        }
+
+        let mut count = 0;
+        for edge in &self.edges {
+            if count + 1 >= self.num_vertices {
+                break;
+            }
+            if self.union_find.union(edge.from, edge.to) {
+                self.tree.add(*edge, keep_edges);
+                count += 1;
+            }
+        }
+        count
+    }
+
+    // Runs the algorithm on another graph, reusing the buffers of the
+    // previous run, see `Reusable`.
+    pub fn run_on(&mut self, graph: &GraphMatrix<usize>) -> Result<MstResult, GraphError> {
+        self.reset_with(graph);
+        self.grow(true);
+        self.tree.result(self.num_vertices)
+    }
+
+    // Returns an estimate of the heap bytes held by the algorithm: the edge
+    // buffer, the union-find and the tree.
+    pub fn memory_footprint(&self) -> usize {
+        self.edges.heap_bytes() + self.union_find.memory_footprint() + self.tree.memory_footprint()
+    }
+
+    // Runs the algorithm and returns a set of edges representing the minimum
+    // spanning tree and its associated total cost, consuming the algorithm
+    // (see `Reusable`).
+    pub fn run(mut self) -> Result<MstResult, GraphError> {
+        self.grow(true);
+        self.tree.into_result(self.num_vertices)
+    }
+
+    // Runs the algorithm and returns only the total cost of the minimum
+    // spanning tree, its edges are never stored.
+    pub fn run_cost(mut self) -> Result<Cost, GraphError> {
+        let count = self.grow(false);
+        self.tree.total(self.num_vertices, count)
+    }
+}
+
+impl Reusable<&GraphMatrix<usize>> for BucketKruskal {
+    // The weight range is detected again with a scan of the edges.
+    fn reset_with(&mut self, graph: &GraphMatrix<usize>) {
+        self.num_vertices = graph.num_vertices();
+        self.edges.clear();
+        self.edges.extend_from_slice(graph.cached_edges());
+        self.min_cost = self
+            .edges
+            .iter()
+            .map(|e| e.weight)
+            .min()
+            .unwrap_or(ZERO_COST);
+        self.max_cost = self
+            .edges
+            .iter()
+            .map(|e| e.weight)
+            .max()
+            .unwrap_or(ZERO_COST);
+        self.union_find.reset(self.num_vertices);
+        self.tree.clear();
+    }
+}
@@ -0,0 +1,250 @@
+// # Certificate
+//
+// Machine-checkable witness that a spanning tree is minimum: every tree
+// edge comes with a cut it's a lightest edge across, and every other edge
+// with the tree path joining its endpoints, on which it's the heaviest.
+// `check_certificate` validates one without trusting the algorithm.
+use crate::constants::*;
+use crate::error::GraphError;
+use crate::graph::{check_edge, Edge};
+use crate::mst_result::MstResult;
+use crate::union_find::UnionFind;
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, Default)]
+pub struct Certificate {
+    // For each tree edge, the vertices of one side of its cut.
+    pub cuts: Vec<(Edge, Vec<VertexId>)>,
+    // For each non-tree edge, the tree path from one endpoint to the other.
+    pub paths: Vec<(Edge, Vec<VertexId>)>,
+}
+
+// Vertices of an undirected edge in increasing order.
+fn endpoints(edge: &Edge) -> (VertexId, VertexId) {
+    (edge.from.min(edge.to), edge.from.max(edge.to))
+}
+
+fn invalid(message: String) -> GraphError {
+    GraphError::InvalidCertificate(message)
+}
+
+impl Certificate {
+    // Builds the certificate of the spanning tree `tree` of the graph with
+    // `num_vertices` vertices and the given edges, whichever algorithm
+    // found it. Cuts are the subtrees hanging from the tree edges.
+    // # Note: O(n^2 + n m) time and space in the worst case, meant for
+    // #       debugging. Self-loops are skipped.
+    // # Panic: `tree` must span the graph of `edges`.
+    pub fn new(num_vertices: usize, edges: &[Edge], tree: &MstResult) -> Self {
+        let n = num_vertices;
+        let mut adjacency = vec![Vec::new(); n];
+        for edge in &tree.edges {
+            adjacency[edge.from].push(edge.to);
+            adjacency[edge.to].push(edge.from);
+        }
+
+        // Preorder of every tree so that each subtree is contiguous.
+        let mut parent = vec![usize::MAX; n];
+        let mut depth = vec![0; n];
+        let mut order = Vec::with_capacity(n);
+        let mut visited = vec![false; n];
+        for root in 0..n {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            let mut stack = vec![root];
+            while let Some(u) = stack.pop() {
+                order.push(u);
+                for &v in &adjacency[u] {
+                    if !visited[v] {
+                        visited[v] = true;
+                        parent[v] = u;
+                        depth[v] = depth[u] + 1;
+                        stack.push(v);
+                    }
+                }
+            }
+        }
+        let mut position = vec![0; n];
+        for (i, &v) in order.iter().enumerate() {
+            position[v] = i;
+        }
+        let mut size = vec![1; n];
+        for &v in order.iter().rev() {
+            if parent[v] != usize::MAX {
+                size[parent[v]] += size[v];
+            }
+        }
+
+        let cuts = tree
+            .edges
+            .iter()
+            .map(|edge| {
+                let child = if parent[edge.to] == edge.from {
+                    edge.to
+                } else {
+                    edge.from
+                };
+                let start = position[child];
+                (*edge, order[start..start + size[child]].to_vec())
+            })
+            .collect();
+
+        let mut in_tree: HashMap<(Cost, VertexId, VertexId), usize> = HashMap::new();
+        for edge in &tree.edges {
+            *in_tree.entry(edge.key()).or_default() += 1;
+        }
+        let mut paths = Vec::new();
+        for edge in edges.iter().filter(|e| e.from != e.to) {
+            if let Some(count) = in_tree.get_mut(&edge.key())
+                && *count > 0
+            {
+                *count -= 1;
+                continue;
+            }
+            // Climb from both ends to their common ancestor.
+            let (mut u, mut v) = (edge.from, edge.to);
+            let (mut up, mut down) = (vec![u], vec![v]);
+            while u != v {
+                if depth[u] >= depth[v] {
+                    u = parent[u];
+                    up.push(u);
+                } else {
+                    v = parent[v];
+                    down.push(v);
+                }
+            }
+            down.pop();
+            up.extend(down.into_iter().rev());
+            paths.push((*edge, up));
+        }
+        Certificate { cuts, paths }
+    }
+}
+
+// Checks that `certificate` proves `tree` to be a minimum spanning tree of
+// the graph with `num_vertices` vertices and the given edges, relying only
+// on the cut and cycle properties.
+// # Note: O(n m) time, self-loops are ignored.
+pub fn check_certificate(
+    num_vertices: usize,
+    edges: &[Edge],
+    tree: &MstResult,
+    certificate: &Certificate,
+) -> Result<(), GraphError> {
+    let n = num_vertices;
+    let edges: Vec<&Edge> = edges.iter().filter(|e| e.from != e.to).collect();
+    for edge in edges.iter().copied().chain(&tree.edges) {
+        check_edge(edge.from, edge.to, n)?;
+    }
+
+    // The tree must be a spanning tree made of graph edges.
+    let mut available: HashMap<(Cost, VertexId, VertexId), usize> = HashMap::new();
+    for edge in &edges {
+        *available.entry(edge.key()).or_default() += 1;
+    }
+    let mut union_find = UnionFind::new(n);
+    let mut tree_weights: HashMap<(VertexId, VertexId), Cost> = HashMap::new();
+    for edge in &tree.edges {
+        match available.get_mut(&edge.key()) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => return Err(invalid(format!("tree edge {:?} isn't in the graph", edge))),
+        }
+        if !union_find.union(edge.from, edge.to) {
+            return Err(invalid(format!("tree edge {:?} closes a cycle", edge)));
+        }
+        tree_weights.insert(endpoints(edge), edge.weight);
+    }
+    if n > 0 && tree.edges.len() != n - 1 {
+        return Err(invalid(format!(
+            "{} tree edges don't span {} vertices",
+            tree.edges.len(),
+            n
+        )));
+    }
+    let cost: CostSum = tree.edges.iter().map(|e| e.weight as CostSum).sum();
+    if cost != tree.cost as CostSum {
+        return Err(invalid(format!(
+            "tree cost {} doesn't match its edges' {}",
+            tree.cost, cost
+        )));
+    }
+
+    // Cut property: each tree edge is a lightest edge across its cut.
+    let cuts: HashMap<_, _> = certificate
+        .cuts
+        .iter()
+        .map(|(edge, side)| (edge.key(), side))
+        .collect();
+    for edge in &tree.edges {
+        let side = cuts
+            .get(&edge.key())
+            .ok_or_else(|| invalid(format!("no cut for tree edge {:?}", edge)))?;
+        let mut inside = vec![false; n];
+        for &v in side.iter() {
+            if v >= n {
+                return Err(invalid(format!("cut vertex {} out of bounds", v)));
+            }
+            inside[v] = true;
+        }
+        if inside[edge.from] == inside[edge.to] {
+            return Err(invalid(format!(
+                "tree edge {:?} doesn't cross its cut",
+                edge
+            )));
+        }
+        if let Some(lighter) = edges
+            .iter()
+            .find(|e| inside[e.from] != inside[e.to] && e.weight < edge.weight)
+        {
+            return Err(invalid(format!(
+                "{:?} crosses the cut of tree edge {:?} and is lighter",
+                lighter, edge
+            )));
+        }
+    }
+
+    // Cycle property: each other edge is a heaviest edge on its tree path.
+    let paths: HashMap<_, _> = certificate
+        .paths
+        .iter()
+        .map(|(edge, path)| (edge.key(), path))
+        .collect();
+    for edge in edges {
+        match available.get_mut(&edge.key()) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => continue,
+        }
+        let path = paths
+            .get(&edge.key())
+            .ok_or_else(|| invalid(format!("no path for non-tree edge {:?}", edge)))?;
+        let ends = (path.first(), path.last());
+        if ends != (Some(&edge.from), Some(&edge.to)) && ends != (Some(&edge.to), Some(&edge.from))
+        {
+            return Err(invalid(format!(
+                "path of {:?} doesn't join its endpoints",
+                edge
+            )));
+        }
+        for step in path.windows(2) {
+            let pair = (step[0].min(step[1]), step[0].max(step[1]));
+            match tree_weights.get(&pair) {
+                Some(&weight) if weight <= edge.weight => {}
+                Some(_) => {
+                    return Err(invalid(format!(
+                        "path of {:?} has a heavier tree edge {:?}",
+                        edge, pair
+                    )))
+                }
+                None => {
+                    return Err(invalid(format!(
+                        "path of {:?} leaves the tree at {:?}",
+                        edge, pair
+                    )))
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,291 @@
+// # Chazelle
+//
+// Simplified version of Chazelle's soft heap based algorithm. Clusters are
+// grown Prim-style with a soft heap, which makes them contractible once the
+// corrupted edges B are set aside, so the MST lies within the MSFs of the
+// clusters, the MSF of the contracted graph (found recursively) and B.
+// # Note: Clusters have a fixed size and a plain Kruskal combines the
+// #       candidates, so the O(m α(m, n)) bound of the original doesn't hold.
+use crate::constants::*;
+use crate::error::GraphError;
+use crate::graph::{Edge, Graph};
+use crate::graph_matrix::GraphMatrix;
+use crate::heaps::SoftHeap;
+use crate::memory::HeapBytes;
+use crate::mst_result::MstResult;
+use crate::reuse::{Reusable, TreeBuffer};
+use crate::union_find::UnionFind;
+
+// Error rate of the soft heaps used by `new`.
+pub const DEFAULT_EPSILON: f64 = 1.0 / 64.0;
+
+// Number of vertices a cluster stops growing at.
+const CLUSTER_SIZE: usize = 32;
+
+// Levels of contraction before falling back to Kruskal.
+const MAX_DEPTH: usize = 8;
+
+// Original key of an edge, see `Edge::key`.
+type Key = (Cost, VertexId, VertexId);
+
+// Edge of a contraction level: its endpoints there and its index in the
+// original edges.
+type LevelEdge = (VertexId, VertexId, usize);
+
+// Soft heap item: the original key of the edge, its original index and
+// its index in the level.
+type Item = (Key, usize, usize);
+
+pub struct Chazelle {
+    num_vertices: usize,
+    edges: Vec<Edge>,
+    epsilon: f64,
+    tree: TreeBuffer,
+}
+
+impl Chazelle {
+    // Constructs the algorithm structures.
+    pub fn new(graph: &GraphMatrix<usize>) -> Self {
+        Self::with_epsilon(graph, DEFAULT_EPSILON)
+    }
+
+    // Constructs the algorithm structures, `epsilon` is the error rate of
+    // the soft heaps: lower means fewer corrupted edges but slower heaps.
+    // # Panic: `epsilon` must be in (0, 1).
+    pub fn with_epsilon(graph: &GraphMatrix<usize>, epsilon: f64) -> Self {
+        assert!(
+            epsilon > 0.0 && epsilon < 1.0,
+            "the error rate must be in (0, 1)"
+        );
+        Chazelle {
+            num_vertices: graph.num_vertices(),
+            edges: graph.all_edges(),
+            epsilon,
+            tree: TreeBuffer::default(),
+        }
+    }
+
+    // Computes the spanning forest, returning the number of tree edges
+    // found. The edges themselves are only kept if `keep_edges` is set.
+    fn grow(&mut self, keep_edges: bool) -> usize {
+        let keys: Vec<Key> = self.edges.iter().map(Edge::key).collect();
+        let level: Vec<LevelEdge> = self
+            .edges
+            .iter()
+            .enumerate()
+            .map(|(id, e)| (e.from, e.to, id))
+            .collect();
+        let forest = msf(self.num_vertices, &level, &keys, self.epsilon, MAX_DEPTH);
+        for &i in &forest {
+            let edge = self.edges[level[i].2];
+            self.tree.add(edge, keep_edges);
+        }
+        forest.len()
+    }
+
+    // Runs the algorithm on another graph, reusing the buffers of the
+    // previous run, see `Reusable`.
+    pub fn run_on(&mut self, graph: &GraphMatrix<usize>) -> Result<MstResult, GraphError> {
+        self.reset_with(graph);
+        self.grow(true);
+        self.tree.result(self.num_vertices)
+    }
+
+    // Returns an estimate of the heap bytes held by the algorithm: the edge
+    // buffer and the tree.
+    pub fn memory_footprint(&self) -> usize {
+        self.edges.heap_bytes() + self.tree.memory_footprint()
+    }
+
+    // Runs the algorithm and returns a set of edges representing the minimum
+    // spanning tree and its associated total cost, consuming the algorithm
+    // (see `Reusable`).
+    pub fn run(mut self) -> Result<MstResult, GraphError> {
+        self.grow(true);
+        self.tree.into_result(self.num_vertices)
+    }
+
+    // Runs the algorithm and returns only the total cost of the minimum
+    // spanning tree, its edges are never stored.
+    pub fn run_cost(mut self) -> Result<Cost, GraphError> {
+        let count = self.grow(false);
+        self.tree.total(self.num_vertices, count)
+    }
+}
+
+impl Reusable<&GraphMatrix<usize>> for Chazelle {
+    fn reset_with(&mut self, graph: &GraphMatrix<usize>) {
+        self.num_vertices = graph.num_vertices();
+        self.edges.clear();
+        self.edges.extend_from_slice(graph.cached_edges());
+        self.tree.clear();
+    }
+}
+
+// Returns the indices in `edges` of the minimum spanning forest of a level
+// with `n` vertices, edges being compared by their original key.
+fn msf(n: usize, edges: &[LevelEdge], keys: &[Key], epsilon: f64, depth: usize) -> Vec<usize> {
+    if depth == 0 || edges.len() <= n {
+        return kruskal(n, edges, keys, (0..edges.len()).collect());
+    }
+
+    let (cluster, num_clusters, corrupted) = grow_clusters(n, edges, keys, epsilon);
+    if num_clusters == n {
+        return kruskal(n, edges, keys, (0..edges.len()).collect());
+    }
+
+    // Internal edges of the clusters and edges between them, without B.
+    let (internal, inter): (Vec<usize>, Vec<usize>) = (0..edges.len())
+        .filter(|&i| !corrupted[i])
+        .partition(|&i| cluster[edges[i].0] == cluster[edges[i].1]);
+
+    // The MSFs of disjoint clusters are found at once.
+    let mut candidates = kruskal(n, edges, keys, internal);
+    let inter = cheapest_parallel(num_clusters, edges, keys, &cluster, inter);
+    let contracted: Vec<LevelEdge> = inter
+        .iter()
+        .map(|&i| (cluster[edges[i].0], cluster[edges[i].1], edges[i].2))
+        .collect();
+    let forest = msf(num_clusters, &contracted, keys, epsilon, depth - 1);
+    candidates.extend(forest.into_iter().map(|j| inter[j]));
+    candidates.extend((0..edges.len()).filter(|&i| corrupted[i]));
+    kruskal(n, edges, keys, candidates)
+}
+
+// Partitions the vertices into clusters grown like Prim trees with a soft
+// heap, returning the cluster of each vertex, their number and which edges
+// got corrupted in some heap.
+// # Note: A cluster stops at `CLUSTER_SIZE` vertices or when its cheapest
+// #       edge leads to another cluster, which it then joins unless the edge
+// #       is corrupted. Either way its MST edges under the corrupted keys
+// #       stay connected.
+fn grow_clusters(
+    n: usize,
+    edges: &[LevelEdge],
+    keys: &[Key],
+    epsilon: f64,
+) -> (Vec<usize>, usize, Vec<bool>) {
+    // Incident edges of each vertex in compressed rows.
+    let mut offsets = vec![0; n + 1];
+    for &(a, b, _) in edges {
+        offsets[a + 1] += 1;
+        offsets[b + 1] += 1;
+    }
+    for v in 0..n {
+        offsets[v + 1] += offsets[v];
+    }
+    let mut incident = vec![0; offsets[n]];
+    let mut next = offsets.clone();
+    for (i, &(a, b, _)) in edges.iter().enumerate() {
+        for v in [a, b] {
+            incident[next[v]] = i;
+            next[v] += 1;
+        }
+    }
+
+    let mut cluster = vec![usize::MAX; n];
+    let mut corrupted = vec![false; edges.len()];
+    let mut num_clusters = 0;
+    for start in 0..n {
+        if cluster[start] != usize::MAX {
+            continue;
+        }
+        let id = num_clusters;
+        num_clusters += 1;
+
+        let mut heap: SoftHeap<Item> = SoftHeap::new(epsilon);
+        let add = |v: usize, heap: &mut SoftHeap<Item>, cluster: &mut [usize]| {
+            cluster[v] = id;
+            for &i in &incident[offsets[v]..offsets[v + 1]] {
+                let (a, b, original) = edges[i];
+                if cluster[a] != id || cluster[b] != id {
+                    heap.insert((keys[original], original, i));
+                }
+            }
+        };
+        add(start, &mut heap, &mut cluster);
+
+        let mut members = vec![start];
+        while members.len() < CLUSTER_SIZE {
+            let Some((item, ckey)) = heap.extract_min() else {
+                break;
+            };
+            let i = item.2;
+            corrupted[i] |= ckey != item;
+            let (a, b, _) = edges[i];
+            let v = if cluster[a] == id { b } else { a };
+            if cluster[v] == id {
+                continue;
+            }
+            if cluster[v] != usize::MAX {
+                // An earlier cluster is final, so if the edge wasn't corrupted
+                // there either it's in the MST and the union stays contractible.
+                if !corrupted[i] {
+                    let target = cluster[v];
+                    for &u in &members {
+                        cluster[u] = target;
+                    }
+                    num_clusters -= 1;
+                }
+                break;
+            }
+            add(v, &mut heap, &mut cluster);
+            members.push(v);
+        }
+        // The keys left in the heap may be corrupted too.
+        for (item, ckey) in heap.drain() {
+            corrupted[item.2] |= ckey != item;
+        }
+    }
+    (cluster, num_clusters, corrupted)
+}
+
+// Keeps only the cheapest of the edges joining the same two clusters, the
+// others can't be in the MSF of the contracted graph.
+// # Note: O(m) bucketing the edges by the cluster of one endpoint.
+fn cheapest_parallel(
+    num_clusters: usize,
+    edges: &[LevelEdge],
+    keys: &[Key],
+    cluster: &[usize],
+    inter: Vec<usize>,
+) -> Vec<usize> {
+    let endpoints = |i: usize| {
+        let (a, b) = (cluster[edges[i].0], cluster[edges[i].1]);
+        (a.min(b), a.max(b))
+    };
+    let key = |i: usize| (keys[edges[i].2], edges[i].2);
+
+    let mut buckets = vec![Vec::new(); num_clusters];
+    for i in inter {
+        buckets[endpoints(i).0].push(i);
+    }
+    // Cheapest edge towards each cluster from the current bucket.
+    let mut best: Vec<Option<usize>> = vec![None; num_clusters];
+    let mut kept = Vec::new();
+    for bucket in buckets {
+        for &i in &bucket {
+            let to = endpoints(i).1;
+            if best[to].is_none_or(|b| key(i) < key(b)) {
+                best[to] = Some(i);
+            }
+        }
+        for &i in &bucket {
+            if let Some(b) = best[endpoints(i).1].take() {
+                kept.push(b);
+            }
+        }
+    }
+    kept
+}
+
+// Returns the indices in `edges` of the minimum spanning forest of the
+// `candidates`, sorted by original key.
+fn kruskal(n: usize, edges: &[LevelEdge], keys: &[Key], mut candidates: Vec<usize>) -> Vec<usize> {
+    candidates.sort_unstable_by_key(|&i| (keys[edges[i].2], edges[i].2));
+    let mut union_find = UnionFind::new(n);
+    candidates
+        .into_iter()
+        .filter(|&i| union_find.union(edges[i].0, edges[i].1))
+        .collect()
+}
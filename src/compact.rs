@@ -0,0 +1,181 @@
+// # Compact Edges
+//
+// Edges with `u32` endpoints and weight, 12 bytes instead of the 24 of
+// `Edge`, for edge lists that only fit in memory at half the size. Kruskal
+// sorts them in place, so the only other memory is the union-find and the
+// tree. Enabled by the `compact` feature.
+use crate::constants::*;
+use crate::error::GraphError;
+use crate::graph::Edge;
+use crate::mst_result::MstResult;
+use crate::reuse::{Reusable, TreeBuffer};
+use crate::union_find::UnionFind;
+use std::cmp::Ordering;
+
+// An edge with 32-bit endpoints and weight.
+// # Note: `repr(C)` so that edge arrays can be shared with other tools.
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct CompactEdge {
+    pub from: u32,
+    pub to: u32,
+    pub weight: u32,
+}
+
+impl CompactEdge {
+    pub fn new(from: u32, to: u32, weight: u32) -> CompactEdge {
+        CompactEdge { from, to, weight }
+    }
+
+    // Returns the canonical ordering key, as `Edge::key`.
+    #[inline]
+    pub fn key(&self) -> (u32, u32, u32) {
+        (self.weight, self.from.min(self.to), self.from.max(self.to))
+    }
+}
+
+impl From<CompactEdge> for Edge {
+    fn from(edge: CompactEdge) -> Edge {
+        Edge::new(
+            edge.from as VertexId,
+            edge.to as VertexId,
+            edge.weight as Cost,
+        )
+    }
+}
+
+impl TryFrom<Edge> for CompactEdge {
+    type Error = GraphError;
+
+    // Fails with `VertexOutOfBounds` or `CostOutOfRange` if the endpoints or
+    // the weight don't fit in 32 bits.
+    fn try_from(edge: Edge) -> Result<Self, Self::Error> {
+        let vertex = edge.from.max(edge.to);
+        let from = u32::try_from(edge.from);
+        let to = u32::try_from(edge.to);
+        let (Ok(from), Ok(to)) = (from, to) else {
+            return Err(GraphError::VertexOutOfBounds {
+                vertex,
+                num_vertices: u32::MAX as usize + 1,
+            });
+        };
+        let weight = u32::try_from(edge.weight).map_err(|_| GraphError::CostOutOfRange {
+            cost: edge.weight,
+            min: 0,
+            max: u32::MAX as Cost,
+        })?;
+        Ok(CompactEdge { from, to, weight })
+    }
+}
+
+// Compact edges are ordered and compared by `CompactEdge::key`.
+impl Ord for CompactEdge {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl PartialOrd for CompactEdge {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for CompactEdge {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for CompactEdge {}
+
+// Kruskal on a borrowed slice of compact edges, sorted in place.
+// # Note: The order of `edges` is changed by the run.
+pub struct CompactKruskal<'a> {
+    num_vertices: usize,
+    edges: &'a mut [CompactEdge],
+    tree: TreeBuffer,
+}
+
+impl<'a> CompactKruskal<'a> {
+    // Constructs the algorithm for a graph of `num_vertices` vertices.
+    pub fn new(num_vertices: usize, edges: &'a mut [CompactEdge]) -> Self {
+        CompactKruskal {
+            num_vertices,
+            edges,
+            tree: TreeBuffer::default(),
+        }
+    }
+
+    // Sorts the edges and scans them, returning the number of tree edges
+    // found. The edges themselves are only kept if `keep_edges` is set.
+    fn grow(&mut self, keep_edges: bool) -> Result<usize, GraphError> {
+        let n = self.num_vertices;
+        if let Some(edge) = self
+            .edges
+            .iter()
+            .find(|e| e.from as usize >= n || e.to as usize >= n)
+        {
+            return Err(GraphError::VertexOutOfBounds {
+                vertex: edge.from.max(edge.to) as VertexId,
+                num_vertices: n,
+            });
+        }
+        self.edges.sort_unstable_by_key(CompactEdge::key);
+
+        let mut union_find = UnionFind::new(n);
+        let mut count = 0;
+        for &edge in self.edges.iter() {
+            if count + 1 >= n {
+                break;
+            }
+            if union_find.union(edge.from as VertexId, edge.to as VertexId) {
+                self.tree.add(edge.into(), keep_edges);
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    // Runs the algorithm on another edge list, reusing the buffer of the
+    // tree edges, see `Reusable`.
+    pub fn run_on(
+        &mut self,
+        num_vertices: usize,
+        edges: &'a mut [CompactEdge],
+    ) -> Result<MstResult, GraphError> {
+        self.reset_with((num_vertices, edges));
+        self.grow(true)?;
+        self.tree.result(self.num_vertices)
+    }
+
+    // Returns an estimate of the heap bytes held by the algorithm: the tree,
+    // the borrowed edges aren't counted.
+    pub fn memory_footprint(&self) -> usize {
+        self.tree.memory_footprint()
+    }
+
+    // Runs the algorithm and returns a set of edges representing the minimum
+    // spanning tree and its associated total cost, consuming the algorithm
+    // (see `Reusable`).
+    pub fn run(mut self) -> Result<MstResult, GraphError> {
+        self.grow(true)?;
+        self.tree.into_result(self.num_vertices)
+    }
+
+    // Runs the algorithm and returns only the total cost of the minimum
+    // spanning tree, its edges are never stored.
+    pub fn run_cost(mut self) -> Result<Cost, GraphError> {
+        let count = self.grow(false)?;
+        self.tree.total(self.num_vertices, count)
+    }
+}
+
+// The graph is given as the number of vertices and the edge list.
+impl<'a> Reusable<(usize, &'a mut [CompactEdge])> for CompactKruskal<'a> {
+    fn reset_with(&mut self, (num_vertices, edges): (usize, &'a mut [CompactEdge])) {
+        self.num_vertices = num_vertices;
+        self.edges = edges;
+        self.tree.clear();
+    }
+}
@@ -0,0 +1,143 @@
+// # Connectivity
+//
+// Connected components of a graph, to check an input before running a
+// variant on it or to split it into parts that can be handled apart, and
+// the bridges and articulation points whose removal splits a component.
+use crate::constants::VertexId;
+use crate::graph::{Edge, Graph};
+use crate::union_find::UnionFind;
+
+// Returns the component of each vertex, numbered from 0 in the order of
+// their smallest vertex.
+// # Note: O(n + m α(n)) with a union-find. Self-loops are ignored.
+pub fn components<T, G: Graph<T>>(graph: &G) -> Vec<usize> {
+    let n = graph.num_vertices();
+    let mut union_find = UnionFind::new(n);
+    for edge in graph.all_edges() {
+        union_find.union(edge.from, edge.to);
+    }
+    let mut labels = vec![usize::MAX; n];
+    let mut count = 0;
+    (0..n)
+        .map(|v| {
+            let root = union_find.find(v);
+            if labels[root] == usize::MAX {
+                labels[root] = count;
+                count += 1;
+            }
+            labels[root]
+        })
+        .collect()
+}
+
+// Returns the number of connected components, 0 for the empty graph.
+pub fn num_components<T, G: Graph<T>>(graph: &G) -> usize {
+    components(graph).iter().max().map_or(0, |&last| last + 1)
+}
+
+// Returns true if every vertex can reach every other one, as required by
+// the variants returning a spanning tree.
+pub fn is_connected<T, G: Graph<T>>(graph: &G) -> bool {
+    num_components(graph) <= 1
+}
+
+// Returns the bridges of the graph, the edges whose removal disconnects
+// their component, in the order of `all_edges`. They are exactly the edges
+// in every spanning tree of the graph.
+// # Note: O(n + m), see `lowlink`. Parallel edges are never bridges.
+pub fn bridges<T, G: Graph<T>>(graph: &G) -> Vec<Edge> {
+    let edges = graph.all_edges();
+    let (is_bridge, _) = lowlink(&adjacency(graph.num_vertices(), &edges), edges.len());
+    edges
+        .into_iter()
+        .zip(is_bridge)
+        .filter_map(|(edge, bridge)| bridge.then_some(edge))
+        .collect()
+}
+
+// Returns the articulation points of the graph, the vertices whose removal
+// disconnects their component, in increasing order.
+// # Note: O(n + m), see `lowlink`.
+pub fn articulation_points<T, G: Graph<T>>(graph: &G) -> Vec<VertexId> {
+    let edges = graph.all_edges();
+    let (_, is_cut) = lowlink(&adjacency(graph.num_vertices(), &edges), edges.len());
+    (0..is_cut.len()).filter(|&v| is_cut[v]).collect()
+}
+
+// Returns the neighbours of each vertex along with the index of the edge
+// leading to them.
+fn adjacency(num_vertices: usize, edges: &[Edge]) -> Vec<Vec<(VertexId, usize)>> {
+    let mut adjacency = vec![Vec::new(); num_vertices];
+    for (e, edge) in edges.iter().enumerate() {
+        adjacency[edge.from].push((edge.to, e));
+        adjacency[edge.to].push((edge.from, e));
+    }
+    adjacency
+}
+
+// Flags the bridges among the `num_edges` edges and the articulation points
+// of the multigraph whose neighbours, with the index of the edge leading
+// to them, are given by `adjacency`.
+// # Note: O(n + m) with an iterative Tarjan lowlink DFS. Only the edge a
+// #       vertex was entered by is skipped, so a parallel edge counts as a
+// #       back edge.
+pub(crate) fn lowlink(
+    adjacency: &[Vec<(usize, usize)>],
+    num_edges: usize,
+) -> (Vec<bool>, Vec<bool>) {
+    let n = adjacency.len();
+    let mut is_bridge = vec![false; num_edges];
+    let mut is_cut = vec![false; n];
+    // Discovery time of each vertex, 0 for unvisited, and the earliest one
+    // reachable from its subtree with a single back edge.
+    let mut discovered = vec![0; n];
+    let mut low = vec![0; n];
+    let mut time = 0;
+    for root in 0..n {
+        if discovered[root] != 0 {
+            continue;
+        }
+        time += 1;
+        discovered[root] = time;
+        low[root] = time;
+        let mut root_children = 0;
+        // Vertices with the edge they were entered by and the number of
+        // their neighbours already visited.
+        let mut stack = vec![(root, usize::MAX, 0)];
+        while let Some(&mut (u, entry, ref mut next)) = stack.last_mut() {
+            if let Some(&(v, e)) = adjacency[u].get(*next) {
+                *next += 1;
+                if e == entry {
+                    continue;
+                }
+                if discovered[v] == 0 {
+                    time += 1;
+                    discovered[v] = time;
+                    low[v] = time;
+                    if u == root {
+                        root_children += 1;
+                    }
+                    stack.push((v, e, 0));
+                } else {
+                    low[u] = low[u].min(discovered[v]);
+                }
+            } else {
+                stack.pop();
+                if let Some(&(parent, _, _)) = stack.last() {
+                    low[parent] = low[parent].min(low[u]);
+                    if low[u] > discovered[parent] {
+                        is_bridge[entry] = true;
+                    }
+                    // A subtree that can't reach above its parent is cut
+                    // off by removing it, the root only if it has several
+                    // subtrees.
+                    if parent != root && low[u] >= discovered[parent] {
+                        is_cut[parent] = true;
+                    }
+                }
+            }
+        }
+        is_cut[root] = root_children > 1;
+    }
+    (is_bridge, is_cut)
+}
@@ -0,0 +1,97 @@
+// # Consistency
+//
+// Cross-check of every variant on the same graph: the trees must have the
+// same cost and each must come with a valid certificate. A regression
+// harness for the crate and for code built on it.
+use crate::algorithm::Algorithm;
+use crate::certificate::check_certificate;
+use crate::constants::Cost;
+use crate::error::GraphError;
+use crate::graph::{Edge, Graph};
+use crate::rng::SplitMix64;
+use std::fmt;
+
+// Why a variant disagreed with the others.
+#[derive(Debug, Clone)]
+pub enum Discrepancy {
+    // The variant returned no tree.
+    Failed(GraphError),
+    // The tree costs differently from the first variant's.
+    Cost { expected: Cost, found: Cost },
+    // The tree isn't a minimum spanning tree, see `check_certificate`.
+    InvalidTree(GraphError),
+}
+
+// The first variant that disagreed and the graph it disagreed on, as an
+// edge list that can be saved to reproduce it.
+#[derive(Debug, Clone)]
+pub struct Mismatch {
+    pub algorithm: Algorithm,
+    pub discrepancy: Discrepancy,
+    pub num_vertices: usize,
+    pub edges: Vec<Edge>,
+    pub seed: u64,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} disagreed on a graph with {} vertices and {} edges (seed {}): ",
+            self.algorithm,
+            self.num_vertices,
+            self.edges.len(),
+            self.seed
+        )?;
+        match &self.discrepancy {
+            Discrepancy::Failed(error) => write!(f, "{}", error),
+            Discrepancy::Cost { expected, found } => {
+                write!(f, "cost {} instead of {}", found, expected)
+            }
+            Discrepancy::InvalidTree(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for Mismatch {}
+
+// Runs every variant on `graph`, each with a `SplitMix64` seeded with `seed`,
+// and returns the common cost of their trees.
+// # Example: consistency::check(&graph, 42).map_err(|m| m.to_string())?;
+// # Note: The graph must be connected, a variant failing is reported as a
+// #       mismatch. O(n m) per variant to check the certificates.
+pub fn check<T, G: Graph<T>>(graph: &G, seed: u64) -> Result<Cost, Mismatch> {
+    check_edges(graph.num_vertices(), &graph.all_edges(), seed)
+}
+
+// Same as `check` on a plain edge list, see `Algorithm::run_on_edges`.
+pub fn check_edges(num_vertices: usize, edges: &[Edge], seed: u64) -> Result<Cost, Mismatch> {
+    let mismatch = |algorithm, discrepancy| Mismatch {
+        algorithm,
+        discrepancy,
+        num_vertices,
+        edges: edges.to_vec(),
+        seed,
+    };
+
+    let mut expected = None;
+    for algorithm in Algorithm::ALL {
+        let mut rng = SplitMix64::new(seed);
+        let (tree, certificate) = algorithm
+            .run_certified(num_vertices, edges, &mut rng)
+            .map_err(|e| mismatch(algorithm, Discrepancy::Failed(e)))?;
+        check_certificate(num_vertices, edges, &tree, &certificate)
+            .map_err(|e| mismatch(algorithm, Discrepancy::InvalidTree(e)))?;
+        match expected {
+            Some(cost) if cost != tree.cost => {
+                let discrepancy = Discrepancy::Cost {
+                    expected: cost,
+                    found: tree.cost,
+                };
+                return Err(mismatch(algorithm, discrepancy));
+            }
+            _ => expected = Some(tree.cost),
+        }
+    }
+    Ok(expected.expect("there is at least one variant"))
+}
@@ -5,6 +5,10 @@
 pub type VertexId = usize;
 pub type EdgeId = usize;
 pub type Cost = usize;
+// Total cost of a tree, wide enough that summing n - 1 costs never wraps.
+pub type CostSum = u128;
+// Signed weight for the graphs solved through the `signed` module.
+pub type ICost = i64;
 
 pub type HeapPos = usize;
 
@@ -0,0 +1,82 @@
+// # Contraction
+//
+// Contraction of the components found so far, shared by the variants that
+// shrink the graph before finishing it with another algorithm, and the
+// quotient graph of a partition of the vertices.
+use crate::constants::VertexId;
+use crate::graph::{Edge, Graph};
+use crate::graph_stars::GraphStars;
+use crate::union_find::UnionFind;
+use std::collections::HashMap;
+
+// Contracts the components of `union_find` in place, keeping only the
+// edges between different components, and returns their number.
+// # Note: Edges keep their original endpoints, so the same union find
+// #       still applies and ties are broken as in the original graph.
+// #       Parallel edges between two components are kept. O(n + m).
+pub(crate) fn contract_edges(
+    num_vertices: usize,
+    edges: &mut Vec<Edge>,
+    union_find: &mut UnionFind,
+) -> usize {
+    let roots: Vec<usize> = (0..num_vertices).map(|v| union_find.find(v)).collect();
+    edges.retain(|e| roots[e.from] != roots[e.to]);
+    roots.iter().enumerate().filter(|&(v, &r)| v == r).count()
+}
+
+// Returns the quotient graph where each component of `union_find` is merged
+// into a super-vertex, with the cheapest edge between each pair of
+// components, and the super-vertex of each vertex.
+// # Example: let (quotient, ids) = contract(&graph, &union_find);
+// # Note: Super-vertices are numbered by their smallest vertex and labelled
+// #       with their id, ties between edges are broken by `Edge::key` in
+// #       the original graph. O(n log n + m) expected.
+// # Panic: If `union_find` doesn't have one element per vertex.
+pub fn contract<T, G: Graph<T>>(
+    graph: &G,
+    union_find: &UnionFind,
+) -> (GraphStars<usize>, Vec<VertexId>) {
+    let num_vertices = graph.num_vertices();
+    assert_eq!(
+        union_find.len(),
+        num_vertices,
+        "the union find must have one element per vertex"
+    );
+
+    let mut super_ids = vec![usize::MAX; num_vertices];
+    let mut mapping = Vec::with_capacity(num_vertices);
+    let mut num_components = 0;
+    for vertex in 0..num_vertices {
+        let root = union_find.root(vertex);
+        if super_ids[root] == usize::MAX {
+            super_ids[root] = num_components;
+            num_components += 1;
+        }
+        mapping.push(super_ids[root]);
+    }
+
+    let mut cheapest: HashMap<(VertexId, VertexId), Edge> = HashMap::new();
+    for edge in graph.all_edges() {
+        let (a, b) = (mapping[edge.from], mapping[edge.to]);
+        if a == b {
+            continue;
+        }
+        cheapest
+            .entry((a.min(b), a.max(b)))
+            .and_modify(|best| {
+                if edge.key() < best.key() {
+                    *best = edge;
+                }
+            })
+            .or_insert(edge);
+    }
+    let mut pairs: Vec<_> = cheapest.into_iter().collect();
+    pairs.sort_unstable_by_key(|&(pair, _)| pair);
+
+    let quotient = GraphStars::from_edges(
+        num_components,
+        pairs.into_iter().map(|((a, b), e)| (a, b, e.weight)),
+    )
+    .expect("the endpoints are super-vertices");
+    (quotient, mapping)
+}
@@ -0,0 +1,230 @@
+// # Directed Graph Matrix
+//
+// Directed graph representation storing the full adjacency matrix.
+use crate::constants::{Cost, VertexId};
+use crate::error::GraphError;
+use crate::graph::{check_bounds, Edge, Graph, Vertex};
+use crate::graph_matrix::GraphMatrix;
+use crate::memory::HeapBytes;
+use crate::MAX_COST;
+#[cfg(feature = "rand")]
+use rand::{
+    distr::{Distribution, Uniform},
+    rngs::StdRng,
+    Rng, RngExt, SeedableRng,
+};
+
+// Directed graph representation using a flattened square matrix.
+pub struct DiGraphMatrix<T> {
+    vertices: Vec<Vertex<T>>,
+    adj_matrix: Vec<Cost>,
+    cached_edges: Vec<Edge>,
+    out_degrees: Vec<usize>,
+}
+
+impl<T: Clone + Eq> Default for DiGraphMatrix<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone + Eq> DiGraphMatrix<T> {
+    pub fn new() -> Self {
+        DiGraphMatrix {
+            vertices: Vec::new(),
+            // # Note: Stored by square shells so adding a vertex only appends,
+            // #       see `index`.
+            adj_matrix: Vec::new(),
+            cached_edges: Vec::new(),
+            out_degrees: Vec::new(),
+        }
+    }
+
+    // Constructs a graph from a generic collection's iterator
+    // # Example: let g = DiGraphMatrix::new_from_collection(vec![1,2,3,4]);
+    pub fn new_from_collection<K: IntoIterator<Item = T>>(collection: K) -> Self {
+        let mut this = DiGraphMatrix::new();
+        for v in collection {
+            this.add_vertex(v);
+        }
+        this
+    }
+
+    // Constructs a random digraph using Erdős–Rényi model G(n, p) over the
+    // ordered pairs, with uniform random costs and a generic collection.
+    // Input:
+    // - p probability of selecting an arc
+    // - min_cost minimum cost value
+    // - max_cost maximum cost value
+    //
+    // # Note: O(n^2) complexity.
+    #[cfg(feature = "rand")]
+    pub fn new_random<K, R>(
+        collection: K,
+        p: f64,
+        min_cost: usize,
+        max_cost: usize,
+        no_self_loops: bool,
+        rng: &mut R,
+    ) -> Result<Self, GraphError>
+    where
+        K: IntoIterator<Item = T>,
+        R: Rng + ?Sized,
+    {
+        if !(0.0..=1.0).contains(&p) {
+            return Err(GraphError::InvalidProbability(p));
+        }
+        if min_cost > max_cost {
+            return Err(GraphError::InvalidCostRange {
+                min: min_cost,
+                max: max_cost,
+            });
+        }
+
+        let mut graph = DiGraphMatrix::new_from_collection(collection);
+        let cost_dist = Uniform::new_inclusive(min_cost, max_cost).unwrap();
+        let num_vertices = graph.num_vertices();
+
+        for from_idx in 0..num_vertices {
+            for to_idx in 0..num_vertices {
+                if no_self_loops && from_idx == to_idx {
+                    continue;
+                }
+                if rng.random::<f64>() < p {
+                    let cost = cost_dist.sample(rng);
+                    graph.add_edge(from_idx, to_idx, cost)?;
+                }
+            }
+        }
+        Ok(graph)
+    }
+
+    // Same as `new_random` but draws from a `StdRng` seeded with `seed`.
+    #[cfg(feature = "rand")]
+    pub fn new_random_seeded<K>(
+        collection: K,
+        p: f64,
+        min_cost: usize,
+        max_cost: usize,
+        no_self_loops: bool,
+        seed: u64,
+    ) -> Result<Self, GraphError>
+    where
+        K: IntoIterator<Item = T>,
+    {
+        let mut rng = StdRng::seed_from_u64(seed);
+        Self::new_random(collection, p, min_cost, max_cost, no_self_loops, &mut rng)
+    }
+
+    // Calculates index in the flattened matrix: shell k = max(row, col)
+    // starts at k^2 and holds row k (k + 1 cells) then column k (k cells).
+    fn index(&self, row: VertexId, col: VertexId) -> usize {
+        if row >= col {
+            row * row + col
+        } else {
+            col * col + col + 1 + row
+        }
+    }
+
+    // Returns the cost of the arc from -> to, if any.
+    pub(crate) fn cost(&self, from: VertexId, to: VertexId) -> Option<Cost> {
+        match self.adj_matrix[self.index(from, to)] {
+            MAX_COST => None,
+            cost => Some(cost),
+        }
+    }
+
+    // Returns the undirected graph keeping the cheapest arc between each
+    // pair of vertices, to run the MST algorithms on it.
+    // # Note: Self-loops are dropped.
+    pub fn to_undirected(&self) -> GraphMatrix<T> {
+        let mut graph =
+            GraphMatrix::new_from_collection(self.vertices.iter().map(|v| v.data.clone()));
+        for edge in &self.cached_edges {
+            if edge.from == edge.to {
+                continue;
+            }
+            let reverse = self.cost(edge.to, edge.from).unwrap_or(MAX_COST);
+            if edge.weight <= reverse {
+                graph
+                    .add_edge(edge.from, edge.to, edge.weight)
+                    .expect("the vertices are copied");
+            }
+        }
+        graph
+    }
+
+    // Returns an estimate of the heap bytes held by the graph.
+    // # Note: Heap memory owned by the vertex data isn't counted.
+    pub fn memory_footprint(&self) -> usize {
+        self.vertices.heap_bytes()
+            + self.adj_matrix.heap_bytes()
+            + self.cached_edges.heap_bytes()
+            + self.out_degrees.heap_bytes()
+    }
+}
+
+impl<T: Clone + Eq> Graph<T> for DiGraphMatrix<T> {
+    // Adds a node to the structure and a new shell to the matrix.
+    fn add_vertex(&mut self, data: T) -> usize {
+        let id = self.vertices.len();
+        self.vertices.push(Vertex { id, data });
+        self.out_degrees.push(0);
+        self.adj_matrix.resize((id + 1) * (id + 1), MAX_COST);
+        id
+    }
+
+    // Adds a weighted arc from -> to.
+    fn add_edge(&mut self, from: VertexId, to: VertexId, cost: Cost) -> Result<(), GraphError> {
+        check_bounds(from, to, self.num_vertices())?;
+        let index = self.index(from, to);
+        // If it's a new arc add it to the cache
+        if self.adj_matrix[index] == MAX_COST {
+            self.cached_edges.push(Edge::new(from, to, cost));
+            self.out_degrees[from] += 1;
+        }
+        self.adj_matrix[index] = cost;
+        Ok(())
+    }
+
+    // Returns a vertex structure from a vertex identifier.
+    fn vertex(&self, id: VertexId) -> Option<&Vertex<T>> {
+        self.vertices.get(id)
+    }
+
+    // Returns the vertices without a copy.
+    fn vertices(&self) -> &[Vertex<T>] {
+        &self.vertices
+    }
+
+    // Returns the total number of vertices
+    fn num_vertices(&self) -> usize {
+        self.vertices.len()
+    }
+
+    // Returns the number of cached arcs.
+    // # Note: O(1).
+    fn num_edges(&self) -> usize {
+        self.cached_edges.len()
+    }
+
+    // Returns a vector of all cached arcs
+    fn all_edges(&self) -> Vec<Edge> {
+        self.cached_edges.clone()
+    }
+
+    // Returns the cost of the arc from -> to, if any.
+    // # Note: O(1) matrix lookup.
+    fn edge_weight(&self, from: VertexId, to: VertexId) -> Option<Cost> {
+        let n = self.num_vertices();
+        if from >= n || to >= n {
+            return None;
+        }
+        self.cost(from, to)
+    }
+
+    // Returns the number of arcs leaving a vertex, 0 if out of range.
+    fn degree(&self, id: VertexId) -> usize {
+        self.out_degrees.get(id).copied().unwrap_or(0)
+    }
+}
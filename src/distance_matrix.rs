@@ -0,0 +1,183 @@
+// # Distance Matrix
+//
+// Complete graph given by a square matrix of pairwise distances, as used
+// in clustering, where every pair of distinct vertices is an edge.
+use crate::constants::{Cost, VertexId};
+use crate::error::GraphError;
+use crate::graph::{check_bounds, Edge, Graph, Vertex};
+use crate::memory::HeapBytes;
+
+// Graph representation viewing every entry of a distance matrix as an edge.
+// # Note: A matrix given to `new` is complete, vertices added later have no
+// #       distances until set with `add_edge`.
+pub struct DistanceMatrix {
+    vertices: Vec<Vertex<usize>>,
+    distances: Vec<Cost>,
+    // One bit per matrix entry set if the distance is known, so any weight
+    // can be stored, as in `GraphMatrix`.
+    present: Vec<u64>,
+}
+
+impl DistanceMatrix {
+    // Wraps a row-major `dimension` x `dimension` matrix of distances,
+    // vertices are labelled with their ids.
+    // # Example: let d = DistanceMatrix::new(2, vec![0, 4, 4, 0])?;
+    // # Note: The diagonal is ignored.
+    pub fn new(dimension: usize, distances: Vec<Cost>) -> Result<Self, GraphError> {
+        if distances.len() != dimension * dimension {
+            // Report the first row that isn't full.
+            let (row, len) = match dimension {
+                0 => (0, distances.len()),
+                n => (distances.len() / n, distances.len() % n),
+            };
+            return Err(GraphError::InvalidShape { row, len });
+        }
+        for from in 0..dimension {
+            for to in (from + 1)..dimension {
+                if distances[from * dimension + to] != distances[to * dimension + from] {
+                    return Err(GraphError::Asymmetric { from, to });
+                }
+            }
+        }
+        let len = distances.len();
+        let mut present = vec![u64::MAX; len.div_ceil(64)];
+        if !len.is_multiple_of(64) {
+            present[len / 64] = (1 << (len % 64)) - 1;
+        }
+        Ok(DistanceMatrix {
+            vertices: (0..dimension).map(|id| Vertex { id, data: id }).collect(),
+            distances,
+            present,
+        })
+    }
+
+    fn is_present(&self, index: usize) -> bool {
+        self.present[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    fn set_present(&mut self, index: usize) {
+        self.present[index / 64] |= 1 << (index % 64);
+    }
+
+    // Returns true if every pair of distinct vertices has a distance, e.g.
+    // before handing `distance` to `ImplicitPrim`.
+    // # Note: O(n^2 / 64) counting the bits of the known distances.
+    pub fn is_complete(&self) -> bool {
+        let n = self.vertices.len();
+        let known: usize = self.present.iter().map(|b| b.count_ones() as usize).sum();
+        known == n * n
+    }
+
+    // Returns the distance between two vertices, 0 on the diagonal and for
+    // the pairs without a distance, see `edge_weight`.
+    // # Panic: Both vertices must be in range.
+    #[inline]
+    pub fn distance(&self, from: VertexId, to: VertexId) -> Cost {
+        self.distances[from * self.vertices.len() + to]
+    }
+
+    // Returns the edges lazily, each pair once with from < to.
+    pub fn edges(&self) -> impl Iterator<Item = Edge> + '_ {
+        let n = self.vertices.len();
+        (0..n)
+            .flat_map(move |from| ((from + 1)..n).map(move |to| (from, to)))
+            .filter(move |&(from, to)| self.is_present(from * n + to))
+            .map(|(from, to)| Edge::new(from, to, self.distance(from, to)))
+    }
+
+    // Returns an estimate of the heap bytes held by the matrix with its
+    // bitset.
+    pub fn memory_footprint(&self) -> usize {
+        self.vertices.heap_bytes() + self.distances.heap_bytes() + self.present.heap_bytes()
+    }
+}
+
+impl Graph<usize> for DistanceMatrix {
+    // Adds a vertex whose distances start missing until set with `add_edge`.
+    // # Note: O(n^2) since the square matrix is copied to its new dimension.
+    fn add_vertex(&mut self, data: usize) -> usize {
+        let n = self.vertices.len();
+        let mut distances = vec![0; (n + 1) * (n + 1)];
+        let mut present = vec![0; ((n + 1) * (n + 1)).div_ceil(64)];
+        for row in 0..n {
+            distances[row * (n + 1)..row * (n + 1) + n]
+                .copy_from_slice(&self.distances[row * n..(row + 1) * n]);
+            for col in 0..n {
+                if self.is_present(row * n + col) {
+                    let index = row * (n + 1) + col;
+                    present[index / 64] |= 1 << (index % 64);
+                }
+            }
+        }
+        self.distances = distances;
+        self.present = present;
+        // The diagonal counts as known, see `is_complete`.
+        self.set_present(n * (n + 1) + n);
+        self.vertices.push(Vertex { id: n, data });
+        n
+    }
+
+    // Sets the distance between two vertices in both directions,
+    // self-loops are ignored.
+    fn add_edge(&mut self, from: VertexId, to: VertexId, cost: Cost) -> Result<(), GraphError> {
+        let n = self.vertices.len();
+        check_bounds(from, to, n)?;
+        if from == to {
+            return Ok(());
+        }
+        self.distances[from * n + to] = cost;
+        self.distances[to * n + from] = cost;
+        self.set_present(from * n + to);
+        self.set_present(to * n + from);
+        Ok(())
+    }
+
+    // Returns a vertex structure from a vertex identifier.
+    fn vertex(&self, id: VertexId) -> Option<&Vertex<usize>> {
+        self.vertices.get(id)
+    }
+
+    // Returns the vertices without a copy.
+    fn vertices(&self) -> &[Vertex<usize>] {
+        &self.vertices
+    }
+
+    // Returns the total number of vertices.
+    fn num_vertices(&self) -> usize {
+        self.vertices.len()
+    }
+
+    // Returns the number of pairs with a distance.
+    // # Note: O(n^2) scanning the matrix, without collecting the edges.
+    fn num_edges(&self) -> usize {
+        self.edges().count()
+    }
+
+    // Returns a vector of all edges, see `edges` to avoid materializing them.
+    fn all_edges(&self) -> Vec<Edge> {
+        self.edges().collect()
+    }
+
+    // Returns the distance between two distinct vertices, if any.
+    // # Note: O(1) matrix lookup.
+    fn edge_weight(&self, from: VertexId, to: VertexId) -> Option<Cost> {
+        let n = self.num_vertices();
+        if from >= n || to >= n || from == to {
+            return None;
+        }
+        self.is_present(from * n + to)
+            .then(|| self.distance(from, to))
+    }
+
+    // Returns the number of edges incident to a vertex, 0 if out of range.
+    // # Note: O(n) scanning its row.
+    fn degree(&self, id: VertexId) -> usize {
+        let n = self.num_vertices();
+        if id >= n {
+            return 0;
+        }
+        (0..n)
+            .filter(|&to| to != id && self.is_present(id * n + to))
+            .count()
+    }
+}
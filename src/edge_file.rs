@@ -0,0 +1,131 @@
+// # Edge File
+//
+// External-memory edge list backed by a memory-mapped binary file, so that
+// Filter Kruskal can run on edge sets larger than RAM. Enabled by the `mmap`
+// feature.
+//
+// Layout: a native-endian `usize` vertex count followed by the edges as
+// native-endian `usize` triples (from, to, weight).
+use crate::error::GraphError;
+use crate::filter_kruskal::{filter_kruskal, DEFAULT_KRUSKAL_THRESHOLD};
+use crate::graph::Edge;
+use crate::mst_result::MstResult;
+use crate::partition::PivotStrategy;
+use crate::union_find::UnionFind;
+use memmap2::MmapMut;
+use rand_core::Rng;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::mem::size_of;
+use std::path::Path;
+
+const HEADER_SIZE: usize = size_of::<usize>();
+
+pub struct EdgeFile {
+    num_vertices: usize,
+    mmap: MmapMut,
+}
+
+impl EdgeFile {
+    // Writes an edge list to `path` in the edge file layout.
+    pub fn create<P, I>(path: P, num_vertices: usize, edges: I) -> Result<(), GraphError>
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = Edge>,
+    {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(&num_vertices.to_ne_bytes())?;
+        for edge in edges {
+            if edge.from >= num_vertices || edge.to >= num_vertices {
+                return Err(GraphError::VertexOutOfBounds {
+                    vertex: edge.from.max(edge.to),
+                    num_vertices,
+                });
+            }
+            for word in [edge.from, edge.to, edge.weight] {
+                writer.write_all(&word.to_ne_bytes())?;
+            }
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    // Maps an edge file in read-write mode and validates its content.
+    // # Note: O(m) scan of the endpoints, pages are loaded lazily by the OS.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, GraphError> {
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        // Safety: the file must not be modified by other processes while mapped.
+        let mmap = unsafe { MmapMut::map_mut(&file)? };
+        if mmap.len() < HEADER_SIZE || !(mmap.len() - HEADER_SIZE).is_multiple_of(size_of::<Edge>())
+        {
+            return Err(GraphError::Io(format!(
+                "Invalid edge file size: {} bytes",
+                mmap.len()
+            )));
+        }
+
+        let mut header = [0; HEADER_SIZE];
+        header.copy_from_slice(&mmap[..HEADER_SIZE]);
+        let mut this = EdgeFile {
+            num_vertices: usize::from_ne_bytes(header),
+            mmap,
+        };
+
+        let num_vertices = this.num_vertices;
+        if let Some(edge) = this
+            .edges_mut()
+            .iter()
+            .find(|e| e.from >= num_vertices || e.to >= num_vertices)
+        {
+            return Err(GraphError::VertexOutOfBounds {
+                vertex: edge.from.max(edge.to),
+                num_vertices,
+            });
+        }
+        Ok(this)
+    }
+
+    pub fn num_vertices(&self) -> usize {
+        self.num_vertices
+    }
+
+    pub fn num_edges(&self) -> usize {
+        (self.mmap.len() - HEADER_SIZE) / size_of::<Edge>()
+    }
+
+    // Returns the mapped edges as a disk-backed slice.
+    pub fn edges_mut(&mut self) -> &mut [Edge] {
+        let num_edges = self.num_edges();
+        // Safety: the mapping is page aligned, so the edges after the
+        // one-word header are aligned, and `Edge` is `repr(C)` over `usize`
+        // words with no invalid bit patterns.
+        unsafe {
+            let ptr = self.mmap.as_mut_ptr().add(HEADER_SIZE) as *mut Edge;
+            std::slice::from_raw_parts_mut(ptr, num_edges)
+        }
+    }
+
+    // Runs Filter Kruskal partitioning the mapped edges in place.
+    // # Note: Only the union find and the tree are kept in RAM, O(n),
+    // #       the file's edges are reordered (the edge set is unchanged).
+    pub fn filter_kruskal<R: Rng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+    ) -> Result<MstResult, GraphError> {
+        let num_vertices = self.num_vertices;
+        let mut union_find = UnionFind::new(num_vertices);
+        let mut mst_edges = Vec::new();
+        let (_, cost) = filter_kruskal(
+            self.edges_mut(),
+            num_vertices,
+            &mut union_find,
+            Some(&mut mst_edges),
+            PivotStrategy::Random,
+            DEFAULT_KRUSKAL_THRESHOLD,
+            false,
+            rng,
+        );
+        self.mmap.flush()?;
+        MstResult::from_forest(num_vertices, mst_edges, cost)
+    }
+}
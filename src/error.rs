@@ -4,7 +4,22 @@ use std::fmt;
 pub enum GraphError {
     InvalidProbability(f64),
     InvalidCostRange { min: usize, max: usize },
+    CostOutOfRange { cost: usize, min: usize, max: usize },
     EmptyInput,
+    Disconnected { components: usize },
+    VertexOutOfBounds { vertex: usize, num_vertices: usize },
+    SelfLoop { vertex: usize },
+    InvalidShape { row: usize, len: usize },
+    Asymmetric { from: usize, to: usize },
+    UnknownAlgorithm(String),
+    InvalidCertificate(String),
+    NotATree,
+    MissingEdge { from: usize, to: usize },
+    CostOverflow(u128),
+    NanWeight { from: usize, to: usize },
+    WeightCount { expected: usize, len: usize },
+    UnknownObjective { index: usize, num_weights: usize },
+    Io(String),
 }
 
 impl fmt::Display for GraphError {
@@ -16,9 +31,57 @@ impl fmt::Display for GraphError {
             GraphError::InvalidCostRange { min, max } => {
                 write!(f, "Invalid cost range: min ({}) > max ({})", min, max)
             }
+            GraphError::CostOutOfRange { cost, min, max } => {
+                write!(f, "Cost {} outside of range [{}, {}]", cost, min, max)
+            }
             GraphError::EmptyInput => write!(f, "Input collection cannot be empty"),
+            GraphError::Disconnected { components } => {
+                write!(f, "Graph is disconnected: {} components", components)
+            }
+            GraphError::VertexOutOfBounds {
+                vertex,
+                num_vertices,
+            } => write!(
+                f,
+                "Vertex {} out of bounds for a graph with {} vertices",
+                vertex, num_vertices
+            ),
+            GraphError::SelfLoop { vertex } => write!(f, "Self-loop on vertex {}", vertex),
+            GraphError::InvalidShape { row, len } => {
+                write!(f, "Matrix is not square, row {} has {} entries", row, len)
+            }
+            GraphError::Asymmetric { from, to } => {
+                write!(f, "Matrix is not symmetric at ({}, {})", from, to)
+            }
+            GraphError::UnknownAlgorithm(name) => write!(f, "Unknown algorithm: {}", name),
+            GraphError::InvalidCertificate(reason) => write!(f, "Invalid certificate: {}", reason),
+            GraphError::NotATree => write!(f, "Edges contain a cycle, not a tree"),
+            GraphError::MissingEdge { from, to } => {
+                write!(f, "No edge between vertices {} and {}", from, to)
+            }
+            GraphError::NanWeight { from, to } => {
+                write!(f, "Edge ({}, {}) has a NaN weight", from, to)
+            }
+            GraphError::WeightCount { expected, len } => {
+                write!(f, "Expected {} weights, got {}", expected, len)
+            }
+            GraphError::UnknownObjective { index, num_weights } => write!(
+                f,
+                "Objective {} out of bounds for edges with {} weights",
+                index, num_weights
+            ),
+            GraphError::CostOverflow(total) => {
+                write!(f, "Total cost {} doesn't fit in the cost type", total)
+            }
+            GraphError::Io(message) => write!(f, "I/O error: {}", message),
         }
     }
 }
 
 impl std::error::Error for GraphError {}
+
+impl From<std::io::Error> for GraphError {
+    fn from(err: std::io::Error) -> Self {
+        GraphError::Io(err.to_string())
+    }
+}
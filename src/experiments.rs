@@ -0,0 +1,418 @@
+// # Experiments
+//
+// Repeated runs of a variant over the graphs a generator draws from many
+// seeds, summarized by the mean and variance of the tree cost and the
+// distribution of the run time. Summaries export to CSV and JSON, alone
+// or gathered in a `ComparisonReport`, to be plotted with external tools.
+// An `ExperimentConfig` records everything a run depends on, so that it
+// can be saved next to its results and replayed.
+// # Example: Expected weight of the tree of K_n with uniform weights, which
+// #          tends to ζ(3) ≈ 1.202 once scaled back to [0, 1], as checked
+// #          by `tests/integration.rs`:
+// #          let s = experiments::run(Algorithm::DensePrim, 0..100, |seed| {
+// #              GraphMatrix::new_random_seeded(0..500, 1.0, 1, 1 << 20, true, seed).unwrap()
+// #          })?;
+// #          let zeta3 = s.mean_cost / (1 << 20) as f64;
+use crate::algorithm::Algorithm;
+use crate::constants::Cost;
+use crate::error::GraphError;
+use crate::graph::Graph;
+use crate::graph_stars::GraphStars;
+use crate::strategies::GraphStrategy;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::fmt;
+use std::ops::Range;
+use std::time::{Duration, Instant};
+
+// Outcome of the run on the graph of one seed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sample {
+    pub seed: u64,
+    pub cost: Cost,
+    pub elapsed: Duration,
+}
+
+// Distribution of the run times, nearest-rank percentiles.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TimeStats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p90: Duration,
+    pub max: Duration,
+    pub mean: Duration,
+}
+
+impl TimeStats {
+    // Collects the statistics of a set of run times, all zero if empty.
+    pub fn new(times: &[Duration]) -> Self {
+        if times.is_empty() {
+            return TimeStats::default();
+        }
+        let mut sorted = times.to_vec();
+        sorted.sort_unstable();
+        let percentile = |p: usize| sorted[(p * sorted.len()).div_ceil(100).max(1) - 1];
+        let total: Duration = sorted.iter().sum();
+        TimeStats {
+            min: sorted[0],
+            median: percentile(50),
+            p90: percentile(90),
+            max: sorted[sorted.len() - 1],
+            mean: total / sorted.len() as u32,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Summary {
+    pub algorithm: Algorithm,
+    // One sample per seed, in order.
+    pub samples: Vec<Sample>,
+    pub mean_cost: f64,
+    // Unbiased sample variance, 0 under two samples.
+    pub cost_variance: f64,
+    pub times: TimeStats,
+}
+
+impl Summary {
+    // Summarizes the samples of a variant.
+    pub fn new(algorithm: Algorithm, samples: Vec<Sample>) -> Self {
+        let count = samples.len() as f64;
+        let mean_cost = if samples.is_empty() {
+            0.0
+        } else {
+            samples.iter().map(|s| s.cost as f64).sum::<f64>() / count
+        };
+        let cost_variance = if samples.len() < 2 {
+            0.0
+        } else {
+            let squares: f64 = samples
+                .iter()
+                .map(|s| (s.cost as f64 - mean_cost).powi(2))
+                .sum();
+            squares / (count - 1.0)
+        };
+        let times: Vec<Duration> = samples.iter().map(|s| s.elapsed).collect();
+        Summary {
+            algorithm,
+            samples,
+            mean_cost,
+            cost_variance,
+            times: TimeStats::new(&times),
+        }
+    }
+}
+
+impl Summary {
+    // Returns one CSV line per sample, times in nanoseconds.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("algorithm,seed,cost,elapsed_ns\n");
+        for sample in &self.samples {
+            csv.push_str(&format!(
+                "{},{},{},{}\n",
+                self.algorithm,
+                sample.seed,
+                sample.cost,
+                sample.elapsed.as_nanos()
+            ));
+        }
+        csv
+    }
+
+    // Returns the summary and its samples as a JSON object, times in
+    // nanoseconds.
+    pub fn to_json(&self) -> String {
+        let samples: Vec<String> = self
+            .samples
+            .iter()
+            .map(|s| {
+                format!(
+                    "{{\"seed\":{},\"cost\":{},\"elapsed_ns\":{}}}",
+                    s.seed,
+                    s.cost,
+                    s.elapsed.as_nanos()
+                )
+            })
+            .collect();
+        format!(
+            "{{{},\"samples\":[{}]}}",
+            self.json_fields(),
+            samples.join(",")
+        )
+    }
+
+    // Fields shared by the JSON of a summary and of a report row.
+    fn json_fields(&self) -> String {
+        let t = &self.times;
+        format!(
+            "\"algorithm\":\"{}\",\"num_samples\":{},\"mean_cost\":{},\"cost_variance\":{},\
+             \"min_ns\":{},\"median_ns\":{},\"p90_ns\":{},\"max_ns\":{},\"mean_ns\":{}",
+            self.algorithm,
+            self.samples.len(),
+            self.mean_cost,
+            self.cost_variance,
+            t.min.as_nanos(),
+            t.median.as_nanos(),
+            t.p90.as_nanos(),
+            t.max.as_nanos(),
+            t.mean.as_nanos()
+        )
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "algorithm: {}", self.algorithm)?;
+        writeln!(f, "samples:   {}", self.samples.len())?;
+        writeln!(
+            f,
+            "cost:      mean {:.4} / std dev {:.4}",
+            self.mean_cost,
+            self.cost_variance.sqrt()
+        )?;
+        write!(
+            f,
+            "time:      min {:?} / median {:?} / p90 {:?} / max {:?}",
+            self.times.min, self.times.median, self.times.p90, self.times.max
+        )
+    }
+}
+
+// Runs `algorithm` on the graph `generate` builds from each seed, the
+// randomized variants drawing from a `StdRng` seeded with the same seed.
+// # Note: The time is the one of `Algorithm::run_on_edges`, which includes
+// #       building the representation the variant works on. Fails on the
+// #       first graph the variant fails on, e.g. a disconnected one.
+pub fn run<T, G, F>(
+    algorithm: Algorithm,
+    seeds: Range<u64>,
+    generate: F,
+) -> Result<Summary, GraphError>
+where
+    G: Graph<T>,
+    F: FnMut(u64) -> G,
+{
+    let mut summaries = compare(&[algorithm], seeds, generate)?;
+    Ok(summaries.pop().expect("one summary per variant"))
+}
+
+// Runs every variant of `algorithms` like `run`, on the same graph for each
+// seed, and returns their summaries in order.
+pub fn compare<T, G, F>(
+    algorithms: &[Algorithm],
+    seeds: Range<u64>,
+    mut generate: F,
+) -> Result<Vec<Summary>, GraphError>
+where
+    G: Graph<T>,
+    F: FnMut(u64) -> G,
+{
+    let mut samples = vec![Vec::new(); algorithms.len()];
+    for seed in seeds {
+        let graph = generate(seed);
+        let edges = graph.all_edges();
+        for (algorithm, samples) in algorithms.iter().zip(samples.iter_mut()) {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let start = Instant::now();
+            let tree = algorithm.run_on_edges(graph.num_vertices(), &edges, &mut rng)?;
+            let elapsed = start.elapsed();
+            samples.push(Sample {
+                seed,
+                cost: tree.cost,
+                elapsed,
+            });
+        }
+    }
+    Ok(algorithms
+        .iter()
+        .zip(samples)
+        .map(|(&algorithm, samples)| Summary::new(algorithm, samples))
+        .collect())
+}
+
+// Table of summaries labelled with the graph they were measured on, e.g.
+// one row per variant and graph size.
+// # Example: report.extend("n=1000", experiments::compare(&Algorithm::ALL, 0..10, gen)?);
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ComparisonReport {
+    pub rows: Vec<(String, Summary)>,
+}
+
+impl ComparisonReport {
+    pub fn new() -> Self {
+        ComparisonReport::default()
+    }
+
+    // Adds a row.
+    pub fn push(&mut self, label: &str, summary: Summary) {
+        self.rows.push((label.to_string(), summary));
+    }
+
+    // Adds a row per summary, all with the same label.
+    pub fn extend<I: IntoIterator<Item = Summary>>(&mut self, label: &str, summaries: I) {
+        for summary in summaries {
+            self.push(label, summary);
+        }
+    }
+
+    // Returns one CSV line per row, times in nanoseconds.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "label,algorithm,num_samples,mean_cost,cost_variance,\
+             min_ns,median_ns,p90_ns,max_ns,mean_ns\n",
+        );
+        for (label, s) in &self.rows {
+            let t = &s.times;
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{},{},{}\n",
+                csv_field(label),
+                s.algorithm,
+                s.samples.len(),
+                s.mean_cost,
+                s.cost_variance,
+                t.min.as_nanos(),
+                t.median.as_nanos(),
+                t.p90.as_nanos(),
+                t.max.as_nanos(),
+                t.mean.as_nanos()
+            ));
+        }
+        csv
+    }
+
+    // Returns the rows as a JSON array of objects, without the samples.
+    pub fn to_json(&self) -> String {
+        let rows: Vec<String> = self
+            .rows
+            .iter()
+            .map(|(label, s)| format!("{{\"label\":{},{}}}", json_string(label), s.json_fields()))
+            .collect();
+        format!("[{}]", rows.join(","))
+    }
+}
+
+// Quotes a CSV field if it holds a separator, a quote or a line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// Returns a JSON string literal.
+fn json_string(text: &str) -> String {
+    let mut json = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+// Family of graphs an experiment draws from, each seed giving one graph.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Generator {
+    // Erdős–Rényi G(n, p) with uniform weights, see `GraphStars::new_random`.
+    Random {
+        num_vertices: usize,
+        p: f64,
+        min_cost: Cost,
+        max_cost: Cost,
+    },
+    // Graphs of a property-test strategy, see `GraphStrategy::generate`.
+    Strategy(GraphStrategy),
+}
+
+impl Generator {
+    // Fails if the parameters can't generate a graph.
+    pub fn validate(&self) -> Result<(), GraphError> {
+        let p = match self {
+            Generator::Random {
+                p,
+                min_cost,
+                max_cost,
+                ..
+            } => {
+                if min_cost > max_cost {
+                    return Err(GraphError::InvalidCostRange {
+                        min: *min_cost,
+                        max: *max_cost,
+                    });
+                }
+                *p
+            }
+            Generator::Strategy(strategy) => strategy.density,
+        };
+        if !(0.0..=1.0).contains(&p) {
+            return Err(GraphError::InvalidProbability(p));
+        }
+        Ok(())
+    }
+
+    // Returns the graph of a seed, vertices labelled with their ids.
+    pub fn generate(&self, seed: u64) -> Result<GraphStars<usize>, GraphError> {
+        match self {
+            Generator::Random {
+                num_vertices,
+                p,
+                min_cost,
+                max_cost,
+            } => GraphStars::new_random_seeded(
+                0..*num_vertices,
+                *p,
+                *min_cost,
+                *max_cost,
+                true,
+                seed,
+            ),
+            Generator::Strategy(strategy) => {
+                self.validate()?;
+                Ok(strategy.generate_seeded(seed).to_graph())
+            }
+        }
+    }
+}
+
+// Everything an experiment depends on: the graphs, the variant and the
+// seeds `seed..seed + runs`, one per graph.
+// # Note: Identical configurations draw identical graphs and trees, so the
+// #       costs of their results match, only the times differ.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExperimentConfig {
+    pub generator: Generator,
+    pub algorithm: Algorithm,
+    pub seed: u64,
+    pub runs: u64,
+}
+
+// A summary along with the configuration that produced it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExperimentResult {
+    pub config: ExperimentConfig,
+    pub summary: Summary,
+}
+
+impl ExperimentConfig {
+    // Runs the experiment, see `run`.
+    pub fn run(&self) -> Result<ExperimentResult, GraphError> {
+        self.generator.validate()?;
+        let seeds = self.seed..self.seed.saturating_add(self.runs);
+        let summary = run(self.algorithm, seeds, |seed| {
+            self.generator
+                .generate(seed)
+                .expect("the generator is validated")
+        })?;
+        Ok(ExperimentResult {
+            config: self.clone(),
+            summary,
+        })
+    }
+}
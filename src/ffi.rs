@@ -0,0 +1,157 @@
+// # C FFI
+//
+// `extern "C"` entry points to build a graph, run one of the variants and
+// copy the resulting tree into caller-provided buffers, enabled by the `ffi`
+// feature. The matching declarations live in `include/mst_kruskal_variants.h`.
+#![allow(clippy::missing_safety_doc)]
+
+use crate::algorithm::Algorithm;
+use crate::constants::{Cost, VertexId};
+use crate::error::GraphError;
+use crate::graph::Edge;
+use crate::mst_result::MstResult;
+use crate::rng::SplitMix64;
+
+pub const MST_OK: i32 = 0;
+pub const MST_ERR_NULL_POINTER: i32 = -1;
+pub const MST_ERR_VERTEX_OUT_OF_BOUNDS: i32 = -2;
+pub const MST_ERR_UNKNOWN_ALGORITHM: i32 = -3;
+pub const MST_ERR_DISCONNECTED: i32 = -4;
+pub const MST_ERR_BUFFER_TOO_SMALL: i32 = -5;
+pub const MST_ERR_OTHER: i32 = -6;
+pub const MST_ERR_COST_OVERFLOW: i32 = -7;
+
+// Algorithm codes, in the order of `Algorithm::ALL`.
+pub const MST_KRUSKAL: u32 = 0;
+pub const MST_QUICK_SORT_KRUSKAL: u32 = 1;
+pub const MST_FILTER_KRUSKAL: u32 = 2;
+pub const MST_SKEWED_FILTER_KRUSKAL: u32 = 3;
+pub const MST_STAR_QUICK_SORT_KRUSKAL: u32 = 4;
+pub const MST_BUCKET_KRUSKAL: u32 = 5;
+pub const MST_RADIX_KRUSKAL: u32 = 6;
+pub const MST_DENSE_PRIM: u32 = 7;
+pub const MST_BORUVKA_FILTER_KRUSKAL: u32 = 8;
+pub const MST_FILTER_KRUSKAL_PLUS: u32 = 9;
+pub const MST_SPARSE_PRIM: u32 = 10;
+pub const MST_CHAZELLE: u32 = 11;
+pub const MST_SORT_KRUSKAL: u32 = 12;
+
+// Edge layout shared with C callers.
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct MstEdge {
+    pub from: usize,
+    pub to: usize,
+    pub weight: usize,
+}
+
+// Opaque graph handle, the representation needed by the chosen variant
+// is built only when running it.
+pub struct MstGraph {
+    num_vertices: usize,
+    edges: Vec<Edge>,
+}
+
+impl MstGraph {
+    // Runs the variant selected by one of the `MST_*` algorithm codes.
+    fn solve(&self, algorithm: u32, seed: u64) -> Result<MstResult, i32> {
+        let algorithm = *Algorithm::ALL
+            .get(algorithm as usize)
+            .ok_or(MST_ERR_UNKNOWN_ALGORITHM)?;
+        let mut rng = SplitMix64::new(seed);
+        algorithm
+            .run_on_edges(self.num_vertices, &self.edges, &mut rng)
+            .map_err(|err| match err {
+                GraphError::Disconnected { .. } => MST_ERR_DISCONNECTED,
+                GraphError::VertexOutOfBounds { .. } => MST_ERR_VERTEX_OUT_OF_BOUNDS,
+                GraphError::CostOverflow(_) => MST_ERR_COST_OVERFLOW,
+                _ => MST_ERR_OTHER,
+            })
+    }
+}
+
+// Creates a graph with `num_vertices` vertices and no edges.
+// # Note: The handle must be released with `mst_graph_free`.
+#[unsafe(no_mangle)]
+pub extern "C" fn mst_graph_new(num_vertices: usize) -> *mut MstGraph {
+    Box::into_raw(Box::new(MstGraph {
+        num_vertices,
+        edges: Vec::new(),
+    }))
+}
+
+// Releases a graph created by `mst_graph_new`, null is a no-op.
+// # Safety: `graph` must come from `mst_graph_new` and not be used afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mst_graph_free(graph: *mut MstGraph) {
+    if !graph.is_null() {
+        drop(unsafe { Box::from_raw(graph) });
+    }
+}
+
+// Adds an undirected weighted edge, self-loops are ignored when running.
+// # Safety: `graph` must be a live handle from `mst_graph_new`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mst_graph_add_edge(
+    graph: *mut MstGraph,
+    from: VertexId,
+    to: VertexId,
+    cost: Cost,
+) -> i32 {
+    let Some(graph) = (unsafe { graph.as_mut() }) else {
+        return MST_ERR_NULL_POINTER;
+    };
+    if from >= graph.num_vertices || to >= graph.num_vertices {
+        return MST_ERR_VERTEX_OUT_OF_BOUNDS;
+    }
+    graph.edges.push(Edge::new(from, to, cost));
+    MST_OK
+}
+
+// Runs the chosen variant and copies the tree into `out_edges`.
+// `out_len` always receives the number of tree edges, so a caller getting
+// `MST_ERR_BUFFER_TOO_SMALL` can retry with a large enough buffer.
+// # Note: `seed` is only used by the randomized variants.
+// # Safety: `graph` must be a live handle, `out_edges` must point to
+// #         `capacity` writable edges (or be null with zero capacity),
+// #         `out_len` and `out_cost` must be writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mst_run(
+    graph: *const MstGraph,
+    algorithm: u32,
+    seed: u64,
+    out_edges: *mut MstEdge,
+    capacity: usize,
+    out_len: *mut usize,
+    out_cost: *mut Cost,
+) -> i32 {
+    let Some(graph) = (unsafe { graph.as_ref() }) else {
+        return MST_ERR_NULL_POINTER;
+    };
+    if out_len.is_null() || out_cost.is_null() || (out_edges.is_null() && capacity > 0) {
+        return MST_ERR_NULL_POINTER;
+    }
+
+    let mst = match graph.solve(algorithm, seed) {
+        Ok(mst) => mst,
+        Err(code) => return code,
+    };
+
+    unsafe {
+        *out_len = mst.len();
+        *out_cost = mst.cost;
+    }
+    if mst.len() > capacity {
+        return MST_ERR_BUFFER_TOO_SMALL;
+    }
+    for (i, edge) in mst.edges.iter().enumerate() {
+        unsafe {
+            *out_edges.add(i) = MstEdge {
+                from: edge.from,
+                to: edge.to,
+                weight: edge.weight,
+            };
+        }
+    }
+    MST_OK
+}
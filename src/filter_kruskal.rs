@@ -2,113 +2,294 @@
 //
 // Implementation of the Kruskal algorithm using a filtered quickselect approach.
 use crate::constants::*;
+use crate::error::GraphError;
 use crate::graph::{Edge, Graph};
 use crate::graph_matrix::GraphMatrix;
+use crate::memory::HeapBytes;
+use crate::mst_result::MstResult;
+#[cfg(feature = "parallel")]
+use crate::partition::par_partition;
+use crate::partition::{partition, partition_three_way, PivotStrategy};
+use crate::reuse::{Reusable, TreeBuffer};
+use crate::rng::SplitMix64;
 use crate::union_find::UnionFind;
-use rand::Rng;
+use rand_core::Rng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+// Ranges up to this many edges are sorted instead of partitioned further.
+pub const DEFAULT_KRUSKAL_THRESHOLD: usize = 64;
+
+// Ranges of at least this many edges are filtered and partitioned in
+// parallel when more than one thread is available, the recursion itself
+// stays sequential.
+#[cfg(feature = "parallel")]
+pub const PARALLEL_THRESHOLD: usize = 1 << 16;
 
 pub struct FilterKruskal {
     num_vertices: usize,
-    num_edges: usize,
     edges: Vec<Edge>,
+    pivot: PivotStrategy,
+    kruskal_threshold: usize,
+    three_way: bool,
     union_find: UnionFind,
-    mst_edges: Vec<Edge>,
-    mst_cost: Cost,
+    tree: TreeBuffer,
 }
 
 impl FilterKruskal {
+    // Constructs the algorithm structures with random pivots.
     pub fn new(graph: &GraphMatrix<usize>) -> Self {
+        Self::with_pivot(graph, PivotStrategy::Random)
+    }
+
+    // Constructs the algorithm structures with the given pivot strategy.
+    pub fn with_pivot(graph: &GraphMatrix<usize>, pivot: PivotStrategy) -> Self {
         let num_vertices = graph.num_vertices();
         let edges = graph.all_edges();
-        let num_edges = edges.len();
         FilterKruskal {
             num_vertices,
-            num_edges,
             edges,
+            pivot,
+            kruskal_threshold: DEFAULT_KRUSKAL_THRESHOLD,
+            three_way: false,
             union_find: UnionFind::new(num_vertices),
-            mst_edges: Vec::new(),
-            mst_cost: 0,
+            tree: TreeBuffer::default(),
         }
     }
 
-    pub fn run<R: Rng>(&mut self, rng: &mut R) -> (Vec<Edge>, Cost) {
-        if self.num_edges == 0 {
-            return (Vec::new(), 0);
+    // Sets the size under which a range is sorted and scanned like in plain
+    // Kruskal, 0 or 1 keeps partitioning down to single edges.
+    pub fn set_kruskal_threshold(&mut self, threshold: usize) {
+        self.kruskal_threshold = threshold;
+    }
+
+    // Sets whether ranges are split in three around the pivot's weight, the
+    // edges sharing it forming a block only split further by key.
+    pub fn set_three_way(&mut self, three_way: bool) {
+        self.three_way = three_way;
+    }
+
+    // Runs Filter Kruskal on the edges, returning the number of tree edges
+    // found. The edges themselves are only kept if `keep_edges` is set.
+    fn grow<R: Rng + ?Sized>(&mut self, rng: &mut R, keep_edges: bool) -> usize {
+        let (count, cost) = filter_kruskal(
+            &mut self.edges,
+            self.num_vertices,
+            &mut self.union_find,
+            keep_edges.then_some(&mut self.tree.edges),
+            self.pivot,
+            self.kruskal_threshold,
+            self.three_way,
+            rng,
+        );
+        self.tree.cost += cost;
+        count
+    }
+
+    // Runs the algorithm on another graph, reusing the buffers of the
+    // previous run, see `Reusable`.
+    pub fn run_on<R: Rng + ?Sized>(
+        &mut self,
+        graph: &GraphMatrix<usize>,
+        rng: &mut R,
+    ) -> Result<MstResult, GraphError> {
+        self.reset_with(graph);
+        self.grow(rng, true);
+        self.tree.result(self.num_vertices)
+    }
+
+    // Returns an estimate of the heap bytes held by the algorithm: the edge
+    // buffer, the union-find and the tree.
+    pub fn memory_footprint(&self) -> usize {
+        self.edges.heap_bytes() + self.union_find.memory_footprint() + self.tree.memory_footprint()
+    }
+
+    // Runs the algorithm and returns a set of edges representing the minimum
+    // spanning tree and its associated total cost, consuming the algorithm
+    // (see `Reusable`).
+    pub fn run<R: Rng + ?Sized>(mut self, rng: &mut R) -> Result<MstResult, GraphError> {
+        self.grow(rng, true);
+        self.tree.into_result(self.num_vertices)
+    }
+
+    // Runs the algorithm and returns only the total cost of the minimum
+    // spanning tree, its edges are never stored.
+    pub fn run_cost<R: Rng + ?Sized>(mut self, rng: &mut R) -> Result<Cost, GraphError> {
+        let count = self.grow(rng, false);
+        self.tree.total(self.num_vertices, count)
+    }
+
+    // Same as `run` with a `SplitMix64` seeded with `seed`, for reproducible
+    // runs without picking a generator.
+    pub fn run_seeded(self, seed: u64) -> Result<MstResult, GraphError> {
+        self.run(&mut SplitMix64::new(seed))
+    }
+}
+
+impl Reusable<&GraphMatrix<usize>> for FilterKruskal {
+    fn reset_with(&mut self, graph: &GraphMatrix<usize>) {
+        self.num_vertices = graph.num_vertices();
+        self.edges.clear();
+        self.edges.extend_from_slice(graph.cached_edges());
+        self.union_find.reset(self.num_vertices);
+        self.tree.clear();
+    }
+}
+
+// Runs Filter Kruskal on a borrowed edge slice, pushing the tree edges
+// found to `mst_edges` if given and returning their number and total cost.
+// # Note: The slice is permuted in place, which lets callers run on
+// #       edges they don't own (e.g. a memory-mapped file).
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn filter_kruskal<R: Rng + ?Sized>(
+    edges: &mut [Edge],
+    num_vertices: usize,
+    union_find: &mut UnionFind,
+    mut mst_edges: Option<&mut Vec<Edge>>,
+    pivot: PivotStrategy,
+    kruskal_threshold: usize,
+    three_way: bool,
+    rng: &mut R,
+) -> (usize, CostSum) {
+    if edges.is_empty() {
+        return (0, 0);
+    }
+
+    let mut count = 0;
+    let mut cost: CostSum = 0;
+    let m: usize = edges.len();
+    // Ranges still to process, flagged if they may hold several weights.
+    let mut mem: Vec<(usize, usize, bool)> = Vec::new();
+
+    mem.push((0, m - 1, three_way));
+    while let Some((p, mut q, split_by_weight)) = mem.pop() {
+        if count + 1 >= num_vertices {
+            break;
         }
 
-        let mut count = 0;
-        let m: usize = self.num_edges;
-        let mut mem: Vec<(usize, usize)> = Vec::new();
+        // Before partitioning, compress the range by removing edges
+        // that are already connected in the UnionFind structure.
+        let write_idx = p + filter_connected(&mut edges[p..=q], union_find);
+        if write_idx == p {
+            continue;
+        }
+        q = write_idx - 1;
 
-        mem.push((0, m - 1));
-        while let Some((p, mut q)) = mem.pop() {
-            if count >= self.num_vertices - 1 {
-                break;
+        if p == q {
+            let edge = edges[p];
+            if union_find.union(edge.from, edge.to) {
+                if let Some(mst_edges) = mst_edges.as_deref_mut() {
+                    mst_edges.push(edge);
+                }
+                cost += edge.weight as CostSum;
+                count += 1;
             }
+            continue;
+        }
 
-            // Before partitioning, compress the range by removing edges
-            // that are already connected in the UnionFind structure.
-            let mut write_idx = p;
-            for read_idx in p..=q {
-                let e = self.edges[read_idx];
-                if self.union_find.find(e.from) != self.union_find.find(e.to) {
-                    self.edges[write_idx] = e;
-                    write_idx += 1;
+        // Small ranges: sort and scan them as plain Kruskal would.
+        if q - p < kruskal_threshold {
+            let range = &mut edges[p..=q];
+            range.sort_unstable_by_key(Edge::key);
+            for edge in range.iter() {
+                if count + 1 >= num_vertices {
+                    break;
+                }
+                if union_find.union(edge.from, edge.to) {
+                    if let Some(mst_edges) = mst_edges.as_deref_mut() {
+                        mst_edges.push(*edge);
+                    }
+                    cost += edge.weight as CostSum;
+                    count += 1;
                 }
             }
-            if write_idx == p {
-                continue;
-            }
-            q = write_idx - 1;
+            continue;
+        }
 
-            if p == q {
-                let edge = self.edges[p];
-                if self.union_find.union(edge.from, edge.to) {
-                    self.mst_edges.push(edge);
-                    self.mst_cost += edge.weight;
-                    count += 1;
+        let pivot_idx = pivot.select(edges, p, q, rng);
+        edges.swap(p, pivot_idx);
+
+        // Split in three by weight while the range holds several, the
+        // edges of the pivot's weight forming a block that is only split
+        // further by key.
+        if split_by_weight {
+            let (lt, gt) = partition_three_way(edges, p, q);
+            if lt > p || gt < q {
+                if (count + 1 < num_vertices) && (gt < q) {
+                    mem.push((gt + 1, q, true));
+                }
+                mem.push((lt, gt, false));
+                if lt > p {
+                    mem.push((p, lt - 1, true));
                 }
                 continue;
             }
+        }
+        #[cfg(feature = "parallel")]
+        let e_plus = if q - p >= PARALLEL_THRESHOLD && rayon::current_num_threads() > 1 {
+            par_partition(edges, p, q)
+        } else {
+            partition(edges, p, q)
+        };
+        #[cfg(not(feature = "parallel"))]
+        let e_plus = partition(edges, p, q);
 
-            let mut e_plus = q;
-            if p < q {
-                let pivot_idx = rng.random_range(p..=q);
-                self.edges.swap(p, pivot_idx);
-
-                let mut e_minus = p;
-                while e_minus <= e_plus {
-                    while self.edges[e_plus].weight > self.edges[p].weight {
-                        if e_plus == 0 {
-                            break;
-                        }
-                        e_plus -= 1;
-                    }
-                    while (e_minus <= e_plus)
-                        && (self.edges[e_minus].weight <= self.edges[p].weight)
-                    {
-                        e_minus += 1;
-                    }
-                    if e_minus < e_plus {
-                        self.edges.swap(e_minus, e_plus);
-                        e_minus += 1;
-                        e_plus = e_plus.saturating_sub(1);
-                    }
-                }
-                self.edges.swap(p, e_plus);
+        if (count + 1 < num_vertices) && (e_plus < q) {
+            mem.push((e_plus + 1, q, false));
+        }
 
-                if (count < self.num_vertices - 1) && (e_plus < q) {
-                    mem.push((e_plus + 1, q));
-                }
+        mem.push((e_plus, e_plus, false));
+
+        if e_plus > p {
+            mem.push((p, e_plus - 1, false));
+        }
+    }
 
-                mem.push((e_plus, e_plus));
+    (count, cost)
+}
 
-                if e_plus > p {
-                    mem.push((p, e_plus - 1));
+// Moves the edges joining different components to the front of the range
+// and returns their number.
+fn filter_connected(edges: &mut [Edge], union_find: &mut UnionFind) -> usize {
+    #[cfg(feature = "parallel")]
+    if edges.len() >= PARALLEL_THRESHOLD && rayon::current_num_threads() > 1 {
+        // Flatten the trees first when it's cheaper than the range, the
+        // threads can't compress paths.
+        if union_find.len() <= edges.len() {
+            union_find.compress();
+        }
+        let union_find = &*union_find;
+        let block = edges.len().div_ceil(rayon::current_num_threads());
+        // Compact every block in place, then move the kept prefixes together.
+        let kept: Vec<usize> = edges
+            .par_chunks_mut(block)
+            .map(|chunk| {
+                let mut write_idx = 0;
+                for read_idx in 0..chunk.len() {
+                    let e = chunk[read_idx];
+                    if union_find.root(e.from) != union_find.root(e.to) {
+                        chunk[write_idx] = e;
+                        write_idx += 1;
+                    }
                 }
-            }
+                write_idx
+            })
+            .collect();
+        let mut write_idx = 0;
+        for (i, &count) in kept.iter().enumerate() {
+            edges.copy_within(i * block..i * block + count, write_idx);
+            write_idx += count;
         }
+        return write_idx;
+    }
 
-        (self.mst_edges.clone(), self.mst_cost)
+    let mut write_idx = 0;
+    for read_idx in 0..edges.len() {
+        let e = edges[read_idx];
+        if union_find.find(e.from) != union_find.find(e.to) {
+            edges[write_idx] = e;
+            write_idx += 1;
+        }
     }
+    write_idx
 }
@@ -0,0 +1,149 @@
+// # Filter Kruskal+
+//
+// Filter Kruskal preceded by a sampling filter: the MSF F of a random
+// sample of the edges is computed and the edges that are F-heavy, the
+// heaviest on a cycle with F, are dropped since they can't be in the MST.
+use crate::constants::*;
+use crate::error::GraphError;
+use crate::filter_kruskal::{filter_kruskal, DEFAULT_KRUSKAL_THRESHOLD};
+use crate::graph::{Edge, Graph};
+use crate::graph_matrix::GraphMatrix;
+use crate::memory::HeapBytes;
+use crate::mst_result::MstResult;
+use crate::partition::PivotStrategy;
+use crate::reuse::{Reusable, TreeBuffer};
+use crate::rng;
+use crate::rng::SplitMix64;
+use crate::union_find::UnionFind;
+use rand_core::Rng;
+
+pub struct FilterKruskalPlus {
+    num_vertices: usize,
+    edges: Vec<Edge>,
+    pivot: PivotStrategy,
+    union_find: UnionFind,
+    tree: TreeBuffer,
+}
+
+impl FilterKruskalPlus {
+    // Constructs the algorithm structures with random pivots.
+    pub fn new(graph: &GraphMatrix<usize>) -> Self {
+        Self::with_pivot(graph, PivotStrategy::Random)
+    }
+
+    // Constructs the algorithm structures, `pivot` is used by the Filter
+    // Kruskal phase.
+    pub fn with_pivot(graph: &GraphMatrix<usize>, pivot: PivotStrategy) -> Self {
+        let num_vertices = graph.num_vertices();
+        FilterKruskalPlus {
+            num_vertices,
+            edges: graph.all_edges(),
+            pivot,
+            union_find: UnionFind::new(num_vertices),
+            tree: TreeBuffer::default(),
+        }
+    }
+
+    // Drops the edges that are heavy for the MSF F of a random sample: an
+    // edge joining two vertices of the same tree of F and heavier than all
+    // of that tree's edges closes a cycle on which it's the heaviest.
+    // # Note: sqrt(n * m) sampled edges, enough for F to span most of a
+    // #       dense graph with light trees. O(s log s + m).
+    fn sample_filter<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let n = self.num_vertices;
+        let m = self.edges.len();
+        if m == 0 {
+            return;
+        }
+        let sample_size = n.saturating_mul(m).isqrt().clamp(1, m);
+        let mut sample: Vec<Edge> = (0..sample_size)
+            .map(|_| self.edges[rng::below(rng, m)])
+            .collect();
+        sample.sort_unstable_by_key(Edge::key);
+
+        let mut union_find = UnionFind::new(n);
+        let forest: Vec<Edge> = sample
+            .into_iter()
+            .filter(|e| union_find.union(e.from, e.to))
+            .collect();
+        let roots: Vec<usize> = (0..n).map(|v| union_find.find(v)).collect();
+        let mut heaviest: Vec<Option<(Cost, VertexId, VertexId)>> = vec![None; n];
+        for edge in &forest {
+            let root = roots[edge.from];
+            heaviest[root] = heaviest[root].max(Some(edge.key()));
+        }
+
+        self.edges.retain(|e| {
+            let root = roots[e.from];
+            root != roots[e.to] || Some(e.key()) <= heaviest[root]
+        });
+    }
+
+    // Filters the edges with the sample, then runs Filter Kruskal on the
+    // rest, returning the number of tree edges found. The edges themselves
+    // are only kept if `keep_edges` is set.
+    fn grow<R: Rng + ?Sized>(&mut self, rng: &mut R, keep_edges: bool) -> usize {
+        self.sample_filter(rng);
+        let (count, cost) = filter_kruskal(
+            &mut self.edges,
+            self.num_vertices,
+            &mut self.union_find,
+            keep_edges.then_some(&mut self.tree.edges),
+            self.pivot,
+            DEFAULT_KRUSKAL_THRESHOLD,
+            false,
+            rng,
+        );
+        self.tree.cost += cost;
+        count
+    }
+
+    // Runs the algorithm on another graph, reusing the buffers of the
+    // previous run, see `Reusable`.
+    pub fn run_on<R: Rng + ?Sized>(
+        &mut self,
+        graph: &GraphMatrix<usize>,
+        rng: &mut R,
+    ) -> Result<MstResult, GraphError> {
+        self.reset_with(graph);
+        self.grow(rng, true);
+        self.tree.result(self.num_vertices)
+    }
+
+    // Returns an estimate of the heap bytes held by the algorithm: the edge
+    // buffer, the union-find and the tree.
+    pub fn memory_footprint(&self) -> usize {
+        self.edges.heap_bytes() + self.union_find.memory_footprint() + self.tree.memory_footprint()
+    }
+
+    // Runs the algorithm and returns a set of edges representing the minimum
+    // spanning tree and its associated total cost, consuming the algorithm
+    // (see `Reusable`).
+    pub fn run<R: Rng + ?Sized>(mut self, rng: &mut R) -> Result<MstResult, GraphError> {
+        self.grow(rng, true);
+        self.tree.into_result(self.num_vertices)
+    }
+
+    // Runs the algorithm and returns only the total cost of the minimum
+    // spanning tree, its edges are never stored.
+    pub fn run_cost<R: Rng + ?Sized>(mut self, rng: &mut R) -> Result<Cost, GraphError> {
+        let count = self.grow(rng, false);
+        self.tree.total(self.num_vertices, count)
+    }
+
+    // Same as `run` with a `SplitMix64` seeded with `seed`, for reproducible
+    // runs without picking a generator.
+    pub fn run_seeded(self, seed: u64) -> Result<MstResult, GraphError> {
+        self.run(&mut SplitMix64::new(seed))
+    }
+}
+
+impl Reusable<&GraphMatrix<usize>> for FilterKruskalPlus {
+    fn reset_with(&mut self, graph: &GraphMatrix<usize>) {
+        self.num_vertices = graph.num_vertices();
+        self.edges.clear();
+        self.edges.extend_from_slice(graph.cached_edges());
+        self.union_find.reset(self.num_vertices);
+        self.tree.clear();
+    }
+}
@@ -0,0 +1,98 @@
+// # Filtered Graph
+//
+// View of a graph keeping only the edges accepted by a predicate, e.g. the
+// edges below a weight or outside a blacklist, without copying the graph.
+use crate::algorithm::Algorithm;
+use crate::constants::{Cost, VertexId};
+use crate::error::GraphError;
+use crate::graph::{Edge, Graph, Vertex};
+use crate::mst_result::MstResult;
+use rand_core::Rng;
+
+// A graph whose edges are the edges of `graph` accepted by `filter`.
+// # Example: let light = FilteredGraph::new(&mut graph, |e| e.weight < 10);
+// # Note: The graph is borrowed mutably so that `add_vertex` and `add_edge`
+// #       can go through the view, an added edge being hidden like any
+// #       other if the filter rejects it.
+pub struct FilteredGraph<'g, G, F> {
+    graph: &'g mut G,
+    filter: F,
+}
+
+impl<'g, G, F: Fn(&Edge) -> bool> FilteredGraph<'g, G, F> {
+    pub fn new(graph: &'g mut G, filter: F) -> Self {
+        FilteredGraph { graph, filter }
+    }
+
+    // Returns the underlying graph.
+    pub fn inner(&self) -> &G {
+        self.graph
+    }
+
+    // Runs `algorithm` on the edges of the view, `rng` is only used by the
+    // randomized variants.
+    // # Note: Only the accepted edges are copied, see `Algorithm::run_on_edges`.
+    pub fn run<T, R: Rng + ?Sized>(
+        &self,
+        algorithm: Algorithm,
+        rng: &mut R,
+    ) -> Result<MstResult, GraphError>
+    where
+        G: Graph<T>,
+    {
+        algorithm.run_on_edges(self.graph.num_vertices(), &self.all_edges(), rng)
+    }
+}
+
+impl<T, G, F> Graph<T> for FilteredGraph<'_, G, F>
+where
+    G: Graph<T>,
+    F: Fn(&Edge) -> bool,
+{
+    fn add_vertex(&mut self, data: T) -> VertexId {
+        self.graph.add_vertex(data)
+    }
+
+    // Adds the edge to the underlying graph, it's only visible through the
+    // view if the filter accepts it.
+    fn add_edge(&mut self, from: VertexId, to: VertexId, cost: Cost) -> Result<(), GraphError> {
+        self.graph.add_edge(from, to, cost)
+    }
+
+    fn vertex(&self, id: VertexId) -> Option<&Vertex<T>> {
+        self.graph.vertex(id)
+    }
+
+    fn vertices(&self) -> &[Vertex<T>] {
+        self.graph.vertices()
+    }
+
+    fn num_vertices(&self) -> usize {
+        self.graph.num_vertices()
+    }
+
+    // Returns the accepted edges in the order of the underlying graph.
+    fn all_edges(&self) -> Vec<Edge> {
+        let mut edges = self.graph.all_edges();
+        edges.retain(|e| (self.filter)(e));
+        edges
+    }
+
+    // Returns the weight of the edge the underlying graph reports between
+    // two vertices, if the filter accepts it.
+    fn edge_weight(&self, from: VertexId, to: VertexId) -> Option<Cost> {
+        self.graph
+            .edge_weight(from, to)
+            .filter(|&w| (self.filter)(&Edge::new(from, to, w)))
+    }
+
+    // Returns the number of accepted edges incident to a vertex.
+    // # Note: O(m), the edges of the underlying graph are scanned.
+    fn degree(&self, id: VertexId) -> usize {
+        self.graph
+            .all_edges()
+            .iter()
+            .filter(|e| (e.from == id || e.to == id) && (self.filter)(e))
+            .count()
+    }
+}
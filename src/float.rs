@@ -0,0 +1,119 @@
+// # Float Weights
+//
+// Minimum spanning trees of graphs with real-valued weights, e.g. distances
+// between points, without quantizing them into `Cost`. Weights are wrapped
+// in a totally ordered `OrderedF64` and ranked like in the `signed` module.
+use crate::algorithm::Algorithm;
+use crate::constants::VertexId;
+use crate::error::GraphError;
+use crate::graph::check_bounds;
+use crate::signed::rank_edges;
+use rand_core::Rng;
+use std::cmp::Ordering;
+
+// A f64 that is never NaN, ordered by `f64::total_cmp`.
+// # Note: -0.0 is stored as 0.0 so that the two compare equal.
+#[derive(Copy, Clone, Debug)]
+pub struct OrderedF64(f64);
+
+impl OrderedF64 {
+    // Wraps a value, None for NaN.
+    pub fn new(value: f64) -> Option<Self> {
+        if value.is_nan() {
+            None
+        } else {
+            Some(OrderedF64(value + 0.0))
+        }
+    }
+
+    pub fn get(self) -> f64 {
+        self.0
+    }
+}
+
+impl Ord for OrderedF64 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl PartialOrd for OrderedF64 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for OrderedF64 {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OrderedF64 {}
+
+// An undirected graph with f64 weights as an edge list.
+#[derive(Clone, Debug, Default)]
+pub struct FloatGraph {
+    num_vertices: usize,
+    edges: Vec<(VertexId, VertexId, OrderedF64)>,
+}
+
+// A spanning tree with f64 weights.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FloatMst {
+    pub edges: Vec<(VertexId, VertexId, f64)>,
+    pub cost: f64,
+}
+
+impl FloatGraph {
+    // Constructs a graph of `num_vertices` vertices without edges.
+    pub fn new(num_vertices: usize) -> Self {
+        FloatGraph {
+            num_vertices,
+            edges: Vec::new(),
+        }
+    }
+
+    pub fn num_vertices(&self) -> usize {
+        self.num_vertices
+    }
+
+    pub fn num_edges(&self) -> usize {
+        self.edges.len()
+    }
+
+    // Adds a weighted edge, failing if an endpoint isn't a vertex or the
+    // weight is NaN. Infinite weights are accepted.
+    pub fn add_edge(
+        &mut self,
+        from: VertexId,
+        to: VertexId,
+        weight: f64,
+    ) -> Result<(), GraphError> {
+        check_bounds(from, to, self.num_vertices)?;
+        let weight = OrderedF64::new(weight).ok_or(GraphError::NanWeight { from, to })?;
+        self.edges.push((from, to, weight));
+        Ok(())
+    }
+
+    // Runs `algorithm` on the graph, `rng` is only used by the randomized
+    // variants.
+    // # Note: Self-loops are ignored, parallel edges collapse to the
+    // #       cheapest as in `Algorithm::run_on_edges`.
+    pub fn run<R: Rng + ?Sized>(
+        &self,
+        algorithm: Algorithm,
+        rng: &mut R,
+    ) -> Result<FloatMst, GraphError> {
+        let (ranked, weights) = rank_edges(&self.edges);
+        let tree = algorithm.run_on_edges(self.num_vertices, &ranked, rng)?;
+        let edges: Vec<_> = tree
+            .edges
+            .iter()
+            .map(|e| (e.from, e.to, weights[e.weight].get()))
+            .collect();
+        // Folded from +0.0, `sum` would give -0.0 for an empty tree.
+        let cost = edges.iter().fold(0.0, |sum, &(_, _, w)| sum + w);
+        Ok(FloatMst { edges, cost })
+    }
+}
@@ -0,0 +1,103 @@
+// # Generators
+//
+// Deterministic graphs of a known shape, e.g. for tests whose expected tree
+// can be worked out by hand. Vertices are labelled with their ids and the
+// weight of each edge is given by its index in the documented edge order.
+// # Example: let g: GraphMatrix<usize> = generators::cycle(5, |i| i + 1);
+use crate::constants::{Cost, VertexId};
+use crate::graph::Graph;
+
+// Builds a graph on `num_vertices` vertices from its edges in order, the
+// i-th edge weighing `weight(i)`.
+fn build<T, G, F, K>(num_vertices: usize, edges: K, mut weight: F) -> G
+where
+    G: Graph<T> + Default,
+    T: From<VertexId>,
+    F: FnMut(usize) -> Cost,
+    K: IntoIterator<Item = (VertexId, VertexId)>,
+{
+    let triples = edges
+        .into_iter()
+        .enumerate()
+        .map(|(i, (from, to))| (from, to, weight(i)));
+    G::from_edges(num_vertices, triples).expect("the endpoints are vertices")
+}
+
+// Returns the path 0 - 1 - ... - (n - 1), the i-th edge joining i and i + 1.
+pub fn path<T, G, F>(num_vertices: usize, weight: F) -> G
+where
+    G: Graph<T> + Default,
+    T: From<VertexId>,
+    F: FnMut(usize) -> Cost,
+{
+    let edges = (1..num_vertices).map(|v| (v - 1, v));
+    build(num_vertices, edges, weight)
+}
+
+// Returns the path closed by a last edge from n - 1 back to 0.
+// # Note: Below 3 vertices there is no closing edge, it would be a
+// #       self-loop or a parallel edge, so the cycle is a path.
+pub fn cycle<T, G, F>(num_vertices: usize, weight: F) -> G
+where
+    G: Graph<T> + Default,
+    T: From<VertexId>,
+    F: FnMut(usize) -> Cost,
+{
+    let closing = (num_vertices >= 3).then(|| (num_vertices - 1, 0));
+    let edges = (1..num_vertices).map(|v| (v - 1, v)).chain(closing);
+    build(num_vertices, edges, weight)
+}
+
+// Returns the star centered in 0, the i-th edge joining 0 and i + 1.
+pub fn star<T, G, F>(num_vertices: usize, weight: F) -> G
+where
+    G: Graph<T> + Default,
+    T: From<VertexId>,
+    F: FnMut(usize) -> Cost,
+{
+    let edges = (1..num_vertices).map(|v| (0, v));
+    build(num_vertices, edges, weight)
+}
+
+// Returns the complete bipartite graph between the vertices 0..left and
+// left..left + right, the edge joining i and left + j being the
+// (i * right + j)-th.
+pub fn complete_bipartite<T, G, F>(left: usize, right: usize, weight: F) -> G
+where
+    G: Graph<T> + Default,
+    T: From<VertexId>,
+    F: FnMut(usize) -> Cost,
+{
+    let edges = (0..left).flat_map(|i| (left..left + right).map(move |j| (i, j)));
+    build(left + right, edges, weight)
+}
+
+// Returns the complete binary tree filled level by level, the i-th edge
+// joining i + 1 to its parent i / 2.
+pub fn binary_tree<T, G, F>(num_vertices: usize, weight: F) -> G
+where
+    G: Graph<T> + Default,
+    T: From<VertexId>,
+    F: FnMut(usize) -> Cost,
+{
+    let edges = (1..num_vertices).map(|v| ((v - 1) / 2, v));
+    build(num_vertices, edges, weight)
+}
+
+// Returns the ladder of `rungs` rungs, two paths 0..rungs and
+// rungs..2 * rungs with a rung between i and rungs + i. The rungs come
+// first in the edge order, then the edges of the first path and of the
+// second one, 3 * rungs - 2 edges in all.
+pub fn ladder<T, G, F>(rungs: usize, weight: F) -> G
+where
+    G: Graph<T> + Default,
+    T: From<VertexId>,
+    F: FnMut(usize) -> Cost,
+{
+    let steps = (1..rungs).map(|v| (v - 1, v));
+    let edges = (0..rungs)
+        .map(|i| (i, rungs + i))
+        .chain(steps.clone())
+        .chain(steps.map(|(from, to)| (rungs + from, rungs + to)));
+    build(2 * rungs, edges, weight)
+}
@@ -1,15 +1,80 @@
-use crate::constants::{Cost, VertexId};
+use crate::constants::{Cost, EdgeId, VertexId};
+use crate::error::GraphError;
+#[cfg(feature = "rand")]
+use rand::{Rng, RngExt};
 use std::cmp::Ordering;
+use std::fmt;
 
 // A basic definition of a graph used by algorithms.
 pub trait Graph<T> {
     // TODO: new_random e new_from_collection
     fn add_vertex(&mut self, data: T) -> VertexId;
-    fn add_edge(&mut self, from: VertexId, to: VertexId, cost: Cost);
+    // Fails with `VertexOutOfBounds` if an endpoint isn't a vertex.
+    fn add_edge(&mut self, from: VertexId, to: VertexId, cost: Cost) -> Result<(), GraphError>;
     fn vertex(&self, id: VertexId) -> Option<&Vertex<T>>;
     fn vertices(&self) -> &[Vertex<T>];
     fn num_vertices(&self) -> usize;
     fn all_edges(&self) -> Vec<Edge>;
+    fn edge_weight(&self, from: VertexId, to: VertexId) -> Option<Cost>;
+    fn degree(&self, id: VertexId) -> usize;
+
+    // Returns the edge with the given id, its position in `all_edges`.
+    // # Note: O(m) by default, O(1) for the representations keeping their
+    // #       edges in insertion order, where ids are stable.
+    fn edge(&self, id: EdgeId) -> Option<Edge> {
+        self.all_edges().get(id).copied()
+    }
+
+    // Returns the number of edges without collecting them.
+    // # Note: O(m) by default, O(1) for the representations counting their
+    // #       edges as they're added.
+    fn num_edges(&self) -> usize {
+        self.all_edges().len()
+    }
+
+    // Returns true if there is an edge between the two vertices.
+    fn has_edge(&self, from: VertexId, to: VertexId) -> bool {
+        self.edge_weight(from, to).is_some()
+    }
+
+    // Constructs a graph of `num_vertices` vertices labelled with their ids
+    // from a list of `(from, to, cost)` triples.
+    // # Example: let g = GraphMatrix::from_edges(3, [(0, 1, 4), (1, 2, 2)])?;
+    fn from_edges<K>(num_vertices: usize, edges: K) -> Result<Self, GraphError>
+    where
+        Self: Default + Sized,
+        T: From<VertexId>,
+        K: IntoIterator<Item = (VertexId, VertexId, Cost)>,
+    {
+        let mut graph = Self::default();
+        for id in 0..num_vertices {
+            graph.add_vertex(T::from(id));
+        }
+        for (from, to, cost) in edges {
+            check_edge(from, to, num_vertices)?;
+            graph.add_edge(from, to, cost)?;
+        }
+        Ok(graph)
+    }
+
+    // Same as `from_edges` but repeated edges, in either direction, collapse
+    // to the cheapest one whatever the representation does with them, e.g.
+    // for datasets with duplicate rows.
+    // # Note: O(m log m), the edges are inserted sorted by endpoints.
+    fn from_edges_dedup<K>(num_vertices: usize, edges: K) -> Result<Self, GraphError>
+    where
+        Self: Default + Sized,
+        T: From<VertexId>,
+        K: IntoIterator<Item = (VertexId, VertexId, Cost)>,
+    {
+        let mut triples: Vec<(VertexId, VertexId, Cost)> = edges
+            .into_iter()
+            .map(|(from, to, cost)| (from.min(to), from.max(to), cost))
+            .collect();
+        triples.sort_unstable();
+        triples.dedup_by_key(|&mut (from, to, _)| (from, to));
+        Self::from_edges(num_vertices, triples)
+    }
 }
 
 // Representation for generic Nodes or Vertices.
@@ -20,7 +85,10 @@ pub struct Vertex<T> {
 }
 
 // Representation for generic Edges.
+// # Note: `repr(C)` fixes the layout used by the edge files.
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
 pub struct Edge {
     pub from: VertexId,
     pub to: VertexId,
@@ -33,11 +101,269 @@ impl Edge {
     pub fn new(from: VertexId, to: VertexId, weight: Cost) -> Edge {
         Edge { from, to, weight }
     }
+
+    // Returns the canonical ordering key: weight, then the smaller and the
+    // larger endpoint. Ties between equal weights are broken the same way
+    // by every algorithm, so they all return the same tree.
+    #[inline]
+    pub fn key(&self) -> (Cost, VertexId, VertexId) {
+        (self.weight, self.from.min(self.to), self.from.max(self.to))
+    }
+
+    // Returns the edge oriented from its smaller endpoint.
+    pub fn normalized(&self) -> Edge {
+        Edge::new(self.from.min(self.to), self.from.max(self.to), self.weight)
+    }
+}
+
+// Broken invariant of a graph structure, as reported by `validate`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Violation {
+    // The vertex stored at `index` has another id.
+    VertexId {
+        index: usize,
+        id: VertexId,
+    },
+    EndpointOutOfBounds {
+        edge: Edge,
+        num_vertices: usize,
+    },
+    SelfLoop {
+        edge: Edge,
+    },
+    // An edge stored in the star of a vertex that isn't its origin.
+    MisplacedEdge {
+        vertex: VertexId,
+        edge: Edge,
+    },
+    // An edge whose reverse is missing from the other endpoint's star.
+    MissingReverse {
+        edge: Edge,
+    },
+    // A repeated edge the edge policy doesn't allow.
+    ParallelEdge {
+        edge: Edge,
+    },
+    // A cached edge whose weight differs from the matrix entry.
+    StaleCache {
+        edge: Edge,
+        stored: Option<Cost>,
+    },
+    // A matrix entry without its cached edge.
+    UncachedEdge {
+        edge: Edge,
+    },
+    // A cached edge the cache index doesn't map its matrix entry to.
+    UnindexedEdge {
+        edge: Edge,
+    },
+    DegreeMismatch {
+        vertex: VertexId,
+        degree: usize,
+        expected: usize,
+    },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::VertexId { index, id } => {
+                write!(f, "Vertex at index {} has id {}", index, id)
+            }
+            Violation::EndpointOutOfBounds { edge, num_vertices } => write!(
+                f,
+                "Edge {:?} out of bounds for a graph with {} vertices",
+                edge, num_vertices
+            ),
+            Violation::SelfLoop { edge } => write!(f, "Self-loop {:?}", edge),
+            Violation::MisplacedEdge { vertex, edge } => {
+                write!(f, "Edge {:?} stored in the star of vertex {}", edge, vertex)
+            }
+            Violation::MissingReverse { edge } => write!(f, "Edge {:?} has no reverse", edge),
+            Violation::ParallelEdge { edge } => {
+                write!(f, "Parallel edge {:?} against the edge policy", edge)
+            }
+            Violation::StaleCache { edge, stored } => {
+                write!(f, "Cached edge {:?} but the matrix has {:?}", edge, stored)
+            }
+            Violation::UncachedEdge { edge } => write!(f, "Matrix edge {:?} isn't cached", edge),
+            Violation::UnindexedEdge { edge } => {
+                write!(f, "Cached edge {:?} isn't in the cache index", edge)
+            }
+            Violation::DegreeMismatch {
+                vertex,
+                degree,
+                expected,
+            } => write!(
+                f,
+                "Vertex {} has degree {} but {} incident edges",
+                vertex, degree, expected
+            ),
+        }
+    }
+}
+
+// Checks that both endpoints of an edge are existing vertices.
+pub(crate) fn check_bounds(
+    from: VertexId,
+    to: VertexId,
+    num_vertices: usize,
+) -> Result<(), GraphError> {
+    let vertex = from.max(to);
+    if vertex >= num_vertices {
+        return Err(GraphError::VertexOutOfBounds {
+            vertex,
+            num_vertices,
+        });
+    }
+    Ok(())
+}
+
+// Checks that an edge joins two distinct existing vertices.
+pub(crate) fn check_edge(
+    from: VertexId,
+    to: VertexId,
+    num_vertices: usize,
+) -> Result<(), GraphError> {
+    check_bounds(from, to, num_vertices)?;
+    if from == to {
+        return Err(GraphError::SelfLoop { vertex: from });
+    }
+    Ok(())
+}
+
+// Maps each vertex of a graph with `num_vertices` vertices to its position
+// in `vertices`, None if it's left out, and returns the vertices kept.
+// # Note: A repeated vertex keeps its first position.
+pub(crate) fn index_vertices(
+    vertices: &[VertexId],
+    num_vertices: usize,
+) -> Result<(Vec<Option<VertexId>>, Vec<VertexId>), GraphError> {
+    let mut ids = vec![None; num_vertices];
+    let mut kept = Vec::with_capacity(vertices.len());
+    for &vertex in vertices {
+        check_bounds(vertex, vertex, num_vertices)?;
+        if ids[vertex].is_none() {
+            ids[vertex] = Some(kept.len());
+            kept.push(vertex);
+        }
+    }
+    Ok((ids, kept))
+}
+
+// Picks each pair of vertices (from, to) with from < to, or from <= to with
+// self-loops, with probability `p` and passes it to `emit` along with the
+// generator, in increasing order of `from` then `to`.
+// # Note: O(n + m) expected for m pairs picked: the number of pairs skipped
+// #       before the next one is drawn from the geometric distribution
+// #       instead of flipping a coin per pair. `p` must be in [0, 1].
+#[cfg(feature = "rand")]
+pub(crate) fn random_pairs<R, F>(
+    num_vertices: usize,
+    p: f64,
+    no_self_loops: bool,
+    rng: &mut R,
+    mut emit: F,
+) where
+    R: Rng + ?Sized,
+    F: FnMut(VertexId, VertexId, &mut R),
+{
+    let n = num_vertices;
+    let first = |from: VertexId| if no_self_loops { from + 1 } else { from };
+    if p <= 0.0 {
+        return;
+    }
+    if p >= 1.0 {
+        for from in 0..n {
+            for to in first(from)..n {
+                emit(from, to, rng);
+            }
+        }
+        return;
+    }
+
+    let log_q = (1.0 - p).ln();
+    let (mut from, mut to) = (0, first(0));
+    while from < n {
+        // 1 - r is in (0, 1], so the number of failures is finite.
+        let r: f64 = rng.random();
+        let mut skip = ((1.0 - r).ln() / log_q).floor().min(usize::MAX as f64) as usize;
+        while from < n && skip >= n.saturating_sub(to) {
+            skip -= n.saturating_sub(to);
+            from += 1;
+            to = first(from);
+        }
+        if from >= n {
+            break;
+        }
+        to += skip;
+        emit(from, to, rng);
+        to += 1;
+    }
+}
+
+// Checks that a dense adjacency matrix is square and symmetric.
+// # Note: The diagonal is not checked since it's ignored by `from_dense`.
+pub(crate) fn validate_dense(matrix: &[Vec<Option<Cost>>]) -> Result<(), GraphError> {
+    let n = matrix.len();
+    if let Some((row, cols)) = matrix.iter().enumerate().find(|(_, r)| r.len() != n) {
+        return Err(GraphError::InvalidShape {
+            row,
+            len: cols.len(),
+        });
+    }
+    for (from, row) in matrix.iter().enumerate() {
+        for (to, cost) in row.iter().enumerate().skip(from + 1) {
+            if *cost != matrix[to][from] {
+                return Err(GraphError::Asymmetric { from, to });
+            }
+        }
+    }
+    Ok(())
+}
+
+// Orients every edge from its smaller endpoint.
+pub fn normalize_edges(edges: &mut [Edge]) {
+    for edge in edges.iter_mut() {
+        *edge = edge.normalized();
+    }
+}
+
+// Puts an edge list in canonical form: normalized and sorted by `Edge::key`,
+// so that two lists with the same edges are identical.
+pub fn sort_canonical(edges: &mut [Edge]) {
+    normalize_edges(edges);
+    edges.sort_unstable_by_key(Edge::key);
+}
+
+// Returns true if both lists hold the same edges, whatever their order and
+// orientation, a repeated edge being repeated as many times on both sides.
+// # Example: assert!(same_edges(&kruskal.edges, &prim.edges));
+// # Note: O(m log m), see `diff` to know how they differ.
+pub fn same_edges(a: &[Edge], b: &[Edge]) -> bool {
+    let key_list = |edges: &[Edge]| {
+        let mut keys: Vec<_> = edges.iter().map(Edge::key).collect();
+        keys.sort_unstable();
+        keys
+    };
+    a.len() == b.len() && key_list(a) == key_list(b)
+}
+
+// Sorts each run of equal weights of a weight-sorted slice by `Edge::key`,
+// for the variants whose sort only looks at the weights.
+pub(crate) fn sort_ties(edges: &mut [Edge]) {
+    for run in edges.chunk_by_mut(|a, b| a.weight == b.weight) {
+        if run.len() > 1 {
+            run.sort_unstable_by_key(Edge::key);
+        }
+    }
 }
 
+// Edges are ordered and compared by `Edge::key`, so an edge and its
+// reverse are equal.
 impl Ord for Edge {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.weight.cmp(&other.weight)
+        self.key().cmp(&other.key())
     }
 }
 
@@ -49,7 +375,7 @@ impl PartialOrd for Edge {
 
 impl PartialEq for Edge {
     fn eq(&self, other: &Self) -> bool {
-        self.weight == other.weight
+        self.key() == other.key()
     }
 }
 
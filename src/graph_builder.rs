@@ -0,0 +1,230 @@
+// # Graph Builder
+//
+// Fluent construction of graphs, validating all the edges at build time.
+use crate::constants::{Cost, VertexId};
+use crate::error::GraphError;
+use crate::graph::{check_edge, Graph};
+use crate::graph_matrix::GraphMatrix;
+use crate::graph_stars::{EdgePolicy, GraphStars};
+#[cfg(feature = "parallel")]
+use rayon::slice::ParallelSliceMut;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+// Collects vertices and edges before building either representation.
+// # Example: let g = GraphBuilder::new().vertices(0..3).edge(0, 1, 5).edge(1, 2, 3).build_stars()?;
+#[derive(Clone, Debug)]
+pub struct GraphBuilder<T> {
+    vertices: Vec<T>,
+    edges: Vec<(VertexId, VertexId, Cost)>,
+    policy: EdgePolicy,
+}
+
+impl<T: Clone + Eq> Default for GraphBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone + Eq> GraphBuilder<T> {
+    pub fn new() -> Self {
+        GraphBuilder {
+            vertices: Vec::new(),
+            edges: Vec::new(),
+            policy: EdgePolicy::default(),
+        }
+    }
+
+    // Adds a vertex, its id is the number of vertices added before it.
+    pub fn vertex(mut self, data: T) -> Self {
+        self.vertices.push(data);
+        self
+    }
+
+    // Adds the vertices of a generic collection, in iteration order.
+    pub fn vertices<K: IntoIterator<Item = T>>(mut self, collection: K) -> Self {
+        self.vertices.extend(collection);
+        self
+    }
+
+    // Adds a weighted edge between two vertex ids.
+    pub fn edge(mut self, from: VertexId, to: VertexId, cost: Cost) -> Self {
+        self.edges.push((from, to, cost));
+        self
+    }
+
+    // Adds the (from, to, cost) triples of a generic collection.
+    pub fn edges_from<K>(mut self, edges: K) -> Self
+    where
+        K: IntoIterator<Item = (VertexId, VertexId, Cost)>,
+    {
+        self.edges.extend(edges);
+        self
+    }
+
+    // Sets how `build_stars` treats repeated edges.
+    pub fn edge_policy(mut self, policy: EdgePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    // Checks that every edge joins two distinct existing vertices.
+    fn validate(&self) -> Result<(), GraphError> {
+        let num_vertices = self.vertices.len();
+        for &(from, to, _) in &self.edges {
+            check_edge(from, to, num_vertices)?;
+        }
+        Ok(())
+    }
+
+    // Builds an adjacency list graph.
+    pub fn build_stars(self) -> Result<GraphStars<T>, GraphError> {
+        self.validate()?;
+        let mut graph = GraphStars::with_edge_policy(self.policy);
+        for data in self.vertices {
+            graph.add_vertex(data);
+        }
+        for (from, to, cost) in self.edges {
+            graph.add_edge(from, to, cost)?;
+        }
+        Ok(graph)
+    }
+
+    // Builds an adjacency matrix graph, a repeated edge overwrites the cost.
+    pub fn build_matrix(self) -> Result<GraphMatrix<T>, GraphError> {
+        self.validate()?;
+        let mut graph = GraphMatrix::new_from_collection(self.vertices);
+        for (from, to, cost) in self.edges {
+            graph.add_edge(from, to, cost)?;
+        }
+        Ok(graph)
+    }
+}
+
+// Edges buffered by an `EdgeWriter` before taking the shared lock.
+const WRITER_CHUNK: usize = 4096;
+
+// Builder shared between threads, e.g. parsing chunks of a large input file
+// in parallel. Each thread fills its own `EdgeWriter` buffer, which is only
+// appended to the shared edge list in chunks, and the graph is built once by
+// `freeze_stars` or `freeze_matrix`.
+// # Example: thread::scope(|s| for part in parts { s.spawn(|| parse(part, builder.writer())); });
+// # Note: The frozen graph doesn't depend on how the writers were scheduled:
+// #       edges are stored with from < to and sorted by endpoints then cost,
+// #       so repeated edges come cheapest first.
+pub struct ConcurrentGraphBuilder<T> {
+    vertices: Vec<T>,
+    edges: Mutex<Vec<(VertexId, VertexId, Cost)>>,
+    policy: EdgePolicy,
+}
+
+// Thread-local edge buffer of a `ConcurrentGraphBuilder`, flushed when full
+// and when dropped.
+pub struct EdgeWriter<'a, T> {
+    builder: &'a ConcurrentGraphBuilder<T>,
+    buffer: Vec<(VertexId, VertexId, Cost)>,
+}
+
+impl<T> ConcurrentGraphBuilder<T> {
+    fn lock(&self) -> MutexGuard<'_, Vec<(VertexId, VertexId, Cost)>> {
+        // A panicking writer can't leave a half-appended chunk behind.
+        self.edges.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T: Clone + Eq> ConcurrentGraphBuilder<T> {
+    // Constructs a builder over a fixed set of vertices, so that edges can
+    // be checked as soon as they're added.
+    pub fn new<K: IntoIterator<Item = T>>(vertices: K) -> Self {
+        ConcurrentGraphBuilder {
+            vertices: vertices.into_iter().collect(),
+            edges: Mutex::new(Vec::new()),
+            policy: EdgePolicy::default(),
+        }
+    }
+
+    // Sets how `freeze_stars` treats repeated edges.
+    pub fn edge_policy(mut self, policy: EdgePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn num_vertices(&self) -> usize {
+        self.vertices.len()
+    }
+
+    // Returns a buffer for the calling thread.
+    pub fn writer(&self) -> EdgeWriter<'_, T> {
+        EdgeWriter {
+            builder: self,
+            buffer: Vec::with_capacity(WRITER_CHUNK),
+        }
+    }
+
+    // Adds a single edge through the shared lock, prefer a `writer` for
+    // many edges.
+    pub fn add_edge(&self, from: VertexId, to: VertexId, cost: Cost) -> Result<(), GraphError> {
+        check_edge(from, to, self.vertices.len())?;
+        self.lock().push((from.min(to), from.max(to), cost));
+        Ok(())
+    }
+
+    // Collects the edges of every writer in their canonical order.
+    fn into_builder(self) -> GraphBuilder<T> {
+        let mut edges = self
+            .edges
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner);
+        #[cfg(feature = "parallel")]
+        edges.par_sort_unstable();
+        #[cfg(not(feature = "parallel"))]
+        edges.sort_unstable();
+        GraphBuilder {
+            vertices: self.vertices,
+            edges,
+            policy: self.policy,
+        }
+    }
+
+    // Builds an adjacency list graph, repeated edges are handled by the
+    // edge policy.
+    // # Note: Writers borrow the builder, so they are all flushed by then.
+    pub fn freeze_stars(self) -> Result<GraphStars<T>, GraphError> {
+        self.into_builder().build_stars()
+    }
+
+    // Builds an adjacency matrix graph, repeated edges collapse to the
+    // cheapest.
+    pub fn freeze_matrix(self) -> Result<GraphMatrix<T>, GraphError> {
+        let mut builder = self.into_builder();
+        builder.edges.dedup_by_key(|&mut (from, to, _)| (from, to));
+        builder.build_matrix()
+    }
+}
+
+impl<T: Clone + Eq> EdgeWriter<'_, T> {
+    // Adds a weighted edge, failing if it isn't between two distinct
+    // vertices of the builder.
+    pub fn add_edge(&mut self, from: VertexId, to: VertexId, cost: Cost) -> Result<(), GraphError> {
+        check_edge(from, to, self.builder.vertices.len())?;
+        self.buffer.push((from.min(to), from.max(to), cost));
+        if self.buffer.len() >= WRITER_CHUNK {
+            self.flush();
+        }
+        Ok(())
+    }
+}
+
+impl<T> EdgeWriter<'_, T> {
+    // Appends the buffered edges to the shared list.
+    pub fn flush(&mut self) {
+        if !self.buffer.is_empty() {
+            self.builder.lock().append(&mut self.buffer);
+        }
+    }
+}
+
+impl<T> Drop for EdgeWriter<'_, T> {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
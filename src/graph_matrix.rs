@@ -1,15 +1,30 @@
-use crate::constants::{Cost, VertexId};
+use crate::constants::{Cost, EdgeId, VertexId};
 use crate::error::GraphError;
-use crate::graph::{Edge, Graph, Vertex};
-use crate::MAX_COST;
-use rand::distr::{Distribution, Uniform};
-use rand::Rng;
+#[cfg(feature = "rand")]
+use crate::graph::random_pairs;
+use crate::graph::{check_bounds, index_vertices, validate_dense, Edge, Graph, Vertex, Violation};
+use crate::graph_stars::GraphStars;
+use crate::memory::HeapBytes;
+#[cfg(feature = "rand")]
+use rand::{
+    distr::{Distribution, Uniform},
+    rngs::StdRng,
+    Rng, SeedableRng,
+};
 use std::mem;
 // Graph representation using nodes' outgoing stars.
 pub struct GraphMatrix<T> {
     vertices: Vec<Vertex<T>>,
     adj_matrix: Vec<Cost>,
+    // One bit per matrix entry set if the edge exists, so any weight can
+    // be stored.
+    present: Vec<u64>,
     cached_edges: Vec<Edge>,
+    // Position in `cached_edges` of the edge at each matrix entry, parallel
+    // to `adj_matrix` and only meaningful where the edge exists, so that
+    // replacing a weight doesn't search the cache.
+    cache_index: Vec<EdgeId>,
+    degrees: Vec<usize>,
 }
 
 impl<T: Clone + Eq> Default for GraphMatrix<T> {
@@ -24,15 +39,37 @@ impl<T: Clone + Eq> GraphMatrix<T> {
             vertices: Vec::new(),
             // # Note: Using a flattened matrix with col*(col-1)/2 + row
             adj_matrix: Vec::new(),
+            present: Vec::new(),
             cached_edges: Vec::new(),
+            cache_index: Vec::new(),
+            degrees: Vec::new(),
+        }
+    }
+
+    // Constructs an empty graph with room for `num_vertices` vertices, the
+    // triangular matrix being allocated once rather than grown by every
+    // `add_vertex`.
+    pub fn with_capacity(num_vertices: usize) -> Self {
+        // The matrix keeps a spare row past the last vertex, see `add_vertex`.
+        let n = num_vertices;
+        let entries = n * (n + 1) / 2;
+        GraphMatrix {
+            vertices: Vec::with_capacity(n),
+            adj_matrix: Vec::with_capacity(entries),
+            present: Vec::with_capacity(entries.div_ceil(64)),
+            cached_edges: Vec::new(),
+            cache_index: Vec::with_capacity(entries),
+            degrees: Vec::with_capacity(n),
         }
     }
 
     // Constructs a graph from a generic collection's iterator
     // # Example: let g = GraphMatrix::new_from_collection(vec![1,2,3,4]);
-    // # Note: O(n^2) due to resizing the matrix at each step.
+    // # Note: O(n^2) to fill the matrix, allocated once if the iterator
+    // #       knows its length, see `with_capacity`.
     pub fn new_from_collection<K: IntoIterator<Item = T>>(collection: K) -> Self {
-        let mut this = GraphMatrix::new();
+        let collection = collection.into_iter();
+        let mut this = GraphMatrix::with_capacity(collection.size_hint().0);
         for v in collection {
             this.add_vertex(v);
         }
@@ -48,10 +85,12 @@ impl<T: Clone + Eq> GraphMatrix<T> {
     //
     // # Example: let g = Graph::new_random(vec![1, 2, 3], 0.5, 0, 100 );
     //
-    // # Note: O(n^2) complexity.
+    // # Note: O(n^2) for the matrix, the edges are drawn in O(n + m)
+    // #       expected, see `random_pairs`.
     //
     // # Panic: Probabilities must be expressed with a f64 between 0.0 and 1.0,
     // #        edge's cost range must be valid (a <= b).
+    #[cfg(feature = "rand")]
     pub fn new_random<K, R>(
         collection: K,
         p: f64,
@@ -62,7 +101,7 @@ impl<T: Clone + Eq> GraphMatrix<T> {
     ) -> Result<Self, GraphError>
     where
         K: IntoIterator<Item = T>,
-        R: Rng,
+        R: Rng + ?Sized,
     {
         if !(0.0..=1.0).contains(&p) {
             return Err(GraphError::InvalidProbability(p));
@@ -79,24 +118,33 @@ impl<T: Clone + Eq> GraphMatrix<T> {
 
         let num_vertices = graph.num_vertices();
 
-        for from_idx in 0..num_vertices {
-            // Optimization: Start inner loop based on self-loops
-            let start = if no_self_loops {
-                from_idx + 1
-            } else {
-                from_idx
-            };
-
-            for to_idx in start..num_vertices {
-                if rng.random::<f64>() < p {
-                    let cost = cost_dist.sample(rng);
-                    graph.add_edge(from_idx, to_idx, cost);
-                }
-            }
-        }
+        random_pairs(num_vertices, p, no_self_loops, rng, |from, to, rng| {
+            let cost = cost_dist.sample(rng);
+            graph
+                .add_edge(from, to, cost)
+                .expect("the endpoints are vertices");
+        });
         Ok(graph)
     }
 
+    // Same as `new_random` but draws from a `StdRng` seeded with `seed`,
+    // for reproducible graphs and targets without an OS entropy source.
+    #[cfg(feature = "rand")]
+    pub fn new_random_seeded<K>(
+        collection: K,
+        p: f64,
+        min_cost: usize,
+        max_cost: usize,
+        no_self_loops: bool,
+        seed: u64,
+    ) -> Result<Self, GraphError>
+    where
+        K: IntoIterator<Item = T>,
+    {
+        let mut rng = StdRng::seed_from_u64(seed);
+        Self::new_random(collection, p, min_cost, max_cost, no_self_loops, &mut rng)
+    }
+
     // Calculates index in the flattened adjacency matrix.
     fn index(&self, mut row: VertexId, mut col: VertexId) -> VertexId {
         if row > col {
@@ -105,9 +153,285 @@ impl<T: Clone + Eq> GraphMatrix<T> {
         col * (col - 1) / 2 + row
     }
 
-    // Returns a copy of the compressed adjacency matrix
-    pub fn adj_matrix(self) -> Vec<Cost> {
-        self.adj_matrix.clone()
+    // Returns true if the matrix entry at `index` holds an edge.
+    fn is_present(&self, index: usize) -> bool {
+        self.present[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    // Returns the cost of the edge between two distinct vertices, if any.
+    pub(crate) fn cost(&self, from: VertexId, to: VertexId) -> Option<Cost> {
+        let index = self.index(from, to);
+        self.is_present(index).then(|| self.adj_matrix[index])
+    }
+
+    // Changes the weight of an existing edge in both the matrix and the
+    // cached edges, so re-running an algorithm sees it, and returns the
+    // previous weight.
+    pub fn update_edge(
+        &mut self,
+        from: VertexId,
+        to: VertexId,
+        new_cost: Cost,
+    ) -> Result<Cost, GraphError> {
+        check_bounds(from, to, self.num_vertices())?;
+        let previous = self
+            .edge_weight(from, to)
+            .ok_or(GraphError::MissingEdge { from, to })?;
+        let index = self.index(from, to);
+        self.adj_matrix[index] = new_cost;
+        self.set_cached_weight(index, new_cost);
+        Ok(previous)
+    }
+
+    // Sets the weight of the cached copy of the edge at a matrix entry.
+    fn set_cached_weight(&mut self, index: usize, cost: Cost) {
+        if self.is_present(index) {
+            self.cached_edges[self.cache_index[index]].weight = cost;
+        }
+    }
+
+    // Returns the edges in insertion order without a copy.
+    pub(crate) fn cached_edges(&self) -> &[Edge] {
+        &self.cached_edges
+    }
+
+    // Returns an estimate of the heap bytes held by the graph: the vertices,
+    // the matrix with its bitset, the cached edges with their index and the
+    // degrees.
+    // # Note: Heap memory owned by the vertex data isn't counted.
+    pub fn memory_footprint(&self) -> usize {
+        self.vertices.heap_bytes()
+            + self.adj_matrix.heap_bytes()
+            + self.present.heap_bytes()
+            + self.cached_edges.heap_bytes()
+            + self.cache_index.heap_bytes()
+            + self.degrees.heap_bytes()
+    }
+
+    // Checks the invariants of the structure: vertex ids, cached edges in
+    // range, without self-loops, matching the matrix one to one and indexed
+    // by their entry, and degrees counting them.
+    // # Note: O(n^2 + m), meant for graphs built from untrusted input.
+    pub fn validate(&self) -> Vec<Violation> {
+        let n = self.num_vertices();
+        let mut violations = Vec::new();
+        for (index, vertex) in self.vertices.iter().enumerate() {
+            if vertex.id != index {
+                violations.push(Violation::VertexId {
+                    index,
+                    id: vertex.id,
+                });
+            }
+        }
+
+        let mut expected = vec![0; n];
+        let mut cached = vec![false; self.adj_matrix.len()];
+        for (id, &edge) in self.cached_edges.iter().enumerate() {
+            if edge.from >= n || edge.to >= n {
+                violations.push(Violation::EndpointOutOfBounds {
+                    edge,
+                    num_vertices: n,
+                });
+                continue;
+            }
+            expected[edge.from] += 1;
+            expected[edge.to] += 1;
+            if edge.from == edge.to {
+                violations.push(Violation::SelfLoop { edge });
+                continue;
+            }
+            let index = self.index(edge.from, edge.to);
+            if mem::replace(&mut cached[index], true) {
+                violations.push(Violation::ParallelEdge { edge });
+            }
+            if self.cache_index[index] != id {
+                violations.push(Violation::UnindexedEdge { edge });
+            }
+            let stored = self.cost(edge.from, edge.to);
+            if stored != Some(edge.weight) {
+                violations.push(Violation::StaleCache { edge, stored });
+            }
+        }
+
+        for to in 1..n {
+            for from in 0..to {
+                if let Some(cost) = self.cost(from, to)
+                    && !cached[self.index(from, to)]
+                {
+                    violations.push(Violation::UncachedEdge {
+                        edge: Edge::new(from, to, cost),
+                    });
+                }
+            }
+        }
+        for (vertex, (&degree, &expected)) in self.degrees.iter().zip(&expected).enumerate() {
+            if degree != expected {
+                violations.push(Violation::DegreeMismatch {
+                    vertex,
+                    degree,
+                    expected,
+                });
+            }
+        }
+        violations
+    }
+
+    // Returns the subgraph induced by `vertices`, the i-th of them becoming
+    // vertex i, with the edges between them in insertion order.
+    // # Example: let community = graph.subgraph(&[4, 8, 15])?;
+    // # Note: A repeated vertex is only kept once.
+    pub fn subgraph(&self, vertices: &[VertexId]) -> Result<Self, GraphError> {
+        let (ids, kept) = index_vertices(vertices, self.num_vertices())?;
+        let mut graph =
+            GraphMatrix::new_from_collection(kept.iter().map(|&v| self.vertices[v].data.clone()));
+        for edge in &self.cached_edges {
+            if let (Some(from), Some(to)) = (ids[edge.from], ids[edge.to]) {
+                graph
+                    .add_edge(from, to, edge.weight)
+                    .expect("the endpoints are vertices of the subgraph");
+            }
+        }
+        Ok(graph)
+    }
+
+    // Returns a copy of the graph with every weight replaced by `f(weight)`,
+    // e.g. scaled or inverted to get a maximum spanning tree.
+    // # Example: let inverted = graph.map_weights(|w| MAX_COST - w);
+    // # Note: O(n^2) copy, edge ids are kept.
+    pub fn map_weights<F: Fn(Cost) -> Cost>(&self, f: F) -> Self {
+        let cached_edges: Vec<Edge> = self
+            .cached_edges
+            .iter()
+            .map(|e| Edge::new(e.from, e.to, f(e.weight)))
+            .collect();
+        let mut adj_matrix = self.adj_matrix.clone();
+        for edge in &cached_edges {
+            let index = self.index(edge.from, edge.to);
+            adj_matrix[index] = edge.weight;
+        }
+        GraphMatrix {
+            vertices: self.vertices.clone(),
+            adj_matrix,
+            present: self.present.clone(),
+            cached_edges,
+            cache_index: self.cache_index.clone(),
+            degrees: self.degrees.clone(),
+        }
+    }
+
+    // Adds the vertices and edges of `other`. With `offset_ids` its vertices
+    // are appended after the current ones (disjoint union), otherwise vertex
+    // ids are shared and the edges are overlaid, a duplicate edge keeping
+    // the cheaper weight. Vertices of `other` beyond the current ones are
+    // appended either way.
+    // # Example: for shard in &shards { graph.merge(shard, true); }
+    pub fn merge(&mut self, other: &Self, offset_ids: bool) {
+        let n = self.num_vertices();
+        let offset = if offset_ids { n } else { 0 };
+        for vertex in other.vertices.iter().skip(n - offset) {
+            self.add_vertex(vertex.data.clone());
+        }
+        for edge in &other.cached_edges {
+            let (from, to) = (edge.from + offset, edge.to + offset);
+            if self.edge_weight(from, to).is_none_or(|w| edge.weight < w) {
+                self.add_edge(from, to, edge.weight)
+                    .expect("the endpoints are merged vertices");
+            }
+        }
+    }
+
+    // Returns a borrowed view of the compressed (lower triangular)
+    // adjacency matrix, see `TriangularView`.
+    pub fn adj_matrix(&self) -> TriangularView<'_, T> {
+        TriangularView { graph: self }
+    }
+
+    // Exports the adjacency matrix as a dense, row-major n x n matrix, with
+    // `missing` where there is no edge (the diagonal included).
+    // # Note: O(n^2) memory, twice the triangular matrix.
+    pub fn to_dense(&self, missing: Cost) -> Vec<Cost> {
+        let n = self.num_vertices();
+        let mut dense = vec![missing; n * n];
+        for edge in &self.cached_edges {
+            dense[edge.from * n + edge.to] = edge.weight;
+            dense[edge.to * n + edge.from] = edge.weight;
+        }
+        dense
+    }
+}
+
+// Read-only view of the flattened triangular matrix of a `GraphMatrix`,
+// entry `col * (col - 1) / 2 + row` holding the edge between `row < col`.
+pub struct TriangularView<'g, T> {
+    graph: &'g GraphMatrix<T>,
+}
+
+impl<T: Clone + Eq> TriangularView<'_, T> {
+    // Number of entries, one per pair of distinct vertices.
+    pub fn len(&self) -> usize {
+        let n = self.graph.num_vertices();
+        n * n.saturating_sub(1) / 2
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Returns the cost of the edge between two vertices, None if there is
+    // no edge, on the diagonal or out of range.
+    pub fn get(&self, from: VertexId, to: VertexId) -> Option<Cost> {
+        let n = self.graph.num_vertices();
+        (from != to && from < n && to < n)
+            .then(|| self.graph.cost(from, to))
+            .flatten()
+    }
+
+    // Iterates over the entries in flattened order, None where there is no
+    // edge.
+    pub fn iter(&self) -> impl Iterator<Item = Option<Cost>> + '_ {
+        let graph = self.graph;
+        (0..self.len()).map(|index| graph.is_present(index).then(|| graph.adj_matrix[index]))
+    }
+
+    // Returns a copy of the entries, see `iter`.
+    pub fn to_vec(&self) -> Vec<Option<Cost>> {
+        self.iter().collect()
+    }
+}
+
+impl GraphMatrix<usize> {
+    // Constructs a graph from `(from, to, cost)` triples, see `Graph::from_edges`.
+    pub fn from_edges<K>(num_vertices: usize, edges: K) -> Result<Self, GraphError>
+    where
+        K: IntoIterator<Item = (VertexId, VertexId, Cost)>,
+    {
+        <Self as Graph<usize>>::from_edges(num_vertices, edges)
+    }
+
+    // Constructs a graph from `(from, to, cost)` triples keeping the
+    // cheapest of repeated edges, see `Graph::from_edges_dedup`.
+    pub fn from_edges_dedup<K>(num_vertices: usize, edges: K) -> Result<Self, GraphError>
+    where
+        K: IntoIterator<Item = (VertexId, VertexId, Cost)>,
+    {
+        <Self as Graph<usize>>::from_edges_dedup(num_vertices, edges)
+    }
+
+    // Constructs a graph from a dense symmetric adjacency matrix where
+    // `None` means no edge, vertices are labelled with their ids.
+    // # Example: let g = GraphMatrix::from_dense(&[vec![None, Some(3)], vec![Some(3), None]])?;
+    // # Note: O(n^2), the diagonal is ignored.
+    pub fn from_dense(matrix: &[Vec<Option<Cost>>]) -> Result<Self, GraphError> {
+        validate_dense(matrix)?;
+        let mut graph = GraphMatrix::new_from_collection(0..matrix.len());
+        for (from, row) in matrix.iter().enumerate() {
+            for (to, cost) in row.iter().enumerate().skip(from + 1) {
+                if let Some(cost) = *cost {
+                    graph.add_edge(from, to, cost)?;
+                }
+            }
+        }
+        Ok(graph)
     }
 }
 
@@ -116,22 +440,40 @@ impl<T: Clone + Eq> Graph<T> for GraphMatrix<T> {
     fn add_vertex(&mut self, data: T) -> usize {
         let last_row = self.vertices.len();
         self.vertices.push(Vertex { id: last_row, data });
+        self.degrees.push(0);
 
         // Add the new lenght to the max size
         // # Note:   size = vert_num * (vert_num - 1) / 2 + row
-        self.adj_matrix
-            .resize(self.index(last_row + 1, last_row + 1), MAX_COST);
+        let len = self.index(last_row + 1, last_row + 1);
+        self.adj_matrix.resize(len, 0);
+        self.present.resize(len.div_ceil(64), 0);
+        self.cache_index.resize(len, 0);
         last_row
     }
 
-    // Adds a weighted edge between two vertices.
-    fn add_edge(&mut self, from: VertexId, to: VertexId, cost: Cost) {
+    // Adds a weighted edge between two vertices, replacing the weight of
+    // an existing one, self-loops are ignored since the matrix has no
+    // diagonal.
+    // # Note: O(1), the cached copy of a replaced edge being found through
+    // #       the cache index.
+    fn add_edge(&mut self, from: VertexId, to: VertexId, cost: Cost) -> Result<(), GraphError> {
+        check_bounds(from, to, self.num_vertices())?;
+        if from == to {
+            return Ok(());
+        }
         let index = self.index(from, to);
         // If it's a new edge add it to the cache
-        if self.adj_matrix[index] == MAX_COST {
+        if !self.is_present(index) {
+            self.present[index / 64] |= 1 << (index % 64);
+            self.cache_index[index] = self.cached_edges.len();
             self.cached_edges.push(Edge::new(from, to, cost));
+            self.degrees[from] += 1;
+            self.degrees[to] += 1;
+        } else {
+            self.set_cached_weight(index, cost);
         }
         self.adj_matrix[index] = cost;
+        Ok(())
     }
 
     // Returns a vertex structure from a vertex identifier.
@@ -150,8 +492,56 @@ impl<T: Clone + Eq> Graph<T> for GraphMatrix<T> {
         self.vertices.len()
     }
 
-    // Returns a vector of all cached edges
+    // Returns the number of cached edges.
+    // # Note: O(1), replacing a weight doesn't add an edge.
+    fn num_edges(&self) -> usize {
+        self.cached_edges.len()
+    }
+
+    // Returns a vector of all cached edges, in insertion order so that the
+    // position of an edge is its id.
     fn all_edges(&self) -> Vec<Edge> {
         self.cached_edges.clone()
     }
+
+    // Returns the edge inserted as the `id`-th, replacing a weight keeps it.
+    // # Note: O(1) lookup in the cached edges.
+    fn edge(&self, id: EdgeId) -> Option<Edge> {
+        self.cached_edges.get(id).copied()
+    }
+
+    // Returns the cost of the edge between two vertices, if any.
+    // # Note: O(1) matrix lookup.
+    fn edge_weight(&self, from: VertexId, to: VertexId) -> Option<Cost> {
+        let n = self.num_vertices();
+        if from >= n || to >= n || from == to {
+            return None;
+        }
+        self.cost(from, to)
+    }
+
+    // Returns the number of edges incident to a vertex, 0 if out of range.
+    // # Note: O(1) since degrees are counted on insertion.
+    fn degree(&self, id: VertexId) -> usize {
+        self.degrees.get(id).copied().unwrap_or(0)
+    }
+}
+
+// Copies an adjacency list graph, parallel edges collapse to the cheapest.
+// # Note: O(n^2 + m).
+impl<T: Clone + Eq, E: Default> From<&GraphStars<T, E>> for GraphMatrix<T> {
+    fn from(graph: &GraphStars<T, E>) -> Self {
+        let mut this =
+            GraphMatrix::new_from_collection(graph.vertices().iter().map(|v| v.data.clone()));
+        for edge in graph.all_edges() {
+            if this
+                .edge_weight(edge.from, edge.to)
+                .is_none_or(|w| edge.weight < w)
+            {
+                this.add_edge(edge.from, edge.to, edge.weight)
+                    .expect("the vertices are copied");
+            }
+        }
+        this
+    }
 }
@@ -3,14 +3,63 @@
 // Data structures adjacency list graph representations.
 use crate::constants::{Cost, EdgeId, VertexId};
 use crate::error::GraphError;
-use crate::graph::{Edge, Graph, Vertex};
-use rand::distr::{Distribution, Uniform};
-use rand::Rng;
+#[cfg(feature = "rand")]
+use crate::graph::random_pairs;
+use crate::graph::{check_bounds, index_vertices, validate_dense, Edge, Graph, Vertex, Violation};
+use crate::graph_matrix::GraphMatrix;
+use crate::memory::{nested_heap_bytes, HeapBytes};
+#[cfg(feature = "rand")]
+use rand::{
+    distr::{Distribution, Uniform},
+    rngs::StdRng,
+    Rng, SeedableRng,
+};
+use std::collections::{HashMap, HashSet};
+use std::mem::size_of;
 
-// Graph representation using nodes' outgoing stars.
-pub struct GraphStars<T> {
+// How `add_edge` treats an edge between already adjacent vertices.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum EdgePolicy {
+    // Keep the first edge inserted, ignore later ones.
+    #[default]
+    KeepFirst,
+    // Keep a single edge with the minimum weight seen.
+    KeepMin,
+    // Multigraph: keep every parallel edge.
+    AllowParallel,
+}
+
+// Outcome of inserting an edge.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EdgeInsertion {
+    // No edge existed between the vertices.
+    Inserted,
+    // Added next to the existing ones (`AllowParallel`).
+    InsertedParallel,
+    // The existing edge's weight was lowered (`KeepMin`).
+    Replaced { previous: Cost },
+    // Dropped in favour of the existing edge.
+    Ignored { existing: Cost },
+    // Dropped since self-loops are never stored.
+    SelfLoop,
+}
+
+// Graph representation using nodes' outgoing stars, with an optional
+// payload of type `E` on each edge.
+pub struct GraphStars<T, E = ()> {
     vertices: Vec<Vertex<T>>,
     stars: Vec<Vec<Edge>>,
+    // Id of each edge in the stars, parallel to `stars`.
+    star_ids: Vec<Vec<EdgeId>>,
+    // Origin and position in its star of each edge in insertion order, the
+    // index being the edge's id.
+    slots: Vec<(VertexId, usize)>,
+    // Payload of each edge, indexed by id.
+    payloads: Vec<E>,
+    policy: EdgePolicy,
+    // Position in the star of `from` of the first edge from `from` to `to`,
+    // if the index is enabled, see `enable_edge_index`.
+    index: Option<HashMap<(VertexId, VertexId), usize>>,
 }
 
 impl<T: Clone + Eq> Default for GraphStars<T> {
@@ -21,10 +70,12 @@ impl<T: Clone + Eq> Default for GraphStars<T> {
 
 impl<T: Clone + Eq> GraphStars<T> {
     pub fn new() -> Self {
-        GraphStars {
-            vertices: Vec::new(),
-            stars: Vec::new(),
-        }
+        Self::with_edge_policy(EdgePolicy::default())
+    }
+
+    // Constructs an empty graph handling repeated edges with `policy`.
+    pub fn with_edge_policy(policy: EdgePolicy) -> Self {
+        Self::with_payloads(policy)
     }
 
     // Constructs a graph without vertices from a generic collection's iterator
@@ -47,10 +98,12 @@ impl<T: Clone + Eq> GraphStars<T> {
     //
     // # Example: let g = Graph::new_random(vec![1, 2, 3], 0.5, 0, 100 );
     //
-    // # Note: O(n^2) complexity.
+    // # Note: O(n + m) expected, see `random_pairs`. Self-loops are never
+    // #       stored.
     //
     // # Panic: Probabilities must be expressed with a f32 between 0.0 and 1.0,
     // #        edge's cost range must be valid (a <= b).
+    #[cfg(feature = "rand")]
     pub fn new_random<K, R>(
         collection: K,
         p: f64,
@@ -61,7 +114,7 @@ impl<T: Clone + Eq> GraphStars<T> {
     ) -> Result<Self, GraphError>
     where
         K: IntoIterator<Item = T>,
-        R: Rng,
+        R: Rng + ?Sized,
     {
         if !(0.0..=1.0).contains(&p) {
             return Err(GraphError::InvalidProbability(p));
@@ -77,52 +130,496 @@ impl<T: Clone + Eq> GraphStars<T> {
         let cost_dist = Uniform::new_inclusive(min_cost, max_cost).unwrap();
         let num_vertices = graph.num_vertices();
 
-        for from_idx in 0..num_vertices {
-            let start = if no_self_loops {
-                from_idx + 1
-            } else {
-                from_idx
-            };
-
-            for to_idx in start..num_vertices {
-                if rng.random::<f64>() < p {
-                    let cost = cost_dist.sample(rng);
-                    graph.add_edge(from_idx, to_idx, cost);
-                }
+        // Each pair comes up once, so edges are pushed without looking for
+        // an existing one.
+        random_pairs(num_vertices, p, no_self_loops, rng, |from, to, rng| {
+            let cost = cost_dist.sample(rng);
+            if from != to {
+                graph.push_edge(from, to, cost, ());
             }
-        }
+        });
         Ok(graph)
     }
 
+    // Same as `new_random` but draws from a `StdRng` seeded with `seed`,
+    // for reproducible graphs and targets without an OS entropy source.
+    #[cfg(feature = "rand")]
+    pub fn new_random_seeded<K>(
+        collection: K,
+        p: f64,
+        min_cost: usize,
+        max_cost: usize,
+        no_self_loops: bool,
+        seed: u64,
+    ) -> Result<Self, GraphError>
+    where
+        K: IntoIterator<Item = T>,
+    {
+        let mut rng = StdRng::seed_from_u64(seed);
+        Self::new_random(collection, p, min_cost, max_cost, no_self_loops, &mut rng)
+    }
+}
+
+impl<T: Clone + Eq, E> GraphStars<T, E> {
+    // Constructs an empty graph whose edges carry a payload of type `E`,
+    // handling repeated edges with `policy`.
+    // # Example: let mut g = GraphStars::<usize, String>::with_payloads(EdgePolicy::AllowParallel);
+    // # Note: The `Graph` methods need `E: Default`, `add_edge` attaching
+    // #       the default payload, see `insert_edge_with`.
+    pub fn with_payloads(policy: EdgePolicy) -> Self {
+        GraphStars {
+            vertices: Vec::new(),
+            stars: Vec::new(),
+            star_ids: Vec::new(),
+            slots: Vec::new(),
+            payloads: Vec::new(),
+            policy,
+            index: None,
+        }
+    }
+
+    // Indexes the edges by their endpoints, and keeps the index up to date,
+    // so that looking for an existing edge in `add_edge`, `update_edge` or
+    // `edge_weight` is O(1) expected rather than O(deg), e.g. before bulk
+    // loading a graph with high-degree vertices.
+    // # Note: O(m), two hash entries per edge. Between parallel edges,
+    // #       `edge_weight` still scans the star for the cheapest one.
+    pub fn enable_edge_index(&mut self) {
+        let mut index = HashMap::with_capacity(2 * self.slots.len());
+        for (from, star) in self.stars.iter().enumerate() {
+            for (pos, edge) in star.iter().enumerate() {
+                index.entry((from, edge.to)).or_insert(pos);
+            }
+        }
+        self.index = Some(index);
+    }
+
+    // Returns the position in the star of `from` of the first edge to `to`.
+    fn find_edge(&self, from: VertexId, to: VertexId) -> Option<usize> {
+        match &self.index {
+            Some(index) => index.get(&(from, to)).copied(),
+            None => self.stars[from].iter().position(|e| e.to == to),
+        }
+    }
+
+    pub fn edge_policy(&self) -> EdgePolicy {
+        self.policy
+    }
+
     pub fn stars(&self) -> Vec<Vec<Edge>> {
         self.stars.clone()
     }
-}
 
-impl<T: Clone + Eq> Graph<T> for GraphStars<T> {
-    // Adds a node to the structure and creates a new adjacency list.
-    fn add_vertex(&mut self, data: T) -> usize {
+    // Consumes the graph and returns its stars without a copy.
+    pub(crate) fn into_stars(self) -> Vec<Vec<Edge>> {
+        self.stars
+    }
+
+    // Returns the edges leaving a vertex without a copy.
+    pub(crate) fn star(&self, id: VertexId) -> &[Edge] {
+        &self.stars[id]
+    }
+
+    // Adds a weighted edge carrying `payload` between two nodes, applying
+    // the edge policy if they are already adjacent, and reports what
+    // happened. An edge replaced by `KeepMin` takes the new payload.
+    // # Note: O(deg(from)) to look for an existing edge. Fails with
+    // #       `VertexOutOfBounds` if an endpoint isn't a vertex.
+    pub fn insert_edge_with(
+        &mut self,
+        from: VertexId,
+        to: VertexId,
+        cost: Cost,
+        payload: E,
+    ) -> Result<EdgeInsertion, GraphError> {
+        check_bounds(from, to, self.vertices.len())?;
+        if from == to {
+            return Ok(EdgeInsertion::SelfLoop);
+        }
+
+        let existing = self.find_edge(from, to);
+
+        Ok(match (existing, self.policy) {
+            (None, _) => {
+                self.push_edge(from, to, cost, payload);
+                EdgeInsertion::Inserted
+            }
+            (Some(_), EdgePolicy::AllowParallel) => {
+                self.push_edge(from, to, cost, payload);
+                EdgeInsertion::InsertedParallel
+            }
+            (Some(pos), EdgePolicy::KeepMin) if cost < self.stars[from][pos].weight => {
+                // Nothing to write for payloads without data, e.g. `()`.
+                if size_of::<E>() > 0 {
+                    self.payloads[self.star_ids[from][pos]] = payload;
+                }
+                let previous = self.set_weight(from, pos, cost);
+                EdgeInsertion::Replaced { previous }
+            }
+            (Some(pos), _) => EdgeInsertion::Ignored {
+                existing: self.stars[from][pos].weight,
+            },
+        })
+    }
+
+    // Changes the weight of an existing edge on both of its stars and
+    // returns the previous weight.
+    // # Note: O(deg(from) + deg(to)). Between parallel edges the first one
+    // #       in the star of `from` is updated.
+    pub fn update_edge(
+        &mut self,
+        from: VertexId,
+        to: VertexId,
+        new_cost: Cost,
+    ) -> Result<Cost, GraphError> {
+        check_bounds(from, to, self.vertices.len())?;
+        let pos = self
+            .find_edge(from, to)
+            .ok_or(GraphError::MissingEdge { from, to })?;
+        Ok(self.set_weight(from, pos, new_cost))
+    }
+
+    // Sets the weight of the edge at `pos` in the star of `from` and of its
+    // reverse, returning the previous weight.
+    fn set_weight(&mut self, from: VertexId, pos: usize, cost: Cost) -> Cost {
+        let Edge { to, weight, .. } = self.stars[from][pos];
+        self.stars[from][pos].weight = cost;
+        if let Some(edge) = self.stars[to]
+            .iter_mut()
+            .find(|e| e.to == from && e.weight == weight)
+        {
+            edge.weight = cost;
+        }
+        weight
+    }
+
+    // Returns an estimate of the heap bytes held by the graph: the
+    // vertices, the stars and their edge ids, the edge slots and the
+    // payloads.
+    // # Note: Heap memory owned by the vertex data or the payloads isn't
+    // #       counted.
+    pub fn memory_footprint(&self) -> usize {
+        self.vertices.heap_bytes()
+            + self.stars.heap_bytes()
+            + nested_heap_bytes(&self.stars)
+            + self.star_ids.heap_bytes()
+            + nested_heap_bytes(&self.star_ids)
+            + self.slots.heap_bytes()
+            + self.payloads.heap_bytes()
+            + self.index.as_ref().map_or(0, HeapBytes::heap_bytes)
+    }
+
+    // Checks the invariants of the structure: vertex ids, each edge stored
+    // in its origin's star with its reverse in the other endpoint's, in
+    // range, without self-loops, and no parallel
+    // edges unless the edge policy allows them.
+    // # Note: O(n + m) expected, meant for graphs built from untrusted input.
+    pub fn validate(&self) -> Vec<Violation> {
+        let n = self.vertices.len();
+        let mut violations = Vec::new();
+        for (index, vertex) in self.vertices.iter().enumerate() {
+            if vertex.id != index {
+                violations.push(Violation::VertexId {
+                    index,
+                    id: vertex.id,
+                });
+            }
+        }
+
+        // Copies of each directed edge still waiting for their reverse.
+        let mut unmatched: HashMap<(VertexId, VertexId, Cost), Vec<Edge>> = HashMap::new();
+        let mut pairs = HashSet::new();
+        for (vertex, star) in self.stars.iter().enumerate() {
+            for &edge in star {
+                if edge.from != vertex {
+                    violations.push(Violation::MisplacedEdge { vertex, edge });
+                }
+                if edge.from >= n || edge.to >= n {
+                    violations.push(Violation::EndpointOutOfBounds {
+                        edge,
+                        num_vertices: n,
+                    });
+                    continue;
+                }
+                if edge.from == edge.to {
+                    violations.push(Violation::SelfLoop { edge });
+                    continue;
+                }
+                if edge.from < edge.to
+                    && !pairs.insert((edge.from, edge.to))
+                    && self.policy != EdgePolicy::AllowParallel
+                {
+                    violations.push(Violation::ParallelEdge { edge });
+                }
+                let reverse = (edge.to, edge.from, edge.weight);
+                match unmatched.get_mut(&reverse).and_then(Vec::pop) {
+                    Some(_) => {}
+                    None => unmatched
+                        .entry((edge.from, edge.to, edge.weight))
+                        .or_default()
+                        .push(edge),
+                }
+            }
+        }
+        let mut missing: Vec<Edge> = unmatched.into_values().flatten().collect();
+        missing.sort_unstable_by_key(|e| (e.from, e.to, e.weight));
+        violations.extend(
+            missing
+                .into_iter()
+                .map(|edge| Violation::MissingReverse { edge }),
+        );
+        violations
+    }
+
+    // Returns the payload of the edge with the given id.
+    pub fn payload(&self, id: EdgeId) -> Option<&E> {
+        self.payloads.get(id)
+    }
+
+    // Returns the payloads indexed by edge id without a copy.
+    pub fn payloads(&self) -> &[E] {
+        &self.payloads
+    }
+
+    // Collapses parallel edges to the cheapest one, the first inserted
+    // between equal weights, and returns the number of edges removed. The
+    // edges left keep their insertion order and payloads but are renumbered.
+    // # Note: O(n + m). Only `AllowParallel` graphs can hold parallel edges,
+    // #       the policy is left unchanged.
+    pub fn dedup_edges(&mut self) -> usize {
+        let mut cheapest: HashMap<(VertexId, VertexId), EdgeId> = HashMap::new();
+        for (id, &(from, pos)) in self.slots.iter().enumerate() {
+            let edge = self.stars[from][pos];
+            let pair = (edge.from.min(edge.to), edge.from.max(edge.to));
+            let best = cheapest.entry(pair).or_insert(id);
+            let (best_from, best_pos) = self.slots[*best];
+            if edge.weight < self.stars[best_from][best_pos].weight {
+                *best = id;
+            }
+        }
+        let removed = self.slots.len() - cheapest.len();
+        if removed == 0 {
+            return 0;
+        }
+
+        let mut keep = vec![false; self.slots.len()];
+        for id in cheapest.into_values() {
+            keep[id] = true;
+        }
+        let slots = std::mem::take(&mut self.slots);
+        let payloads = std::mem::take(&mut self.payloads);
+        let stars = std::mem::replace(&mut self.stars, vec![Vec::new(); self.vertices.len()]);
+        self.star_ids = vec![Vec::new(); self.vertices.len()];
+        if let Some(index) = &mut self.index {
+            index.clear();
+        }
+        for ((id, (from, pos)), payload) in slots.into_iter().enumerate().zip(payloads) {
+            if keep[id] {
+                let edge = stars[from][pos];
+                self.push_edge(edge.from, edge.to, edge.weight, payload);
+            }
+        }
+        removed
+    }
+
+    fn push_vertex(&mut self, data: T) -> VertexId {
         let id = self.vertices.len();
         self.vertices.push(Vertex { id, data });
         self.stars.push(Vec::new());
+        self.star_ids.push(Vec::new());
         id
     }
 
-    // Adds a weighted edge between two nodes adding each node to the
-    // other's adjacency list.
-    fn add_edge(&mut self, from: VertexId, to: EdgeId, cost: Cost) {
-        if from == to {
-            return;
+    fn push_edge(&mut self, from: VertexId, to: VertexId, cost: Cost, payload: E) {
+        if let Some(index) = &mut self.index {
+            index.entry((from, to)).or_insert(self.stars[from].len());
+            index.entry((to, from)).or_insert(self.stars[to].len());
         }
+        let id = self.slots.len();
+        self.slots.push((from, self.stars[from].len()));
+        self.payloads.push(payload);
+        self.stars[from].push(Edge::new(from, to, cost));
+        self.stars[to].push(Edge::new(to, from, cost));
+        self.star_ids[from].push(id);
+        self.star_ids[to].push(id);
+    }
+}
 
-        let exists = self.stars[from].iter().any(|e| e.to == to);
+impl<T: Clone + Eq, E: Clone> GraphStars<T, E> {
+    // Returns the subgraph induced by `vertices`, the i-th of them becoming
+    // vertex i, with the edges between them and their payloads in insertion
+    // order and the same edge policy.
+    // # Example: let community = graph.subgraph(&[4, 8, 15])?;
+    // # Note: A repeated vertex is only kept once, O(n + m).
+    pub fn subgraph(&self, vertices: &[VertexId]) -> Result<Self, GraphError> {
+        let (ids, kept) = index_vertices(vertices, self.vertices.len())?;
+        let mut graph = GraphStars::with_payloads(self.policy);
+        for &vertex in &kept {
+            graph.push_vertex(self.vertices[vertex].data.clone());
+        }
+        for (id, &(from, pos)) in self.slots.iter().enumerate() {
+            let edge = self.stars[from][pos];
+            if let (Some(from), Some(to)) = (ids[edge.from], ids[edge.to]) {
+                graph.push_edge(from, to, edge.weight, self.payloads[id].clone());
+            }
+        }
+        Ok(graph)
+    }
 
-        if !exists {
-            let edge_fwd = Edge::new(from, to, cost);
-            let edge_bwd = Edge::new(to, from, cost);
-            self.stars[from].push(edge_fwd);
-            self.stars[to].push(edge_bwd);
+    // Returns a copy of the graph with every weight replaced by `f(weight)`,
+    // e.g. scaled or inverted to get a maximum spanning tree.
+    // # Example: let inverted = graph.map_weights(|w| MAX_COST - w);
+    // # Note: O(n + m), `f` is applied to both copies of each edge. Edge ids
+    // #       and payloads are kept.
+    pub fn map_weights<F: Fn(Cost) -> Cost>(&self, f: F) -> Self {
+        let stars = self
+            .stars
+            .iter()
+            .map(|star| {
+                star.iter()
+                    .map(|e| Edge::new(e.from, e.to, f(e.weight)))
+                    .collect()
+            })
+            .collect();
+        GraphStars {
+            vertices: self.vertices.clone(),
+            stars,
+            star_ids: self.star_ids.clone(),
+            slots: self.slots.clone(),
+            payloads: self.payloads.clone(),
+            policy: self.policy,
+            index: self.index.clone(),
+        }
+    }
+
+    // Adds the vertices and edges of `other`. With `offset_ids` its vertices
+    // are appended after the current ones (disjoint union) and its edges
+    // follow the edge policy. Otherwise vertex ids are shared and the edges
+    // are overlaid, a duplicate edge keeping the cheaper weight and its
+    // payload. Vertices of `other` beyond the current ones are appended
+    // either way.
+    // # Example: for shard in &shards { graph.merge(shard, true); }
+    pub fn merge(&mut self, other: &Self, offset_ids: bool) {
+        let n = self.vertices.len();
+        let offset = if offset_ids { n } else { 0 };
+        for vertex in other.vertices.iter().skip(n - offset) {
+            self.push_vertex(vertex.data.clone());
+        }
+        let policy = self.policy;
+        if !offset_ids {
+            self.policy = EdgePolicy::KeepMin;
         }
+        for (id, &(from, pos)) in other.slots.iter().enumerate() {
+            let edge = other.stars[from][pos];
+            self.insert_edge_with(
+                edge.from + offset,
+                edge.to + offset,
+                edge.weight,
+                other.payloads[id].clone(),
+            )
+            .expect("the endpoints are merged vertices");
+        }
+        self.policy = policy;
+    }
+}
+
+impl<T: Clone + Eq, E: Default> GraphStars<T, E> {
+    // Adds a weighted edge between two nodes with the default payload, see
+    // `insert_edge_with`.
+    pub fn insert_edge(
+        &mut self,
+        from: VertexId,
+        to: VertexId,
+        cost: Cost,
+    ) -> Result<EdgeInsertion, GraphError> {
+        self.insert_edge_with(from, to, cost, E::default())
+    }
+}
+
+impl GraphStars<usize> {
+    // Constructs a graph from `(from, to, cost)` triples, see `Graph::from_edges`.
+    pub fn from_edges<K>(num_vertices: usize, edges: K) -> Result<Self, GraphError>
+    where
+        K: IntoIterator<Item = (VertexId, VertexId, Cost)>,
+    {
+        <Self as Graph<usize>>::from_edges(num_vertices, edges)
+    }
+
+    // Constructs a graph from `(from, to, cost)` triples keeping the
+    // cheapest of repeated edges, see `Graph::from_edges_dedup`.
+    pub fn from_edges_dedup<K>(num_vertices: usize, edges: K) -> Result<Self, GraphError>
+    where
+        K: IntoIterator<Item = (VertexId, VertexId, Cost)>,
+    {
+        <Self as Graph<usize>>::from_edges_dedup(num_vertices, edges)
+    }
+
+    // Constructs a graph from a dense symmetric adjacency matrix where
+    // `None` means no edge, vertices are labelled with their ids.
+    // # Example: let g = GraphStars::from_dense(&[vec![None, Some(3)], vec![Some(3), None]])?;
+    // # Note: O(n^2), the diagonal is ignored.
+    pub fn from_dense(matrix: &[Vec<Option<Cost>>]) -> Result<Self, GraphError> {
+        validate_dense(matrix)?;
+        let mut graph = GraphStars::new_from_collection(0..matrix.len());
+        for (from, row) in matrix.iter().enumerate() {
+            for (to, cost) in row.iter().enumerate().skip(from + 1) {
+                if let Some(cost) = *cost {
+                    graph.add_edge(from, to, cost)?;
+                }
+            }
+        }
+        Ok(graph)
+    }
+
+    // Constructs the symmetric k-nearest-neighbor graph of a point set:
+    // each point is joined to its `k` closest others, weighted by
+    // `distance`, vertices are labelled with the points' indices.
+    // # Example: let g = GraphStars::new_knn(&points, 10, |a, b| manhattan(a, b));
+    // # Note: O(n^2) distance evaluations, ties are broken by index.
+    pub fn new_knn<P, F>(points: &[P], k: usize, distance: F) -> Self
+    where
+        F: Fn(&P, &P) -> Cost,
+    {
+        let n = points.len();
+        let k = k.min(n.saturating_sub(1));
+        let mut pairs = Vec::with_capacity(n * k);
+        let mut candidates = Vec::with_capacity(n);
+        for (i, point) in points.iter().enumerate() {
+            candidates.clear();
+            candidates.extend(
+                (0..n)
+                    .filter(|&j| j != i)
+                    .map(|j| (distance(point, &points[j]), j)),
+            );
+            if k > 0 && k < candidates.len() {
+                candidates.select_nth_unstable(k - 1);
+            }
+            for &(cost, j) in candidates.iter().take(k) {
+                pairs.push((i.min(j), i.max(j), cost));
+            }
+        }
+        // A pair chosen from both sides is added once.
+        pairs.sort_unstable();
+        pairs.dedup_by_key(|&mut (from, to, _)| (from, to));
+
+        let mut graph = GraphStars::new_from_collection(0..n);
+        for (from, to, cost) in pairs {
+            graph.push_edge(from, to, cost, ());
+        }
+        graph
+    }
+}
+
+impl<T: Clone + Eq, E: Default> Graph<T> for GraphStars<T, E> {
+    // Adds a node to the structure and creates a new adjacency list.
+    fn add_vertex(&mut self, data: T) -> usize {
+        self.push_vertex(data)
+    }
+
+    // Adds a weighted edge between two nodes adding each node to the
+    // other's adjacency list, see `insert_edge`.
+    fn add_edge(&mut self, from: VertexId, to: VertexId, cost: Cost) -> Result<(), GraphError> {
+        self.insert_edge(from, to, cost).map(|_| ())
     }
 
     // Returns a vertex structure from a vertex identifier.
@@ -141,17 +638,70 @@ impl<T: Clone + Eq> Graph<T> for GraphStars<T> {
         self.vertices.len()
     }
 
-    // Returns a vector of all edges
+    // Returns the cost of the edge between two vertices, if any, the
+    // cheapest one between parallel edges.
+    // # Note: O(min(deg(from), deg(to))) scanning the smaller star, O(1)
+    // #       expected with the edge index and no parallel edges.
+    fn edge_weight(&self, from: VertexId, to: VertexId) -> Option<Cost> {
+        let n = self.num_vertices();
+        if from >= n || to >= n {
+            return None;
+        }
+        if self.index.is_some() && self.policy != EdgePolicy::AllowParallel {
+            return self
+                .find_edge(from, to)
+                .map(|pos| self.stars[from][pos].weight);
+        }
+        let (from, to) = if self.stars[from].len() <= self.stars[to].len() {
+            (from, to)
+        } else {
+            (to, from)
+        };
+        self.stars[from]
+            .iter()
+            .filter(|e| e.to == to)
+            .map(|e| e.weight)
+            .min()
+    }
+
+    // Returns the number of edges incident to a vertex, 0 if out of range.
+    fn degree(&self, id: VertexId) -> usize {
+        self.stars.get(id).map_or(0, Vec::len)
+    }
+
+    // Returns the number of edges, each stored in two stars but counted once.
+    // # Note: O(1), one slot per edge.
+    fn num_edges(&self) -> usize {
+        self.slots.len()
+    }
+
+    // Returns a vector of all edges in insertion order, oriented as
+    // inserted, so that the position of an edge is its id.
     fn all_edges(&self) -> Vec<Edge> {
-        let mut edges = Vec::new();
-        for (from_id, star) in self.stars.iter().enumerate() {
-            for edge in star {
-                // Avoid duplicates
-                if from_id < edge.to {
-                    edges.push(*edge);
-                }
-            }
+        self.slots
+            .iter()
+            .map(|&(from, pos)| self.stars[from][pos])
+            .collect()
+    }
+
+    // Returns the edge inserted as the `id`-th, edges dropped by the edge
+    // policy get no id and replacing a weight keeps it.
+    // # Note: O(1) through the insertion slots.
+    fn edge(&self, id: EdgeId) -> Option<Edge> {
+        self.slots.get(id).map(|&(from, pos)| self.stars[from][pos])
+    }
+}
+
+// Copies an adjacency matrix graph with the default edge policy.
+// # Note: The matrix has no parallel edges or self-loops, so the edges are
+// #       pushed without the policy's lookups.
+impl<T: Clone + Eq> From<&GraphMatrix<T>> for GraphStars<T> {
+    fn from(graph: &GraphMatrix<T>) -> Self {
+        let mut this =
+            GraphStars::new_from_collection(graph.vertices().iter().map(|v| v.data.clone()));
+        for edge in graph.all_edges() {
+            this.push_edge(edge.from, edge.to, edge.weight, ());
         }
-        edges
+        this
     }
 }
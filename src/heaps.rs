@@ -0,0 +1,741 @@
+// # Heaps
+//
+// Priority queues used by the algorithms, exposed for reuse.
+use crate::memory::HeapBytes;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::mem::{self, size_of};
+
+// A min-priority queue.
+pub trait MinHeap<T> {
+    // Builds the heap from a vector.
+    fn from_vec(items: Vec<T>) -> Self;
+    fn push(&mut self, item: T);
+    fn pop_min(&mut self) -> Option<T>;
+    fn len(&self) -> usize;
+
+    // Returns true if the heap is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Removes every item, keeping the allocated memory where possible.
+    fn clear(&mut self) {
+        while self.pop_min().is_some() {}
+    }
+
+    // Replaces the items by `items`, keeping the allocated memory where
+    // possible, by default one push at a time.
+    fn refill(&mut self, items: &[T])
+    where
+        T: Clone,
+    {
+        self.clear();
+        for item in items {
+            self.push(item.clone());
+        }
+    }
+
+    // Returns an estimate of the heap bytes held by the queue, by default
+    // one `T` per item.
+    fn memory_footprint(&self) -> usize {
+        self.len() * size_of::<T>()
+    }
+}
+
+impl<T: Ord> MinHeap<T> for BinaryHeap<Reverse<T>> {
+    fn from_vec(items: Vec<T>) -> Self {
+        items.into_iter().map(Reverse).collect()
+    }
+
+    fn push(&mut self, item: T) {
+        BinaryHeap::push(self, Reverse(item));
+    }
+
+    fn pop_min(&mut self) -> Option<T> {
+        self.pop().map(|Reverse(item)| item)
+    }
+
+    fn len(&self) -> usize {
+        BinaryHeap::len(self)
+    }
+
+    fn clear(&mut self) {
+        BinaryHeap::clear(self);
+    }
+
+    // Heapifies the new items in O(n) in the same buffer.
+    fn refill(&mut self, items: &[T])
+    where
+        T: Clone,
+    {
+        let mut buffer = mem::take(self).into_vec();
+        buffer.clear();
+        buffer.extend(items.iter().cloned().map(Reverse));
+        *self = BinaryHeap::from(buffer);
+    }
+
+    fn memory_footprint(&self) -> usize {
+        self.capacity() * size_of::<Reverse<T>>()
+    }
+}
+
+// Implicit min-heap where every node has `D` children.
+// # Note: log_D(n) levels and contiguous children make pops cheaper on
+// #       cache than a binary heap, at the cost of D - 1 comparisons
+// #       per level.
+#[derive(Clone, Debug)]
+pub struct DaryHeap<T, const D: usize = 4> {
+    items: Vec<T>,
+}
+
+impl<T: Ord, const D: usize> Default for DaryHeap<T, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord, const D: usize> DaryHeap<T, D> {
+    pub fn new() -> Self {
+        const { assert!(D >= 2, "a d-ary heap needs at least two children") };
+        DaryHeap { items: Vec::new() }
+    }
+
+    // Returns the minimum without removing it.
+    pub fn peek(&self) -> Option<&T> {
+        self.items.first()
+    }
+
+    // Moves the item at `i` up until its parent is smaller.
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / D;
+            if self.items[i] >= self.items[parent] {
+                break;
+            }
+            self.items.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    // Restores the heap order of the whole array bottom-up, O(n).
+    fn heapify(&mut self) {
+        let n = self.items.len();
+        if n > 1 {
+            for i in (0..=(n - 2) / D).rev() {
+                self.sift_down(i);
+            }
+        }
+    }
+
+    // Moves the item at `i` down until its children are larger.
+    fn sift_down(&mut self, mut i: usize) {
+        let n = self.items.len();
+        loop {
+            let first = i * D + 1;
+            if first >= n {
+                break;
+            }
+            let last = (first + D).min(n);
+            let mut min = first;
+            for child in (first + 1)..last {
+                if self.items[child] < self.items[min] {
+                    min = child;
+                }
+            }
+            if self.items[min] >= self.items[i] {
+                break;
+            }
+            self.items.swap(i, min);
+            i = min;
+        }
+    }
+}
+
+impl<T: Ord, const D: usize> MinHeap<T> for DaryHeap<T, D> {
+    // Heapifies bottom-up in O(n).
+    fn from_vec(items: Vec<T>) -> Self {
+        let mut heap = DaryHeap::new();
+        heap.items = items;
+        heap.heapify();
+        heap
+    }
+
+    fn push(&mut self, item: T) {
+        self.items.push(item);
+        self.sift_up(self.items.len() - 1);
+    }
+
+    fn pop_min(&mut self) -> Option<T> {
+        if self.items.is_empty() {
+            return None;
+        }
+        let item = self.items.swap_remove(0);
+        self.sift_down(0);
+        Some(item)
+    }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    // Heapifies the new items in O(n) in the same buffer.
+    fn refill(&mut self, items: &[T])
+    where
+        T: Clone,
+    {
+        self.items.clear();
+        self.items.extend_from_slice(items);
+        self.heapify();
+    }
+
+    fn memory_footprint(&self) -> usize {
+        self.items.heap_bytes()
+    }
+}
+
+// Handle to an item of a `PairingHeap`, used to decrease its key.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PairingHandle(usize);
+
+const NIL: usize = usize::MAX;
+
+#[derive(Clone, Debug)]
+struct PairingNode<T> {
+    item: T,
+    child: usize,
+    sibling: usize,
+    // Parent for a leftmost child, left sibling otherwise.
+    prev: usize,
+    in_heap: bool,
+}
+
+// Pairing heap with decrease-key, nodes live in an arena so handles stay
+// valid until the heap is dropped.
+// # Note: O(1) insert and decrease-key (amortized o(log n)), O(log n)
+// #       amortized pop.
+#[derive(Clone, Debug)]
+pub struct PairingHeap<T> {
+    nodes: Vec<PairingNode<T>>,
+    root: usize,
+    len: usize,
+}
+
+impl<T: Ord + Clone> Default for PairingHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Clone> PairingHeap<T> {
+    pub fn new() -> Self {
+        PairingHeap {
+            nodes: Vec::new(),
+            root: NIL,
+            len: 0,
+        }
+    }
+
+    // Inserts an item and returns its handle.
+    pub fn insert(&mut self, item: T) -> PairingHandle {
+        let id = self.nodes.len();
+        self.nodes.push(PairingNode {
+            item,
+            child: NIL,
+            sibling: NIL,
+            prev: NIL,
+            in_heap: true,
+        });
+        self.root = if self.root == NIL {
+            id
+        } else {
+            self.meld(self.root, id)
+        };
+        self.len += 1;
+        PairingHandle(id)
+    }
+
+    // Returns the minimum without removing it.
+    pub fn peek(&self) -> Option<&T> {
+        self.nodes.get(self.root).map(|node| &node.item)
+    }
+
+    // Returns the item of a handle still in the heap.
+    pub fn get(&self, handle: PairingHandle) -> Option<&T> {
+        self.nodes
+            .get(handle.0)
+            .filter(|node| node.in_heap)
+            .map(|node| &node.item)
+    }
+
+    // Replaces the item of a handle with a smaller one, returning false and
+    // leaving the heap unchanged if it isn't smaller or was already popped.
+    pub fn decrease_key(&mut self, handle: PairingHandle, item: T) -> bool {
+        let id = handle.0;
+        match self.nodes.get(id) {
+            Some(node) if node.in_heap && item < node.item => {}
+            _ => return false,
+        }
+        self.nodes[id].item = item;
+        if id == self.root {
+            return true;
+        }
+
+        // Cut the subtree and meld it back with the root.
+        let (prev, sibling) = (self.nodes[id].prev, self.nodes[id].sibling);
+        if self.nodes[prev].child == id {
+            self.nodes[prev].child = sibling;
+        } else {
+            self.nodes[prev].sibling = sibling;
+        }
+        if sibling != NIL {
+            self.nodes[sibling].prev = prev;
+        }
+        self.nodes[id].prev = NIL;
+        self.nodes[id].sibling = NIL;
+        self.root = self.meld(self.root, id);
+        true
+    }
+
+    // Links two roots, the larger becoming the leftmost child of the smaller.
+    fn meld(&mut self, a: usize, b: usize) -> usize {
+        let (parent, child) = if self.nodes[b].item < self.nodes[a].item {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        let first = self.nodes[parent].child;
+        self.nodes[child].sibling = first;
+        if first != NIL {
+            self.nodes[first].prev = child;
+        }
+        self.nodes[child].prev = parent;
+        self.nodes[parent].child = child;
+        parent
+    }
+}
+
+impl<T: Ord + Clone> MinHeap<T> for PairingHeap<T> {
+    fn from_vec(items: Vec<T>) -> Self {
+        let mut heap = PairingHeap::new();
+        for item in items {
+            heap.insert(item);
+        }
+        heap
+    }
+
+    fn push(&mut self, item: T) {
+        self.insert(item);
+    }
+
+    // Removes the root and melds its children in two passes, pairing them
+    // left to right and then folding the pairs right to left.
+    fn pop_min(&mut self) -> Option<T> {
+        if self.root == NIL {
+            return None;
+        }
+        let root = self.root;
+        self.nodes[root].in_heap = false;
+        self.len -= 1;
+
+        let mut children = Vec::new();
+        let mut child = self.nodes[root].child;
+        while child != NIL {
+            let next = self.nodes[child].sibling;
+            self.nodes[child].prev = NIL;
+            self.nodes[child].sibling = NIL;
+            children.push(child);
+            child = next;
+        }
+        self.nodes[root].child = NIL;
+
+        let mut pairs = Vec::with_capacity(children.len().div_ceil(2));
+        for pair in children.chunks(2) {
+            pairs.push(match *pair {
+                [a, b] => self.meld(a, b),
+                [a] => a,
+                _ => unreachable!(),
+            });
+        }
+        self.root = pairs.pop().unwrap_or(NIL);
+        while let Some(next) = pairs.pop() {
+            self.root = self.meld(next, self.root);
+        }
+        Some(self.nodes[root].item.clone())
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    // Drops the arena nodes too, so every handle is invalidated.
+    fn clear(&mut self) {
+        self.nodes.clear();
+        self.root = NIL;
+        self.len = 0;
+    }
+
+    // Counts the whole arena, popped nodes are never freed.
+    fn memory_footprint(&self) -> usize {
+        self.nodes.heap_bytes()
+    }
+}
+
+#[derive(Clone, Debug)]
+struct SoftNode<T> {
+    // Upper bound on the keys of the items in the list, their common
+    // corrupted key.
+    ckey: T,
+    rank: usize,
+    // Number of items the list is refilled to.
+    target: usize,
+    left: usize,
+    right: usize,
+    // Singly linked list of item slots.
+    head: usize,
+    tail: usize,
+    len: usize,
+}
+
+// Soft heap in the binary tree form of Kaplan, Tarjan and Zwick: inserts
+// are O(1) and extractions O(log 1/ε) amortized because items may have
+// their key raised ("corrupted") to a larger one of the same heap, at most
+// ε·n items at any time for n insertions.
+// # Note: Extractions return the item along with its current key, the item
+// #       is corrupted if the key is larger.
+#[derive(Clone, Debug)]
+pub struct SoftHeap<T> {
+    nodes: Vec<SoftNode<T>>,
+    items: Vec<(Option<T>, usize)>,
+    // Tree root of each rank, NIL if none.
+    roots: Vec<usize>,
+    // Nodes of rank up to `threshold` keep a single item.
+    threshold: usize,
+    len: usize,
+}
+
+impl<T: Ord + Clone> SoftHeap<T> {
+    // Constructs an empty soft heap with error rate `epsilon`.
+    // # Panic: `epsilon` must be in (0, 1).
+    pub fn new(epsilon: f64) -> Self {
+        assert!(
+            epsilon > 0.0 && epsilon < 1.0,
+            "the error rate must be in (0, 1)"
+        );
+        SoftHeap {
+            nodes: Vec::new(),
+            items: Vec::new(),
+            roots: Vec::new(),
+            threshold: (1.0 / epsilon).log2().ceil() as usize + 5,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    // Returns true if the heap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Returns an estimate of the heap bytes held by the nodes and items.
+    pub fn memory_footprint(&self) -> usize {
+        self.nodes.heap_bytes() + self.items.heap_bytes() + self.roots.heap_bytes()
+    }
+
+    pub fn insert(&mut self, item: T) {
+        self.items.push((Some(item.clone()), NIL));
+        let slot = self.items.len() - 1;
+        let mut tree = self.new_node(item, 0, NIL, NIL);
+        self.nodes[tree].head = slot;
+        self.nodes[tree].tail = slot;
+        self.nodes[tree].len = 1;
+        self.len += 1;
+
+        // Adds the tree like a carry in a binary counter.
+        let mut rank = 0;
+        while let Some(&other) = self.roots.get(rank)
+            && other != NIL
+        {
+            self.roots[rank] = NIL;
+            tree = self.combine(other, tree);
+            rank += 1;
+        }
+        if rank == self.roots.len() {
+            self.roots.push(NIL);
+        }
+        self.roots[rank] = tree;
+    }
+
+    // Removes an item of the root with the smallest key, returning it with
+    // its current, possibly corrupted, key.
+    pub fn extract_min(&mut self) -> Option<(T, T)> {
+        let rank = (0..self.roots.len())
+            .filter(|&rank| self.roots[rank] != NIL)
+            .min_by(|&a, &b| {
+                let (a, b) = (self.roots[a], self.roots[b]);
+                self.nodes[a].ckey.cmp(&self.nodes[b].ckey)
+            })?;
+        let root = self.roots[rank];
+        let ckey = self.nodes[root].ckey.clone();
+
+        let slot = self.nodes[root].head;
+        let (item, next) = &mut self.items[slot];
+        let item = item.take().expect("listed slots hold an item");
+        self.nodes[root].head = *next;
+        if *next == NIL {
+            self.nodes[root].tail = NIL;
+        }
+        self.nodes[root].len -= 1;
+        self.len -= 1;
+
+        let node = &self.nodes[root];
+        if node.len * 2 < node.target && !self.is_leaf(root) {
+            self.sift(root);
+        }
+        if self.nodes[root].len == 0 {
+            self.roots[rank] = NIL;
+            while self.roots.last() == Some(&NIL) {
+                self.roots.pop();
+            }
+        }
+        Some((item, ckey))
+    }
+
+    // Empties the heap, returning every item with its current key in no
+    // particular order.
+    // # Note: O(n), unlike repeated extractions.
+    pub fn drain(&mut self) -> Vec<(T, T)> {
+        let mut drained = Vec::with_capacity(self.len);
+        for node in &self.nodes {
+            let mut slot = node.head;
+            while slot != NIL {
+                let (item, next) = &mut self.items[slot];
+                drained.push((
+                    item.take().expect("listed slots hold an item"),
+                    node.ckey.clone(),
+                ));
+                slot = *next;
+            }
+        }
+        self.nodes.clear();
+        self.items.clear();
+        self.roots.clear();
+        self.len = 0;
+        drained
+    }
+
+    fn new_node(&mut self, ckey: T, rank: usize, left: usize, right: usize) -> usize {
+        let target = match rank {
+            rank if rank <= self.threshold => 1,
+            _ => (3 * self.nodes[left].target).div_ceil(2),
+        };
+        self.nodes.push(SoftNode {
+            ckey,
+            rank,
+            target,
+            left,
+            right,
+            head: NIL,
+            tail: NIL,
+            len: 0,
+        });
+        self.nodes.len() - 1
+    }
+
+    // Links two trees of the same rank under a new root.
+    fn combine(&mut self, left: usize, right: usize) -> usize {
+        let ckey = self.nodes[left].ckey.clone();
+        let node = self.new_node(ckey, self.nodes[left].rank + 1, left, right);
+        self.sift(node);
+        node
+    }
+
+    fn is_leaf(&self, node: usize) -> bool {
+        self.nodes[node].left == NIL && self.nodes[node].right == NIL
+    }
+
+    // Refills the list of a node from its children, taking the whole list
+    // of the child with the smaller key until the target is reached.
+    fn sift(&mut self, node: usize) {
+        while self.nodes[node].len < self.nodes[node].target && !self.is_leaf(node) {
+            let (left, right) = (self.nodes[node].left, self.nodes[node].right);
+            if left == NIL || (right != NIL && self.nodes[left].ckey > self.nodes[right].ckey) {
+                self.nodes[node].left = right;
+                self.nodes[node].right = left;
+            }
+            let child = self.nodes[node].left;
+
+            // Concatenates the child's list to the node's one.
+            let (head, tail, len) = {
+                let child = &mut self.nodes[child];
+                let list = (child.head, child.tail, child.len);
+                (child.head, child.tail, child.len) = (NIL, NIL, 0);
+                list
+            };
+            match self.nodes[node].tail {
+                NIL => self.nodes[node].head = head,
+                last => self.items[last].1 = head,
+            }
+            self.nodes[node].tail = tail;
+            self.nodes[node].len += len;
+            self.nodes[node].ckey = self.nodes[child].ckey.clone();
+
+            if self.is_leaf(child) {
+                self.nodes[node].left = NIL;
+            } else {
+                self.sift(child);
+            }
+        }
+    }
+}
+
+// Binary min-heap over the indices 0..capacity, each present at most once
+// with a key that can be lowered in place.
+// # Note: The heap never holds more than `capacity` entries, unlike lazy
+// #       insertion of updated keys.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndexedBinaryHeap<K> {
+    // Heap of indices ordered by their key.
+    heap: Vec<usize>,
+    // Position of each index in `heap`, NIL if absent.
+    positions: Vec<usize>,
+    keys: Vec<Option<K>>,
+}
+
+impl<K: Ord> IndexedBinaryHeap<K> {
+    // Constructs an empty heap for the indices 0..capacity.
+    pub fn new(capacity: usize) -> Self {
+        IndexedBinaryHeap {
+            heap: Vec::new(),
+            positions: vec![NIL; capacity],
+            keys: (0..capacity).map(|_| None).collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    // Returns true if the heap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    // Empties the heap and resizes it for the indices 0..capacity, reusing
+    // its buffers.
+    pub fn reset(&mut self, capacity: usize) {
+        self.heap.clear();
+        self.positions.clear();
+        self.positions.resize(capacity, NIL);
+        self.keys.clear();
+        self.keys.resize_with(capacity, || None);
+    }
+
+    // Returns an estimate of the heap bytes held by the heap and its index.
+    pub fn memory_footprint(&self) -> usize {
+        self.heap.heap_bytes() + self.positions.heap_bytes() + self.keys.heap_bytes()
+    }
+
+    // Returns true if the index is in the heap.
+    pub fn contains(&self, index: usize) -> bool {
+        self.positions.get(index).is_some_and(|&p| p != NIL)
+    }
+
+    // Returns the key of an index in the heap.
+    pub fn key(&self, index: usize) -> Option<&K> {
+        self.keys.get(index).and_then(Option::as_ref)
+    }
+
+    // Returns the index with the smallest key and its key.
+    pub fn peek(&self) -> Option<(usize, &K)> {
+        let &index = self.heap.first()?;
+        self.key(index).map(|key| (index, key))
+    }
+
+    // Inserts an index with its key.
+    // # Panic: The index must be in range and not already in the heap.
+    pub fn push(&mut self, index: usize, key: K) {
+        assert!(
+            !self.contains(index),
+            "index {index} is already in the heap"
+        );
+        self.keys[index] = Some(key);
+        self.positions[index] = self.heap.len();
+        self.heap.push(index);
+        self.sift_up(self.heap.len() - 1);
+    }
+
+    // Removes the index with the smallest key, returning it with its key.
+    pub fn pop(&mut self) -> Option<(usize, K)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        let index = self.heap.swap_remove(0);
+        self.positions[index] = NIL;
+        if let Some(&first) = self.heap.first() {
+            self.positions[first] = 0;
+            self.sift_down(0);
+        }
+        self.keys[index].take().map(|key| (index, key))
+    }
+
+    // Lowers the key of an index in the heap, returning false and leaving
+    // the heap unchanged if it's absent or the key isn't smaller.
+    pub fn decrease_key(&mut self, index: usize, new_key: K) -> bool {
+        match self.key(index) {
+            Some(key) if new_key < *key => {}
+            _ => return false,
+        }
+        self.keys[index] = Some(new_key);
+        self.sift_up(self.positions[index]);
+        true
+    }
+
+    fn less(&self, a: usize, b: usize) -> bool {
+        self.keys[self.heap[a]] < self.keys[self.heap[b]]
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.positions[self.heap[a]] = a;
+        self.positions[self.heap[b]] = b;
+    }
+
+    // Moves the entry at `i` up until its parent is smaller.
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if !self.less(i, parent) {
+                break;
+            }
+            self.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    // Moves the entry at `i` down until its children are larger.
+    fn sift_down(&mut self, mut i: usize) {
+        let n = self.heap.len();
+        loop {
+            let mut min = i;
+            for child in [2 * i + 1, 2 * i + 2] {
+                if child < n && self.less(child, min) {
+                    min = child;
+                }
+            }
+            if min == i {
+                break;
+            }
+            self.swap(i, min);
+            i = min;
+        }
+    }
+}
@@ -0,0 +1,72 @@
+// # Incremental Kruskal
+//
+// Kruskal fed with batches of edges, e.g. chunks read from a message queue.
+// Only the current spanning forest is kept between batches: each batch is
+// merged with the forest and Kruskal keeps the tree edges of the union, so
+// memory is O(n + b) for batches of b edges.
+use crate::constants::*;
+use crate::error::GraphError;
+use crate::graph::Edge;
+use crate::memory::HeapBytes;
+use crate::mst_result::MstResult;
+use crate::union_find::UnionFind;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IncrementalKruskal {
+    num_vertices: usize,
+    union_find: UnionFind,
+    // Minimum spanning forest of the edges pushed so far, sorted by
+    // `Edge::key` between batches.
+    forest: Vec<Edge>,
+}
+
+impl IncrementalKruskal {
+    // Constructs an empty forest on `num_vertices` vertices.
+    pub fn new(num_vertices: usize) -> Self {
+        IncrementalKruskal {
+            num_vertices,
+            union_find: UnionFind::new(num_vertices),
+            forest: Vec::new(),
+        }
+    }
+
+    // Adds a batch of edges, keeping the minimum spanning forest of all the
+    // edges pushed so far. The batch is rejected as a whole if an endpoint
+    // isn't a vertex, self-loops are ignored.
+    // # Note: O((n + b) log(n + b)) for a batch of b edges.
+    pub fn push_edges(&mut self, edges: &[Edge]) -> Result<(), GraphError> {
+        let n = self.num_vertices;
+        if let Some(edge) = edges.iter().find(|e| e.from >= n || e.to >= n) {
+            return Err(GraphError::VertexOutOfBounds {
+                vertex: edge.from.max(edge.to),
+                num_vertices: n,
+            });
+        }
+        self.forest
+            .extend(edges.iter().filter(|e| e.from != e.to).copied());
+        self.forest.sort_unstable_by_key(Edge::key);
+
+        self.union_find.reset(n);
+        let union_find = &mut self.union_find;
+        self.forest.retain(|e| union_find.union(e.from, e.to));
+        Ok(())
+    }
+
+    // Returns the minimum spanning forest of the edges pushed so far.
+    pub fn forest(&self) -> &[Edge] {
+        &self.forest
+    }
+
+    // Returns an estimate of the heap bytes held by the algorithm: the
+    // union-find and the forest, which also holds the batch being merged.
+    pub fn memory_footprint(&self) -> usize {
+        self.union_find.memory_footprint() + self.forest.heap_bytes()
+    }
+
+    // Returns the spanning tree of all the edges pushed, failing if they
+    // don't connect the graph.
+    pub fn finalize(self) -> Result<MstResult, GraphError> {
+        let cost = self.forest.iter().map(|e| e.weight as CostSum).sum();
+        MstResult::from_forest(self.num_vertices, self.forest, cost)
+    }
+}
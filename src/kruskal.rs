@@ -3,47 +3,98 @@ use std::cmp::Reverse;
 use std::collections::BinaryHeap;
 // # Heap Kruskal
 //
-// Implementation of the Kruskal algorithm using an heap.
+// Implementation of the Kruskal algorithm using an heap, the standard
+// binary heap by default or any `MinHeap` such as a `DaryHeap`.
 use crate::constants::*;
+use crate::error::GraphError;
 use crate::graph_matrix::GraphMatrix;
+use crate::heaps::MinHeap;
+use crate::mst_result::MstResult;
+use crate::reuse::{Reusable, TreeBuffer};
 use crate::union_find::UnionFind;
 
-pub struct Kruskal {
+pub struct Kruskal<H = BinaryHeap<Reverse<Edge>>> {
     num_vertices: usize,
     union_find: UnionFind,
-    heap: BinaryHeap<Reverse<Edge>>,
-    mst_edges: Vec<Edge>,
-    mst_cost: Cost,
+    heap: H,
+    tree: TreeBuffer,
 }
 
 impl Kruskal {
     // Constructs the algorithm structures
     pub fn new(graph: &GraphMatrix<usize>) -> Self {
-        let edges = graph.all_edges();
-        let num_vertices = graph.num_vertices();
-        let heap: BinaryHeap<Reverse<Edge>> = edges.into_iter().map(Reverse).collect();
+        Self::with_heap(graph)
+    }
+}
 
+impl<H: MinHeap<Edge>> Kruskal<H> {
+    // Constructs the algorithm structures on the heap `H`.
+    // # Example: let algo = Kruskal::<DaryHeap<Edge, 4>>::with_heap(&g);
+    pub fn with_heap(graph: &GraphMatrix<usize>) -> Self {
+        let num_vertices = graph.num_vertices();
         Kruskal {
             num_vertices,
             union_find: UnionFind::new(num_vertices),
-            heap,
-            mst_cost: 0,
-            mst_edges: Vec::new(),
+            heap: H::from_vec(graph.all_edges()),
+            tree: TreeBuffer::default(),
         }
     }
-    // Runs the algorithm and returns a set of edges representing the minimum
-    // spanning tree and its associated totale cost.
-    pub fn run(&mut self) -> (Vec<Edge>, Cost) {
-        while self.mst_edges.len() < self.num_vertices - 1 {
-            if let Some(Reverse(edge)) = self.heap.pop() {
+
+    // Pops the edges in order, returning the number of tree edges found.
+    // The edges themselves are only kept if `keep_edges` is set.
+    fn grow(&mut self, keep_edges: bool) -> usize {
+        let mut count = 0;
+        while count + 1 < self.num_vertices {
+            if let Some(edge) = self.heap.pop_min() {
                 if self.union_find.union(edge.from, edge.to) {
-                    self.mst_edges.push(edge);
-                    self.mst_cost += edge.weight;
+                    self.tree.add(edge, keep_edges);
+                    count += 1;
                 }
             } else {
                 break;
             }
         }
-        (self.mst_edges.clone(), self.mst_cost)
+        count
+    }
+
+    // Runs the algorithm on another graph, reusing the buffers of the
+    // previous run, see `Reusable`.
+    pub fn run_on(&mut self, graph: &GraphMatrix<usize>) -> Result<MstResult, GraphError> {
+        self.reset_with(graph);
+        self.grow(true);
+        self.tree.result(self.num_vertices)
+    }
+
+    // Returns an estimate of the heap bytes held by the algorithm: the heap,
+    // the union-find and the tree.
+    pub fn memory_footprint(&self) -> usize {
+        self.union_find.memory_footprint()
+            + self.heap.memory_footprint()
+            + self.tree.memory_footprint()
+    }
+
+    // Runs the algorithm and returns a set of edges representing the minimum
+    // spanning tree and its associated total cost, consuming the algorithm
+    // (see `Reusable`).
+    pub fn run(mut self) -> Result<MstResult, GraphError> {
+        self.grow(true);
+        self.tree.into_result(self.num_vertices)
+    }
+
+    // Runs the algorithm and returns only the total cost of the minimum
+    // spanning tree, its edges are never stored.
+    pub fn run_cost(mut self) -> Result<Cost, GraphError> {
+        let count = self.grow(false);
+        self.tree.total(self.num_vertices, count)
+    }
+}
+
+impl<H: MinHeap<Edge>> Reusable<&GraphMatrix<usize>> for Kruskal<H> {
+    // Refills the heap in bulk, O(m) for the binary and d-ary heaps.
+    fn reset_with(&mut self, graph: &GraphMatrix<usize>) {
+        self.num_vertices = graph.num_vertices();
+        self.heap.refill(graph.cached_edges());
+        self.union_find.reset(self.num_vertices);
+        self.tree.clear();
     }
 }
@@ -0,0 +1,112 @@
+// # Lowest Common Ancestor
+//
+// Constant time LCA queries on a rooted minimum spanning tree, with a sparse
+// table of depth minima over its Euler tour.
+use crate::constants::{Cost, VertexId};
+use crate::rooted_tree::RootedTree;
+
+#[derive(Clone, Debug)]
+pub struct Lca {
+    depths: Vec<usize>,
+    // Total weight of the path from the root to each vertex.
+    distances: Vec<Cost>,
+    // Vertices in the order a DFS enters and re-enters them, 2n - 1 of them.
+    tour: Vec<VertexId>,
+    // First position of each vertex in the tour.
+    first: Vec<usize>,
+    // Position of the shallowest vertex in `tour[i..i + 2^k]` at `sparse[k][i]`.
+    sparse: Vec<Vec<usize>>,
+}
+
+impl Lca {
+    // Builds the Euler tour and its sparse table.
+    // # Example: let lca = Lca::new(&mst.root_at(0));
+    // # Note: O(n log n) time and memory.
+    pub fn new(tree: &RootedTree) -> Self {
+        let n = tree.num_vertices();
+        let depths = tree.depths().to_vec();
+        let mut distances = vec![0; n];
+        for &v in tree.bfs_order() {
+            if let Some(parent) = tree.parent(v) {
+                distances[v] = distances[parent] + tree.parent_weight(v).unwrap_or(0);
+            }
+        }
+
+        let mut tour = Vec::with_capacity(2 * n - 1);
+        let mut first = vec![0; n];
+        // Vertices with the number of their children already visited.
+        let mut stack = vec![(tree.root(), 0)];
+        tour.push(tree.root());
+        while let Some((v, visited)) = stack.pop() {
+            match tree.children(v).get(visited) {
+                Some(&child) => {
+                    stack.push((v, visited + 1));
+                    stack.push((child, 0));
+                    first[child] = tour.len();
+                    tour.push(child);
+                }
+                None => {
+                    if let Some(&(parent, _)) = stack.last() {
+                        tour.push(parent);
+                    }
+                }
+            }
+        }
+
+        let shallower = |a: usize, b: usize| {
+            if depths[tour[a]] <= depths[tour[b]] {
+                a
+            } else {
+                b
+            }
+        };
+        let mut sparse = vec![(0..tour.len()).collect::<Vec<_>>()];
+        let mut width = 1;
+        while 2 * width <= tour.len() {
+            let previous = &sparse[sparse.len() - 1];
+            let level = (0..=tour.len() - 2 * width)
+                .map(|i| shallower(previous[i], previous[i + width]))
+                .collect();
+            sparse.push(level);
+            width *= 2;
+        }
+
+        Lca {
+            depths,
+            distances,
+            tour,
+            first,
+            sparse,
+        }
+    }
+
+    // Returns the deepest vertex that is an ancestor of both `u` and `v`.
+    // # Panic: Both vertices must be in the tree.
+    pub fn lca(&self, u: VertexId, v: VertexId) -> VertexId {
+        let (mut i, mut j) = (self.first[u], self.first[v]);
+        if i > j {
+            std::mem::swap(&mut i, &mut j);
+        }
+        let k = (usize::BITS - 1 - (j - i + 1).leading_zeros()) as usize;
+        let (a, b) = (self.sparse[k][i], self.sparse[k][j + 1 - (1 << k)]);
+        if self.depths[self.tour[a]] <= self.depths[self.tour[b]] {
+            self.tour[a]
+        } else {
+            self.tour[b]
+        }
+    }
+
+    // Returns the total weight of the tree path between `u` and `v`.
+    // # Panic: Both vertices must be in the tree.
+    pub fn path_length(&self, u: VertexId, v: VertexId) -> Cost {
+        let ancestor = self.lca(u, v);
+        self.distances[u] + self.distances[v] - 2 * self.distances[ancestor]
+    }
+
+    // Returns the number of edges on the tree path between `u` and `v`.
+    // # Panic: Both vertices must be in the tree.
+    pub fn path_edges(&self, u: VertexId, v: VertexId) -> usize {
+        let ancestor = self.lca(u, v);
+        self.depths[u] + self.depths[v] - 2 * self.depths[ancestor]
+    }
+}
@@ -3,30 +3,129 @@
 // From-scratch generic implementation of different variants of Kruskal's algorithm.
 
 mod constants;
+mod contraction;
 mod union_find;
 
+mod digraph_matrix;
+mod distance_matrix;
+mod filtered_graph;
 mod graph;
+mod graph_builder;
 mod graph_matrix;
 mod graph_stars;
+mod lca;
+mod memory;
+mod minimax_paths;
+mod mst_builder;
+mod mst_result;
+mod partition;
+mod reuse;
+mod rng;
+mod rooted_tree;
 
+#[cfg(feature = "petgraph")]
+mod petgraph_interop;
+
+pub mod algorithm;
+pub mod approx_kruskal;
+pub mod auto;
+pub mod boruvka_filter_kruskal;
+pub mod bucket_kruskal;
+pub mod certificate;
+pub mod chazelle;
+#[cfg(feature = "compact")]
+pub mod compact;
+pub mod connectivity;
+pub mod consistency;
+#[cfg(feature = "mmap")]
+pub mod edge_file;
 pub mod error;
+#[cfg(feature = "rand")]
+pub mod experiments;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod filter_kruskal;
+pub mod filter_kruskal_plus;
+#[cfg(feature = "float")]
+pub mod float;
+pub mod generators;
+pub mod heaps;
+pub mod incremental_kruskal;
 pub mod kruskal;
+pub mod multi_weight;
+#[cfg(feature = "parallel")]
+pub mod par_sort_kruskal;
+pub mod prim;
+#[cfg(feature = "python")]
+mod python;
 pub mod qs_kruskal;
+pub mod radix_kruskal;
+pub mod registry;
+pub mod render;
+pub mod sensitivity;
+pub mod shortest_path;
+pub mod signed;
 pub mod skewed_filter_kruskal;
+pub mod sort_kruskal;
+pub mod spanning_tree;
 pub mod sqsk;
+pub mod stats;
+#[cfg(feature = "rand")]
+pub mod strategies;
+pub mod streaming_mst;
+pub mod trace;
 
 pub use constants::*;
 
+pub use contraction::contract;
+pub use digraph_matrix::DiGraphMatrix;
+pub use distance_matrix::DistanceMatrix;
+pub use filtered_graph::FilteredGraph;
 pub use graph::Edge;
 pub use graph::Graph;
 pub use graph::Vertex;
-pub use graph_matrix::GraphMatrix;
-pub use graph_stars::GraphStars;
+pub use graph::Violation;
+pub use graph::{normalize_edges, same_edges, sort_canonical};
+pub use graph_builder::{ConcurrentGraphBuilder, EdgeWriter, GraphBuilder};
+pub use graph_matrix::{GraphMatrix, TriangularView};
+pub use graph_stars::{EdgeInsertion, EdgePolicy, GraphStars};
+pub use lca::Lca;
+pub use minimax_paths::MinimaxPaths;
+pub use mst_builder::{Direction, Mst, MstBuilder};
+pub use mst_result::{diff, MstDiff, MstResult};
+pub use partition::PivotStrategy;
+pub use reuse::Reusable;
+pub use rng::SplitMix64;
+pub use rooted_tree::RootedTree;
+pub use union_find::{ConcurrentUnionFind, DisjointSets, UnionFind};
 
+pub use algorithm::Algorithm;
+pub use approx_kruskal::{ApproximateKruskal, ApproximateMst};
+pub use boruvka_filter_kruskal::BoruvkaFilterKruskal;
+pub use bucket_kruskal::BucketKruskal;
+pub use certificate::{check_certificate, Certificate};
+pub use chazelle::Chazelle;
+#[cfg(feature = "compact")]
+pub use compact::{CompactEdge, CompactKruskal};
+#[cfg(feature = "mmap")]
+pub use edge_file::EdgeFile;
 pub use error::GraphError;
 pub use filter_kruskal::FilterKruskal;
+pub use filter_kruskal_plus::FilterKruskalPlus;
+#[cfg(feature = "float")]
+pub use float::{FloatGraph, FloatMst, OrderedF64};
+pub use heaps::{DaryHeap, IndexedBinaryHeap, MinHeap, PairingHandle, PairingHeap, SoftHeap};
+pub use incremental_kruskal::IncrementalKruskal;
 pub use kruskal::Kruskal;
+pub use multi_weight::{CostSelector, MultiWeightGraph, MultiWeightMst};
+#[cfg(feature = "parallel")]
+pub use par_sort_kruskal::ParallelSortKruskal;
+pub use prim::{DensePrim, ImplicitPrim, SparsePrim};
 pub use qs_kruskal::QuickSortKruskal;
+pub use radix_kruskal::RadixKruskal;
+pub use registry::{MstAlgorithm, Registry};
+pub use sensitivity::{classify_edges, EdgeClass};
 pub use skewed_filter_kruskal::SkewedFilterKruskal;
+pub use sort_kruskal::SortKruskal;
 pub use sqsk::StarQuickSortKruskal;
+pub use streaming_mst::StreamingMst;
@@ -1,24 +1,31 @@
-use mst_kruskal_variants::{Graph, GraphMatrix, Kruskal};
+use mst_kruskal_variants::{stats, Graph, GraphMatrix, Registry};
 
-// Library example usage
+// Library example usage, an optional seed argument makes the graph
+// reproducible and an optional second one names the variant to run (see
+// `Registry`), Kruskal by default.
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut rng = rand::rng();
-    let graph = GraphMatrix::new_random(0..10, 0.5, 1, 100, true, &mut rng)?;
+    let mut args = std::env::args().skip(1);
+    let graph = match args.next() {
+        Some(seed) => GraphMatrix::new_random_seeded(0..10, 0.5, 1, 100, true, seed.parse()?)?,
+        None => GraphMatrix::new_random(0..10, 0.5, 1, 100, true, &mut rand::rng())?,
+    };
 
     println!(
         "Generated a random graph with {} vertices.",
         graph.num_vertices()
     );
+    println!("{}", stats::summarize(&graph));
 
-    let mut algo = Kruskal::new(&graph);
+    let name = args.next().unwrap_or_else(|| "kruskal".to_string());
+    let algo = Registry::new().create(&name)?;
 
-    let (mst_edges, total_cost) = algo.run();
+    let mst = algo.run(graph.num_vertices(), &graph.all_edges(), &mut rand::rng())?;
 
     println!("MST Calculation complete.");
-    println!("Total Cost: {}", total_cost);
-    println!("Edges in MST: {}", mst_edges.len());
+    println!("Total Cost: {}", mst.cost);
+    println!("Edges in MST: {}", mst.len());
 
-    for edge in mst_edges {
+    for edge in mst.edges {
         println!("  {} -> {} (cost: {})", edge.from, edge.to, edge.weight);
     }
 
@@ -0,0 +1,29 @@
+// # Memory
+//
+// Estimates of the heap memory held by the graphs and the algorithms, from
+// the capacity of their buffers.
+use std::collections::HashMap;
+use std::mem::size_of;
+
+// Heap bytes allocated by a buffer, not counting what its elements own.
+pub(crate) trait HeapBytes {
+    fn heap_bytes(&self) -> usize;
+}
+
+impl<T> HeapBytes for Vec<T> {
+    fn heap_bytes(&self) -> usize {
+        self.capacity() * size_of::<T>()
+    }
+}
+
+// # Note: The control bytes of the table aren't counted.
+impl<K, V> HeapBytes for HashMap<K, V> {
+    fn heap_bytes(&self) -> usize {
+        self.capacity() * size_of::<(K, V)>()
+    }
+}
+
+// Heap bytes of a vector of vectors, the rows included.
+pub(crate) fn nested_heap_bytes<T>(rows: &[Vec<T>]) -> usize {
+    rows.iter().map(HeapBytes::heap_bytes).sum()
+}
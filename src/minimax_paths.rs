@@ -0,0 +1,85 @@
+// # Minimax Paths
+//
+// Bottleneck queries on a minimum spanning tree: the path between two
+// vertices in the MST minimizes the heaviest edge over all the paths
+// between them in the graph.
+use crate::constants::{Cost, VertexId};
+use crate::rooted_tree::RootedTree;
+
+// Binary lifting table of the ancestors and the heaviest edges towards
+// them, answering minimax queries in O(log n).
+#[derive(Clone, Debug)]
+pub struct MinimaxPaths {
+    depths: Vec<usize>,
+    // Ancestor 2^k levels above each vertex, the root for the ones above it.
+    ancestors: Vec<Vec<VertexId>>,
+    // Heaviest edge on the way to that ancestor.
+    maxima: Vec<Vec<Cost>>,
+}
+
+impl MinimaxPaths {
+    // Builds the table of a rooted minimum spanning tree.
+    // # Example: let paths = MinimaxPaths::new(&mst.root_at(0));
+    // # Note: O(n log n) time and memory.
+    pub fn new(tree: &RootedTree) -> Self {
+        let n = tree.num_vertices();
+        let levels = (usize::BITS - n.leading_zeros()).max(1) as usize;
+        let mut ancestors = vec![vec![tree.root(); n]; levels];
+        let mut maxima = vec![vec![0; n]; levels];
+        for v in 0..n {
+            if let Some(parent) = tree.parent(v) {
+                ancestors[0][v] = parent;
+                maxima[0][v] = tree.parent_weight(v).unwrap_or(0);
+            }
+        }
+        for k in 1..levels {
+            for v in 0..n {
+                let middle = ancestors[k - 1][v];
+                ancestors[k][v] = ancestors[k - 1][middle];
+                maxima[k][v] = maxima[k - 1][v].max(maxima[k - 1][middle]);
+            }
+        }
+        MinimaxPaths {
+            depths: tree.depths().to_vec(),
+            ancestors,
+            maxima,
+        }
+    }
+
+    // Returns the smallest possible heaviest edge over the paths between
+    // two vertices, the heaviest edge on their tree path, 0 if they're equal.
+    // # Panic: Both vertices must be in the tree.
+    pub fn minimax(&self, u: VertexId, v: VertexId) -> Cost {
+        let (mut u, mut v) = if self.depths[u] >= self.depths[v] {
+            (u, v)
+        } else {
+            (v, u)
+        };
+        let mut heaviest = 0;
+
+        // Lift the deeper vertex to the depth of the other.
+        let mut gap = self.depths[u] - self.depths[v];
+        let mut k = 0;
+        while gap > 0 {
+            if gap & 1 == 1 {
+                heaviest = heaviest.max(self.maxima[k][u]);
+                u = self.ancestors[k][u];
+            }
+            gap >>= 1;
+            k += 1;
+        }
+        if u == v {
+            return heaviest;
+        }
+
+        // Lift both below their lowest common ancestor.
+        for k in (0..self.ancestors.len()).rev() {
+            if self.ancestors[k][u] != self.ancestors[k][v] {
+                heaviest = heaviest.max(self.maxima[k][u]).max(self.maxima[k][v]);
+                u = self.ancestors[k][u];
+                v = self.ancestors[k][v];
+            }
+        }
+        heaviest.max(self.maxima[0][u]).max(self.maxima[0][v])
+    }
+}
@@ -0,0 +1,113 @@
+// # Builder
+//
+// One place for the options otherwise spread over the constructors and the
+// `run` signatures of the variants.
+use crate::algorithm::Algorithm;
+use crate::constants::CostSum;
+use crate::error::GraphError;
+use crate::graph::{Edge, Graph};
+use crate::mst_result::MstResult;
+use crate::partition::PivotStrategy;
+use crate::rng::SplitMix64;
+
+// Whether to minimize or maximize the total weight of the tree.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Direction {
+    #[default]
+    Min,
+    Max,
+}
+
+// Entry point of the builder.
+// # Example: let mst = Mst::builder().algorithm(Algorithm::FilterKruskal).seed(42).solve(&g)?;
+pub struct Mst;
+
+impl Mst {
+    pub fn builder() -> MstBuilder {
+        MstBuilder::default()
+    }
+}
+
+// Options of a spanning tree computation, by default Kruskal's minimum
+// spanning tree with the variants' own pivots and a seed of 0.
+#[derive(Copy, Clone, Debug)]
+pub struct MstBuilder {
+    algorithm: Algorithm,
+    pivot: Option<PivotStrategy>,
+    kruskal_threshold: Option<usize>,
+    seed: u64,
+    direction: Direction,
+}
+
+impl Default for MstBuilder {
+    fn default() -> Self {
+        MstBuilder {
+            algorithm: Algorithm::Kruskal,
+            pivot: None,
+            kruskal_threshold: None,
+            seed: 0,
+            direction: Direction::Min,
+        }
+    }
+}
+
+impl MstBuilder {
+    pub fn algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    // Pivot strategy of the partitioning variants, ignored by the others.
+    pub fn pivot(mut self, pivot: PivotStrategy) -> Self {
+        self.pivot = Some(pivot);
+        self
+    }
+
+    // Range length below which Filter Kruskal sorts, see
+    // `FilterKruskal::set_kruskal_threshold`.
+    pub fn kruskal_threshold(mut self, threshold: usize) -> Self {
+        self.kruskal_threshold = Some(threshold);
+        self
+    }
+
+    // Seed of the `SplitMix64` drawn from by the randomized variants.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    // Computes the spanning tree of the graph.
+    pub fn solve<T, G: Graph<T>>(&self, graph: &G) -> Result<MstResult, GraphError> {
+        self.solve_edges(graph.num_vertices(), &graph.all_edges())
+    }
+
+    // Same as `solve` on a plain edge list, see `Algorithm::run_on_edges`.
+    // # Note: A maximum spanning tree is found as the minimum one under the
+    // #       weights `max - w`, the tree keeping the original weights.
+    pub fn solve_edges(
+        &self,
+        num_vertices: usize,
+        edges: &[Edge],
+    ) -> Result<MstResult, GraphError> {
+        let mut rng = SplitMix64::new(self.seed);
+        let run = |edges: &[Edge], rng: &mut SplitMix64| {
+            self.algorithm
+                .run_tuned(num_vertices, edges, self.pivot, self.kruskal_threshold, rng)
+        };
+        if self.direction == Direction::Min {
+            return run(edges, &mut rng);
+        }
+
+        let max = edges.iter().map(|e| e.weight).max().unwrap_or(0);
+        let flip = |e: &Edge| Edge::new(e.from, e.to, max - e.weight);
+        let flipped: Vec<Edge> = edges.iter().map(flip).collect();
+        let tree: Vec<Edge> = run(&flipped, &mut rng)?.edges.iter().map(flip).collect();
+        let cost: CostSum = tree.iter().map(|e| e.weight as CostSum).sum();
+        MstResult::from_forest(num_vertices, tree, cost)
+    }
+}
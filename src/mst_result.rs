@@ -0,0 +1,271 @@
+// # MST Result
+//
+// Output of the minimum spanning tree algorithms.
+use crate::constants::{Cost, CostSum, EdgeId, VertexId};
+use crate::error::GraphError;
+use crate::graph::{sort_canonical, Edge, Graph};
+use crate::graph_stars::GraphStars;
+use crate::rooted_tree::RootedTree;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+// Fails if a forest of `num_edges` edges doesn't span all the
+// `num_vertices` vertices of the graph.
+// # Note: A forest on n vertices with k edges has n - k components.
+pub(crate) fn check_spanning(num_vertices: usize, num_edges: usize) -> Result<(), GraphError> {
+    if num_edges + 1 < num_vertices {
+        return Err(GraphError::Disconnected {
+            components: num_vertices - num_edges,
+        });
+    }
+    Ok(())
+}
+
+// Narrows the total cost accumulated by an algorithm to the cost type,
+// failing if it doesn't fit.
+pub(crate) fn to_cost(total: CostSum) -> Result<Cost, GraphError> {
+    Cost::try_from(total).map_err(|_| GraphError::CostOverflow(total))
+}
+
+// A spanning tree as returned by the algorithms' `run`.
+// # Note: Equality compares the edges in order, see `canonical`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MstResult {
+    pub edges: Vec<Edge>,
+    pub cost: Cost,
+}
+
+impl MstResult {
+    // Wraps the forest found by an algorithm, failing if it doesn't span
+    // all the `num_vertices` vertices of the graph or its total cost
+    // overflows `Cost`.
+    pub(crate) fn from_forest(
+        num_vertices: usize,
+        edges: Vec<Edge>,
+        cost: CostSum,
+    ) -> Result<Self, GraphError> {
+        check_spanning(num_vertices, edges.len())?;
+        let cost = to_cost(cost)?;
+        Ok(MstResult { edges, cost })
+    }
+
+    // Maps the tree edges back to the data of their endpoints.
+    // # Example: for (from, to, cost) in mst.resolve(&graph) { ... }
+    // # Note: Panics if an endpoint isn't a vertex of `graph`, which must be
+    // #       the graph the tree was computed on.
+    pub fn resolve<'g, T, G: Graph<T>>(&self, graph: &'g G) -> Vec<(&'g T, &'g T, Cost)> {
+        let vertices = graph.vertices();
+        self.edges
+            .iter()
+            .map(|e| (&vertices[e.from].data, &vertices[e.to].data, e.weight))
+            .collect()
+    }
+
+    // Returns the id in `graph` of each tree edge, see `Graph::edge`.
+    // # Note: O(m), parallel edges with the same weight are told apart by
+    // #       giving each tree edge a distinct id.
+    // # Panic: Every tree edge must be an edge of `graph`.
+    pub fn edge_ids<T, G: Graph<T>>(&self, graph: &G) -> Vec<EdgeId> {
+        let mut ids: HashMap<(Cost, VertexId, VertexId), Vec<EdgeId>> = HashMap::new();
+        for (id, edge) in graph.all_edges().iter().enumerate().rev() {
+            ids.entry(edge.key()).or_default().push(id);
+        }
+        self.edges
+            .iter()
+            .map(|edge| {
+                ids.get_mut(&edge.key())
+                    .and_then(Vec::pop)
+                    .expect("tree edges are edges of the graph")
+            })
+            .collect()
+    }
+
+    // Returns the payload of each tree edge in `graph`, see
+    // `GraphStars::insert_edge_with`.
+    // # Panic: Every tree edge must be an edge of `graph`.
+    pub fn payloads<'g, T: Clone + Eq, E: Default>(
+        &self,
+        graph: &'g GraphStars<T, E>,
+    ) -> Vec<&'g E> {
+        self.edge_ids(graph)
+            .into_iter()
+            .map(|id| &graph.payloads()[id])
+            .collect()
+    }
+
+    // Builds the tree as a graph with the vertices of `original`, the graph
+    // it was computed on, so it can be fed to anything taking a `Graph`.
+    // # Note: Panics if an endpoint isn't a vertex of `original`.
+    pub fn to_graph_stars<T: Clone + Eq>(&self, original: &GraphStars<T>) -> GraphStars<T> {
+        let mut tree = GraphStars::with_edge_policy(original.edge_policy());
+        for vertex in original.vertices() {
+            tree.add_vertex(vertex.data.clone());
+        }
+        for edge in &self.edges {
+            tree.add_edge(edge.from, edge.to, edge.weight)
+                .expect("tree edges join vertices of the original graph");
+        }
+        tree
+    }
+
+    // Roots the tree at vertex `root`, its vertices being the ids up to the
+    // number of edges.
+    // # Panic: `root` must be one of them.
+    pub fn root_at(&self, root: VertexId) -> RootedTree {
+        RootedTree::new(self.edges.len() + 1, &self.edges, root)
+    }
+
+    // Returns, for each tree edge, how much the cost of the minimum spanning
+    // tree of `graph` grows if it's deleted: the weight of the lightest other
+    // edge across its cut minus its own, None for a bridge.
+    // # Note: O(m log m), every non-tree edge in increasing weight labels the
+    // #       tree edges of its path not labelled yet, skipping those with a
+    // #       union-find on the tree. Self-loops are ignored.
+    // # Panic: The tree must be a spanning tree of `graph`.
+    pub fn replacement_costs<T, G: Graph<T>>(&self, graph: &G) -> Vec<Option<Cost>> {
+        let n = graph.num_vertices();
+        if n == 0 {
+            return Vec::new();
+        }
+        let tree = RootedTree::new(n, &self.edges, 0);
+
+        let mut in_tree: HashMap<(Cost, VertexId, VertexId), usize> = HashMap::new();
+        for edge in &self.edges {
+            *in_tree.entry(edge.key()).or_default() += 1;
+        }
+        let mut others: Vec<Edge> = Vec::new();
+        for edge in graph.all_edges().into_iter().filter(|e| e.from != e.to) {
+            match in_tree.get_mut(&edge.key()) {
+                Some(count) if *count > 0 => *count -= 1,
+                _ => others.push(edge),
+            }
+        }
+        others.sort_unstable_by_key(Edge::key);
+
+        // Replacement of the edge from each vertex to its parent, and the
+        // closest ancestor-or-self whose edge has none yet.
+        let mut replacement: Vec<Option<Cost>> = vec![None; n];
+        let mut jump: Vec<VertexId> = (0..n).collect();
+        let find = |jump: &mut Vec<VertexId>, v: VertexId| {
+            let mut root = v;
+            while jump[root] != root {
+                root = jump[root];
+            }
+            let mut curr = v;
+            while curr != root {
+                let next = jump[curr];
+                jump[curr] = root;
+                curr = next;
+            }
+            root
+        };
+        for edge in others {
+            let mut u = find(&mut jump, edge.from);
+            let mut v = find(&mut jump, edge.to);
+            while u != v {
+                if tree.depth(u) < tree.depth(v) {
+                    std::mem::swap(&mut u, &mut v);
+                }
+                replacement[u] = Some(edge.weight);
+                jump[u] = tree.parent(u).expect("only the root has no parent");
+                u = find(&mut jump, u);
+            }
+        }
+
+        self.edges
+            .iter()
+            .map(|edge| {
+                let child = if tree.parent(edge.to) == Some(edge.from) {
+                    edge.to
+                } else {
+                    edge.from
+                };
+                replacement[child].map(|weight| weight - edge.weight)
+            })
+            .collect()
+    }
+
+    // Puts the tree in canonical form: edges sorted by `Edge::key` and
+    // oriented from their smaller endpoint. Every exact variant picks the
+    // same edges, so their canonical results compare equal.
+    // # Example: assert_eq!(a.run()?.canonical(), b.run()?.canonical());
+    pub fn canonical(mut self) -> Self {
+        sort_canonical(&mut self.edges);
+        self
+    }
+
+    // Compares the tree with another one, see `diff`.
+    pub fn diff(&self, other: &MstResult) -> MstDiff {
+        diff(&self.edges, &other.edges)
+    }
+
+    // Returns the number of edges in the tree.
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    // Returns true if the tree has no edges.
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+}
+
+// Differences between two edge sets, e.g. the trees of two variants that
+// broke ties differently.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MstDiff {
+    // Edges of the first set missing from the second, in canonical form.
+    pub only_a: Vec<Edge>,
+    // Edges of the second set missing from the first, in canonical form.
+    pub only_b: Vec<Edge>,
+    // Total weight of the second set minus the first's.
+    pub cost_delta: i128,
+}
+
+impl MstDiff {
+    // Returns true if both sets have the same edges.
+    pub fn is_empty(&self) -> bool {
+        self.only_a.is_empty() && self.only_b.is_empty()
+    }
+}
+
+// Returns the edges in only one of `a` and `b` and their cost difference.
+// Edges match on their weight and endpoints, whatever their orientation,
+// and a repeated edge must be repeated as many times on both sides.
+// # Example: let d = diff(&kruskal.edges, &prim.edges);
+// # Note: O(m log m).
+pub fn diff(a: &[Edge], b: &[Edge]) -> MstDiff {
+    let canonical = |edges: &[Edge]| {
+        let mut edges = edges.to_vec();
+        sort_canonical(&mut edges);
+        edges
+    };
+    let (a, b) = (canonical(a), canonical(b));
+    let total = |edges: &[Edge]| edges.iter().map(|e| e.weight as i128).sum::<i128>();
+    let mut result = MstDiff {
+        cost_delta: total(&b) - total(&a),
+        ..MstDiff::default()
+    };
+
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].key().cmp(&b[j].key()) {
+            Ordering::Less => {
+                result.only_a.push(a[i]);
+                i += 1;
+            }
+            Ordering::Greater => {
+                result.only_b.push(b[j]);
+                j += 1;
+            }
+            Ordering::Equal => {
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    result.only_a.extend_from_slice(&a[i..]);
+    result.only_b.extend_from_slice(&b[j..]);
+    result
+}
@@ -0,0 +1,209 @@
+// # Multi-Weight Edges
+//
+// Graphs whose edges carry several weights, e.g. latency and monetary cost,
+// and a `CostSelector` choosing which of them, or which weighted combination,
+// the spanning tree minimizes. Any variant runs on the selected costs and the
+// tree is reported with the totals of every weight. Lexicographic selectors
+// go through weight ranks, like the `signed` module.
+use crate::algorithm::Algorithm;
+use crate::constants::{Cost, CostSum, EdgeId, VertexId};
+use crate::error::GraphError;
+use crate::graph::{check_bounds, Edge};
+use crate::mst_result::to_cost;
+use crate::signed::rank_edges;
+use rand_core::Rng;
+use std::collections::HashMap;
+
+// The cost an MST run minimizes, picked from the weights of each edge.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CostSelector {
+    // The weight at the given index.
+    Objective(usize),
+    // The sum of the weights scaled by one coefficient each.
+    Combination(Vec<Cost>),
+    // The weight at `primary`, ties broken by the weight at `secondary`,
+    // giving the lexicographically smallest tree.
+    Lexicographic { primary: usize, secondary: usize },
+}
+
+impl CostSelector {
+    // Returns the selected cost of an edge with the given weights, the
+    // primary weight for lexicographic selectors.
+    // # Note: Fails with `CostOverflow` if a combination doesn't fit in `Cost`.
+    pub fn cost(&self, weights: &[Cost]) -> Result<Cost, GraphError> {
+        match self {
+            CostSelector::Objective(index) => objective(weights, *index),
+            CostSelector::Lexicographic { primary, secondary } => {
+                objective(weights, *secondary)?;
+                objective(weights, *primary)
+            }
+            CostSelector::Combination(coefficients) => {
+                if coefficients.len() != weights.len() {
+                    return Err(GraphError::WeightCount {
+                        expected: weights.len(),
+                        len: coefficients.len(),
+                    });
+                }
+                let total = coefficients
+                    .iter()
+                    .zip(weights)
+                    .try_fold(0 as CostSum, |total, (&c, &w)| {
+                        total.checked_add(c as CostSum * w as CostSum)
+                    })
+                    .ok_or(GraphError::CostOverflow(CostSum::MAX))?;
+                to_cost(total)
+            }
+        }
+    }
+}
+
+// Returns the weight at `index`.
+fn objective(weights: &[Cost], index: usize) -> Result<Cost, GraphError> {
+    weights
+        .get(index)
+        .copied()
+        .ok_or(GraphError::UnknownObjective {
+            index,
+            num_weights: weights.len(),
+        })
+}
+
+// An undirected graph as an edge list, every edge carrying `num_weights`
+// weights stored contiguously.
+#[derive(Clone, Debug, Default)]
+pub struct MultiWeightGraph {
+    num_vertices: usize,
+    num_weights: usize,
+    endpoints: Vec<(VertexId, VertexId)>,
+    weights: Vec<Cost>,
+}
+
+// A spanning tree chosen by a `CostSelector`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultiWeightMst {
+    // Ids of the tree edges, their positions in insertion order.
+    pub edge_ids: Vec<EdgeId>,
+    // Total selected cost, of the primary weight for lexicographic
+    // selectors.
+    pub cost: Cost,
+    // Total of each weight over the tree edges.
+    pub totals: Vec<CostSum>,
+}
+
+impl MultiWeightGraph {
+    // Constructs a graph of `num_vertices` vertices without edges, whose
+    // edges will carry `num_weights` weights.
+    pub fn new(num_vertices: usize, num_weights: usize) -> Self {
+        MultiWeightGraph {
+            num_vertices,
+            num_weights,
+            endpoints: Vec::new(),
+            weights: Vec::new(),
+        }
+    }
+
+    pub fn num_vertices(&self) -> usize {
+        self.num_vertices
+    }
+
+    pub fn num_weights(&self) -> usize {
+        self.num_weights
+    }
+
+    pub fn num_edges(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    // Adds an edge and returns its id, failing if an endpoint isn't a vertex
+    // or the number of weights is wrong.
+    // # Example: let id = graph.add_edge(0, 1, &[latency, price])?;
+    pub fn add_edge(
+        &mut self,
+        from: VertexId,
+        to: VertexId,
+        weights: &[Cost],
+    ) -> Result<EdgeId, GraphError> {
+        check_bounds(from, to, self.num_vertices)?;
+        if weights.len() != self.num_weights {
+            return Err(GraphError::WeightCount {
+                expected: self.num_weights,
+                len: weights.len(),
+            });
+        }
+        self.endpoints.push((from, to));
+        self.weights.extend_from_slice(weights);
+        Ok(self.endpoints.len() - 1)
+    }
+
+    // Returns the endpoints of the edge with the given id.
+    pub fn endpoints(&self, id: EdgeId) -> Option<(VertexId, VertexId)> {
+        self.endpoints.get(id).copied()
+    }
+
+    // Returns the weights of the edge with the given id.
+    pub fn weights(&self, id: EdgeId) -> Option<&[Cost]> {
+        let start = id.checked_mul(self.num_weights)?;
+        self.weights.get(start..start + self.num_weights)
+    }
+
+    // Returns every edge with its selected cost, in insertion order.
+    pub fn select(&self, selector: &CostSelector) -> Result<Vec<Edge>, GraphError> {
+        (0..self.num_edges())
+            .map(|id| {
+                let (from, to) = self.endpoints[id];
+                let weights = self.weights(id).expect("every edge has weights");
+                Ok(Edge::new(from, to, selector.cost(weights)?))
+            })
+            .collect()
+    }
+
+    // Runs `algorithm` on the costs picked by `selector`, `rng` is only used
+    // by the randomized variants.
+    // # Note: Self-loops are ignored, parallel edges collapse to the
+    // #       cheapest as in `Algorithm::run_on_edges`, the first inserted
+    // #       one among equally cheap ones.
+    pub fn run<R: Rng + ?Sized>(
+        &self,
+        selector: &CostSelector,
+        algorithm: Algorithm,
+        rng: &mut R,
+    ) -> Result<MultiWeightMst, GraphError> {
+        // Checked up front so that graphs without edges fail the same way.
+        selector.cost(&vec![0; self.num_weights])?;
+        let edges = match *selector {
+            CostSelector::Lexicographic { primary, secondary } => {
+                let pairs: Vec<_> = (0..self.num_edges())
+                    .map(|id| {
+                        let (from, to) = self.endpoints[id];
+                        let weights = self.weights(id).expect("every edge has weights");
+                        (from, to, (weights[primary], weights[secondary]))
+                    })
+                    .collect();
+                rank_edges(&pairs).0
+            }
+            _ => self.select(selector)?,
+        };
+        let tree = algorithm.run_on_edges(self.num_vertices, &edges, rng)?;
+
+        let mut ids: HashMap<(Cost, VertexId, VertexId), EdgeId> = HashMap::new();
+        for (id, edge) in edges.iter().enumerate() {
+            ids.entry(edge.key()).or_insert(id);
+        }
+        let edge_ids: Vec<EdgeId> = tree.edges.iter().map(|e| ids[&e.key()]).collect();
+
+        let mut cost: CostSum = 0;
+        let mut totals = vec![0 as CostSum; self.num_weights];
+        for &id in &edge_ids {
+            let weights = self.weights(id).expect("every edge has weights");
+            cost += selector.cost(weights)? as CostSum;
+            for (total, &w) in totals.iter_mut().zip(weights) {
+                *total += w as CostSum;
+            }
+        }
+        Ok(MultiWeightMst {
+            edge_ids,
+            cost: to_cost(cost)?,
+            totals,
+        })
+    }
+}
@@ -0,0 +1,90 @@
+// # Parallel Sort Kruskal
+//
+// Baseline implementation of the Kruskal algorithm sorting the whole edge
+// array in parallel with rayon before the sequential union loop.
+use crate::constants::*;
+use crate::error::GraphError;
+use crate::graph::{Edge, Graph};
+use crate::graph_matrix::GraphMatrix;
+use crate::memory::HeapBytes;
+use crate::mst_result::MstResult;
+use crate::reuse::{Reusable, TreeBuffer};
+use crate::union_find::UnionFind;
+use rayon::slice::ParallelSliceMut;
+
+pub struct ParallelSortKruskal {
+    num_vertices: usize,
+    edges: Vec<Edge>,
+    union_find: UnionFind,
+    tree: TreeBuffer,
+}
+
+impl ParallelSortKruskal {
+    // Constructs the algorithm structures
+    pub fn new(graph: &GraphMatrix<usize>) -> Self {
+        let num_vertices = graph.num_vertices();
+        ParallelSortKruskal {
+            num_vertices,
+            edges: graph.all_edges(),
+            union_find: UnionFind::new(num_vertices),
+            tree: TreeBuffer::default(),
+        }
+    }
+
+    // Sorts and scans the edges, returning the number of tree edges found.
+    // The edges themselves are only kept if `keep_edges` is set.
+    fn grow(&mut self, keep_edges: bool) -> usize {
+        self.edges.par_sort_unstable_by_key(Edge::key);
+
+        let mut count = 0;
+        for edge in &self.edges {
+            if count + 1 >= self.num_vertices {
+                break;
+            }
+            if self.union_find.union(edge.from, edge.to) {
+                self.tree.add(*edge, keep_edges);
+                count += 1;
+            }
+        }
+        count
+    }
+
+    // Runs the algorithm on another graph, reusing the buffers of the
+    // previous run, see `Reusable`.
+    pub fn run_on(&mut self, graph: &GraphMatrix<usize>) -> Result<MstResult, GraphError> {
+        self.reset_with(graph);
+        self.grow(true);
+        self.tree.result(self.num_vertices)
+    }
+
+    // Returns an estimate of the heap bytes held by the algorithm: the edge
+    // buffer, the union-find and the tree.
+    pub fn memory_footprint(&self) -> usize {
+        self.edges.heap_bytes() + self.union_find.memory_footprint() + self.tree.memory_footprint()
+    }
+
+    // Runs the algorithm and returns a set of edges representing the minimum
+    // spanning tree and its associated total cost, consuming the algorithm
+    // (see `Reusable`).
+    pub fn run(mut self) -> Result<MstResult, GraphError> {
+        self.grow(true);
+        self.tree.into_result(self.num_vertices)
+    }
+
+    // Runs the algorithm and returns only the total cost of the minimum
+    // spanning tree, its edges are never stored.
+    pub fn run_cost(mut self) -> Result<Cost, GraphError> {
+        let count = self.grow(false);
+        self.tree.total(self.num_vertices, count)
+    }
+}
+
+impl Reusable<&GraphMatrix<usize>> for ParallelSortKruskal {
+    fn reset_with(&mut self, graph: &GraphMatrix<usize>) {
+        self.num_vertices = graph.num_vertices();
+        self.edges.clear();
+        self.edges.extend_from_slice(graph.cached_edges());
+        self.union_find.reset(self.num_vertices);
+        self.tree.clear();
+    }
+}
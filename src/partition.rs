@@ -0,0 +1,189 @@
+// # Partition
+//
+// Pivot selection and partitioning shared by the quickselect based variants.
+use crate::graph::Edge;
+use crate::rng;
+use rand_core::Rng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::cmp::Ordering;
+
+// Strategy to choose the pivot of a range of edges.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PivotStrategy {
+    // First edge of the range.
+    First,
+    // Uniformly random edge of the range.
+    Random,
+    // Median of the first, middle and last edges.
+    MedianOfThree,
+    // Tukey's median of three medians of three, spread over the range.
+    Ninther,
+    // Lightest among the first edge and r random ones, with
+    // r = clamp(len / 100, 1, max_samples), skewing the partition so the
+    // light side is small.
+    SkewedMinOf(usize),
+}
+
+impl PivotStrategy {
+    // Returns the index of the chosen pivot in the inclusive range p..=q.
+    pub(crate) fn select<R: Rng + ?Sized>(
+        &self,
+        edges: &[Edge],
+        p: usize,
+        q: usize,
+        rng: &mut R,
+    ) -> usize {
+        match *self {
+            PivotStrategy::First => p,
+            PivotStrategy::Random => rng::between(rng, p, q),
+            PivotStrategy::MedianOfThree => median_of_three(edges, p, p + (q - p) / 2, q),
+            PivotStrategy::Ninther => {
+                let len = q - p + 1;
+                if len < 9 {
+                    return median_of_three(edges, p, p + (q - p) / 2, q);
+                }
+                let step = len / 8;
+                let mid = p + (q - p) / 2;
+                median_of_three(
+                    edges,
+                    median_of_three(edges, p, p + step, p + 2 * step),
+                    median_of_three(edges, mid - step, mid, mid + step),
+                    median_of_three(edges, q - 2 * step, q - step, q),
+                )
+            }
+            PivotStrategy::SkewedMinOf(max_samples) => {
+                let r = ((q - p + 1) / 100).clamp(1, max_samples.max(1));
+                let mut best = p;
+                for _ in 0..r {
+                    let candidate = rng::between(rng, p, q);
+                    if edges[candidate].key() < edges[best].key() {
+                        best = candidate;
+                    }
+                }
+                best
+            }
+        }
+    }
+}
+
+// Returns the index of the median edge among three indices.
+fn median_of_three(edges: &[Edge], a: usize, b: usize, c: usize) -> usize {
+    let (wa, wb, wc) = (edges[a].key(), edges[b].key(), edges[c].key());
+    if (wa <= wb) == (wb <= wc) {
+        b
+    } else if (wb <= wa) == (wa <= wc) {
+        a
+    } else {
+        c
+    }
+}
+
+// Partitions the inclusive range p..=q (p < q) around the pivot in `edges[p]`
+// and returns its final position: lighter edges end up on its left, heavier
+// ones on its right, comparing by `Edge::key`.
+pub(crate) fn partition(edges: &mut [Edge], p: usize, q: usize) -> usize {
+    let pivot_key = edges[p].key();
+    let mut e_plus = q;
+    let mut e_minus = p;
+
+    while e_minus <= e_plus {
+        while edges[e_plus].key() > pivot_key {
+            if e_plus == 0 {
+                break;
+            }
+            e_plus -= 1;
+        }
+        while (e_minus <= e_plus) && (edges[e_minus].key() <= pivot_key) {
+            e_minus += 1;
+        }
+        if e_minus < e_plus {
+            edges.swap(e_minus, e_plus);
+            e_minus += 1;
+            e_plus = e_plus.saturating_sub(1);
+        }
+    }
+    edges.swap(p, e_plus);
+    e_plus
+}
+
+// Partitions the inclusive range p..=q around the weight of `edges[p]` into
+// three parts (Dijkstra's Dutch national flag) and returns the bounds of the
+// middle one: lighter edges end up in p..lt, edges of the pivot's weight in
+// lt..=gt and heavier ones in gt + 1..=q, comparing by weight alone.
+// # Note: Unlike `partition` ties aren't broken by `Edge::key`, the middle
+// #       part still has to be ordered by key before being scanned.
+pub(crate) fn partition_three_way(edges: &mut [Edge], p: usize, q: usize) -> (usize, usize) {
+    let pivot_weight = edges[p].weight;
+    let (mut lt, mut i, mut gt) = (p, p, q + 1);
+
+    while i < gt {
+        match edges[i].weight.cmp(&pivot_weight) {
+            Ordering::Less => {
+                edges.swap(lt, i);
+                lt += 1;
+                i += 1;
+            }
+            Ordering::Greater => {
+                gt -= 1;
+                edges.swap(i, gt);
+            }
+            Ordering::Equal => i += 1,
+        }
+    }
+    (lt, gt - 1)
+}
+
+// Same as `partition`, splitting the range around `edges[p]` in parallel:
+// each thread partitions a block in place, then the light parts are moved
+// together and the heavy ones gathered after them through a buffer.
+// # Note: O(q - p) extra memory, only worth it for large ranges.
+#[cfg(feature = "parallel")]
+pub(crate) fn par_partition(edges: &mut [Edge], p: usize, q: usize) -> usize {
+    let pivot = edges[p];
+    let pivot_key = pivot.key();
+    let range = &mut edges[p + 1..=q];
+    let block = range.len().div_ceil(rayon::current_num_threads()).max(1);
+
+    let lights: Vec<usize> = range
+        .par_chunks_mut(block)
+        .map(|chunk| {
+            let mut light = 0;
+            for i in 0..chunk.len() {
+                if chunk[i].key() <= pivot_key {
+                    chunk.swap(light, i);
+                    light += 1;
+                }
+            }
+            light
+        })
+        .collect();
+
+    let total_light: usize = lights.iter().sum();
+    let mut heavy = vec![pivot; range.len() - total_light];
+    let mut heavy_dst = heavy.as_mut_slice();
+    let mut moves = Vec::with_capacity(lights.len());
+    for (chunk, &light) in range.chunks(block).zip(&lights) {
+        let (to, rest) = std::mem::take(&mut heavy_dst).split_at_mut(chunk.len() - light);
+        heavy_dst = rest;
+        moves.push((&chunk[light..], to));
+    }
+    moves
+        .into_par_iter()
+        .for_each(|(from, to)| to.copy_from_slice(from));
+
+    let mut write_idx = 0;
+    for (i, &light) in lights.iter().enumerate() {
+        range.copy_within(i * block..i * block + light, write_idx);
+        write_idx += light;
+    }
+
+    // The pivot goes between the light and the heavy edges, swapping
+    // places with the last light one.
+    let e_plus = p + total_light;
+    edges[p] = edges[e_plus];
+    edges[e_plus] = pivot;
+    edges[e_plus + 1..=q].copy_from_slice(&heavy);
+    e_plus
+}
@@ -0,0 +1,79 @@
+// # Petgraph interoperability
+//
+// Conversions between petgraph's undirected graphs and this crate's
+// representations, enabled by the `petgraph` feature.
+use crate::constants::Cost;
+use crate::graph::Graph;
+use crate::graph_matrix::GraphMatrix;
+use crate::graph_stars::{EdgePolicy, GraphStars};
+use crate::mst_result::MstResult;
+use petgraph::graph::{NodeIndex, UnGraph};
+use petgraph::visit::EdgeRef;
+use petgraph::Undirected;
+
+impl<N, W> From<&petgraph::Graph<N, W, Undirected>> for GraphStars<N>
+where
+    N: Clone + Eq,
+    W: Copy + Into<Cost>,
+{
+    // Copies the nodes in index order, so a `NodeIndex` maps to the
+    // `VertexId` with the same value.
+    // # Note: Self-loops are dropped since they never belong to a MST, and
+    // #       parallel edges collapse to the cheapest one (`KeepMin`).
+    fn from(graph: &petgraph::Graph<N, W, Undirected>) -> Self {
+        let mut this = GraphStars::with_edge_policy(EdgePolicy::KeepMin);
+        for node in graph.node_weights() {
+            this.add_vertex(node.clone());
+        }
+        for edge in graph.edge_references() {
+            let (from, to) = (edge.source().index(), edge.target().index());
+            if from != to {
+                this.add_edge(from, to, (*edge.weight()).into())
+                    .expect("petgraph indices are below the node count");
+            }
+        }
+        this
+    }
+}
+
+impl<N, W> From<&petgraph::Graph<N, W, Undirected>> for GraphMatrix<N>
+where
+    N: Clone + Eq,
+    W: Copy + Into<Cost>,
+{
+    // Copies the nodes in index order, so a `NodeIndex` maps to the
+    // `VertexId` with the same value.
+    // # Note: Self-loops are dropped since they never belong to a MST, and
+    // #       parallel edges collapse to the cheapest one.
+    fn from(graph: &petgraph::Graph<N, W, Undirected>) -> Self {
+        let mut this = GraphMatrix::new_from_collection(graph.node_weights().cloned());
+        for edge in graph.edge_references() {
+            let (from, to) = (edge.source().index(), edge.target().index());
+            let cost = (*edge.weight()).into();
+            if from != to && this.edge_weight(from, to).is_none_or(|w| cost < w) {
+                this.add_edge(from, to, cost)
+                    .expect("petgraph indices are below the node count");
+            }
+        }
+        this
+    }
+}
+
+impl MstResult {
+    // Converts the tree into a petgraph graph carrying the vertex data
+    // of the graph it was computed on.
+    pub fn to_petgraph<T: Clone, G: Graph<T>>(&self, graph: &G) -> UnGraph<T, Cost> {
+        let mut tree = UnGraph::with_capacity(graph.num_vertices(), self.edges.len());
+        for vertex in graph.vertices() {
+            tree.add_node(vertex.data.clone());
+        }
+        for edge in &self.edges {
+            tree.add_edge(
+                NodeIndex::new(edge.from),
+                NodeIndex::new(edge.to),
+                edge.weight,
+            );
+        }
+        tree
+    }
+}
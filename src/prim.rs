@@ -0,0 +1,296 @@
+// # Prim
+//
+// Implementation of the Prim algorithm scanning the adjacency matrix, O(n^2)
+// regardless of the number of edges, which beats sorting for dense graphs.
+// The same scan runs on implicit complete graphs given by a weight function,
+// while sparse graphs use the adjacency lists and a pairing heap.
+use crate::constants::*;
+use crate::error::GraphError;
+use crate::graph::{Edge, Graph};
+use crate::graph_matrix::GraphMatrix;
+use crate::graph_stars::GraphStars;
+use crate::heaps::{MinHeap, PairingHandle, PairingHeap};
+use crate::memory::HeapBytes;
+use crate::mst_result::{check_spanning, to_cost, MstResult};
+use crate::reuse::{Reusable, TreeBuffer};
+
+pub struct DensePrim<'a> {
+    graph: &'a GraphMatrix<usize>,
+    tree: TreeBuffer,
+}
+
+impl<'a> DensePrim<'a> {
+    // Constructs the algorithm structures, the matrix is borrowed
+    // since Prim only reads it.
+    pub fn new(graph: &'a GraphMatrix<usize>) -> Self {
+        DensePrim {
+            graph,
+            tree: TreeBuffer::default(),
+        }
+    }
+
+    // Grows the trees of the spanning forest, returning the number of tree
+    // edges found. The edges themselves are only kept if `keep_edges` is set.
+    fn grow(&mut self, keep_edges: bool) -> usize {
+        let graph = self.graph;
+        let (count, cost) = dense_prim(
+            graph.num_vertices(),
+            |u, v| graph.cost(u, v),
+            &mut self.tree.edges,
+            keep_edges,
+        );
+        self.tree.cost += cost;
+        count
+    }
+
+    // Runs the algorithm on another graph, reusing the buffer of the tree
+    // edges, see `Reusable`.
+    pub fn run_on(&mut self, graph: &'a GraphMatrix<usize>) -> Result<MstResult, GraphError> {
+        self.reset_with(graph);
+        self.grow(true);
+        self.tree.result(self.graph.num_vertices())
+    }
+
+    // Returns an estimate of the heap bytes held by the algorithm: the tree,
+    // the borrowed graph isn't counted.
+    pub fn memory_footprint(&self) -> usize {
+        self.tree.memory_footprint()
+    }
+
+    // Runs the algorithm and returns a set of edges representing the minimum
+    // spanning tree and its associated total cost, consuming the algorithm
+    // (see `Reusable`).
+    pub fn run(mut self) -> Result<MstResult, GraphError> {
+        self.grow(true);
+        self.tree.into_result(self.graph.num_vertices())
+    }
+
+    // Runs the algorithm and returns only the total cost of the minimum
+    // spanning tree, its edges are never stored.
+    pub fn run_cost(mut self) -> Result<Cost, GraphError> {
+        let count = self.grow(false);
+        self.tree.total(self.graph.num_vertices(), count)
+    }
+}
+
+impl<'a> Reusable<&'a GraphMatrix<usize>> for DensePrim<'a> {
+    fn reset_with(&mut self, graph: &'a GraphMatrix<usize>) {
+        self.graph = graph;
+        self.tree.clear();
+    }
+}
+
+// Prim on an implicit complete graph whose weights are computed on demand,
+// e.g. distances between feature vectors, so the O(n^2) edges are never
+// materialized.
+pub struct ImplicitPrim<F> {
+    num_vertices: usize,
+    weight: F,
+    mst_edges: Vec<Edge>,
+    mst_cost: CostSum,
+}
+
+impl<F: Fn(VertexId, VertexId) -> Cost> ImplicitPrim<F> {
+    // Constructs the algorithm for the complete graph on `num_vertices`
+    // vertices, `weight(u, v)` is only called with u < v.
+    // # Example: let prim = ImplicitPrim::new(points.len(), |u, v| dist(&points[u], &points[v]));
+    pub fn new(num_vertices: usize, weight: F) -> Self {
+        ImplicitPrim {
+            num_vertices,
+            weight,
+            mst_edges: Vec::new(),
+            mst_cost: 0,
+        }
+    }
+
+    // Returns an estimate of the heap bytes held by the algorithm: the tree,
+    // the weights are never stored.
+    pub fn memory_footprint(&self) -> usize {
+        self.mst_edges.heap_bytes()
+    }
+
+    // Runs the algorithm and returns a set of edges representing the minimum
+    // spanning tree and its associated total cost.
+    // # Note: O(n^2) weight evaluations, O(n) memory.
+    pub fn run(mut self) -> Result<MstResult, GraphError> {
+        let weight = &self.weight;
+        let (_, cost) = dense_prim(
+            self.num_vertices,
+            |u, v| Some(weight(u.min(v), u.max(v))),
+            &mut self.mst_edges,
+            true,
+        );
+        self.mst_cost += cost;
+        MstResult::from_forest(self.num_vertices, self.mst_edges, self.mst_cost)
+    }
+
+    // Runs the algorithm and returns only the total cost of the minimum
+    // spanning tree, its edges are never stored.
+    pub fn run_cost(mut self) -> Result<Cost, GraphError> {
+        let weight = &self.weight;
+        let (count, cost) = dense_prim(
+            self.num_vertices,
+            |u, v| Some(weight(u.min(v), u.max(v))),
+            &mut self.mst_edges,
+            false,
+        );
+        self.mst_cost += cost;
+        check_spanning(self.num_vertices, count)?;
+        to_cost(self.mst_cost)
+    }
+}
+
+// Prim on the adjacency lists, keeping for every vertex outside the tree
+// its cheapest connection in a pairing heap lowered with decrease-key.
+// # Note: O(m + n log n) amortized, a spanning forest is grown if the
+// #       graph isn't connected.
+pub struct SparsePrim<'a, T, E = ()> {
+    graph: &'a GraphStars<T, E>,
+    tree: TreeBuffer,
+}
+
+impl<'a, T: Clone + Eq, E: Default> SparsePrim<'a, T, E> {
+    // Constructs the algorithm structures, the graph is borrowed
+    // since Prim only reads it.
+    pub fn new(graph: &'a GraphStars<T, E>) -> Self {
+        SparsePrim {
+            graph,
+            tree: TreeBuffer::default(),
+        }
+    }
+
+    // Grows a tree from every vertex not reached yet, returning the number
+    // of tree edges found. The edges themselves are only kept if
+    // `keep_edges` is set.
+    fn grow(&mut self, keep_edges: bool) -> usize {
+        let n = self.graph.num_vertices();
+        let mut count = 0;
+        let mut in_tree = vec![false; n];
+        // Heap entry of each vertex outside the tree, `edge.to` is the
+        // vertex and `edge.from` its neighbour in the tree.
+        let mut handles: Vec<Option<PairingHandle>> = vec![None; n];
+        let mut heap = PairingHeap::new();
+
+        for root in 0..n {
+            if in_tree[root] {
+                continue;
+            }
+            let mut next = Some(root);
+            while let Some(u) = next {
+                in_tree[u] = true;
+                for &edge in self.graph.star(u) {
+                    let v = edge.to;
+                    if in_tree[v] {
+                        continue;
+                    }
+                    match handles[v] {
+                        Some(handle) => {
+                            heap.decrease_key(handle, edge);
+                        }
+                        None => handles[v] = Some(heap.insert(edge)),
+                    }
+                }
+
+                next = heap.pop_min().map(|edge: Edge| {
+                    self.tree.add(edge, keep_edges);
+                    count += 1;
+                    edge.to
+                });
+            }
+        }
+        count
+    }
+
+    // Runs the algorithm on another graph, reusing the buffer of the tree
+    // edges, see `Reusable`.
+    pub fn run_on(&mut self, graph: &'a GraphStars<T, E>) -> Result<MstResult, GraphError> {
+        self.reset_with(graph);
+        self.grow(true);
+        self.tree.result(self.graph.num_vertices())
+    }
+
+    // Returns an estimate of the heap bytes held by the algorithm: the tree,
+    // the borrowed graph isn't counted.
+    pub fn memory_footprint(&self) -> usize {
+        self.tree.memory_footprint()
+    }
+
+    // Runs the algorithm and returns a set of edges representing the minimum
+    // spanning tree and its associated total cost, consuming the algorithm
+    // (see `Reusable`).
+    pub fn run(mut self) -> Result<MstResult, GraphError> {
+        self.grow(true);
+        self.tree.into_result(self.graph.num_vertices())
+    }
+
+    // Runs the algorithm and returns only the total cost of the minimum
+    // spanning tree, its edges are never stored.
+    pub fn run_cost(mut self) -> Result<Cost, GraphError> {
+        let count = self.grow(false);
+        self.tree.total(self.graph.num_vertices(), count)
+    }
+}
+
+impl<'a, T: Clone + Eq, E: Default> Reusable<&'a GraphStars<T, E>> for SparsePrim<'a, T, E> {
+    fn reset_with(&mut self, graph: &'a GraphStars<T, E>) {
+        self.graph = graph;
+        self.tree.clear();
+    }
+}
+
+// Grows Prim trees from vertex 0 and then from the first vertex left out,
+// scanning every pair with `cost`, pushing the tree edges to `mst_edges` if
+// `keep_edges` is set and returning their number and total cost.
+fn dense_prim<C>(n: usize, cost: C, mst_edges: &mut Vec<Edge>, keep_edges: bool) -> (usize, CostSum)
+where
+    C: Fn(VertexId, VertexId) -> Option<Cost>,
+{
+    let mut count = 0;
+    let mut total: CostSum = 0;
+    let mut in_tree = vec![false; n];
+    // Cheapest known connection of each vertex to the tree, compared
+    // by `Edge::key`.
+    let mut best: Vec<Option<Edge>> = vec![None; n];
+
+    // Vertices before `restart` are all in a tree.
+    let mut restart = 0;
+    let mut next = if n > 0 { Some(0) } else { None };
+    while let Some(u) = next {
+        in_tree[u] = true;
+        if let Some(edge) = best[u] {
+            if keep_edges {
+                mst_edges.push(edge);
+            }
+            total += edge.weight as CostSum;
+            count += 1;
+        }
+
+        next = None;
+        let mut next_edge: Option<Edge> = None;
+        for v in 0..n {
+            if in_tree[v] {
+                continue;
+            }
+            if let Some(weight) = cost(u, v) {
+                let edge = Edge::new(u, v, weight);
+                if best[v].is_none_or(|b| edge < b) {
+                    best[v] = Some(edge);
+                }
+            }
+            if let Some(edge) = best[v]
+                && next_edge.is_none_or(|e| edge < e)
+            {
+                next = Some(v);
+                next_edge = Some(edge);
+            }
+        }
+        if next.is_none() {
+            // Nothing reachable is left, start a tree in the next component.
+            while restart < n && in_tree[restart] {
+                restart += 1;
+            }
+            next = (restart < n).then_some(restart);
+        }
+    }
+    (count, total)
+}
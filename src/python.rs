@@ -0,0 +1,122 @@
+// # Python bindings
+//
+// PyO3 module exposing graph construction and the MST variants to Python,
+// enabled by the `python` feature.
+use crate::algorithm::Algorithm;
+use crate::constants::{Cost, VertexId};
+use crate::error::GraphError;
+use crate::graph::{Edge, Graph};
+use crate::graph_stars::GraphStars;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+// Edge as exposed to Python: `(from, to, weight)`.
+type EdgeTriple = (VertexId, VertexId, Cost);
+
+impl From<GraphError> for PyErr {
+    fn from(err: GraphError) -> PyErr {
+        PyValueError::new_err(err.to_string())
+    }
+}
+
+// Undirected weighted graph with vertices identified by 0..n.
+#[pyclass(name = "Graph")]
+#[derive(Clone, Default)]
+pub struct PyGraph {
+    num_vertices: usize,
+    edges: Vec<Edge>,
+}
+
+#[pymethods]
+impl PyGraph {
+    #[new]
+    #[pyo3(signature = (num_vertices = 0))]
+    fn new(num_vertices: usize) -> Self {
+        PyGraph {
+            num_vertices,
+            edges: Vec::new(),
+        }
+    }
+
+    // Generates a G(n, p) graph with uniform costs in [min_cost, max_cost].
+    #[staticmethod]
+    #[pyo3(signature = (num_vertices, p, min_cost, max_cost, seed = 0))]
+    fn new_random(
+        num_vertices: usize,
+        p: f64,
+        min_cost: Cost,
+        max_cost: Cost,
+        seed: u64,
+    ) -> PyResult<Self> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let graph = GraphStars::new_random(0..num_vertices, p, min_cost, max_cost, true, &mut rng)?;
+        Ok(PyGraph {
+            num_vertices,
+            edges: graph.all_edges(),
+        })
+    }
+
+    // Adds a vertex and returns its id.
+    fn add_vertex(&mut self) -> VertexId {
+        self.num_vertices += 1;
+        self.num_vertices - 1
+    }
+
+    // Adds an undirected weighted edge.
+    fn add_edge(&mut self, from: VertexId, to: VertexId, cost: Cost) -> PyResult<()> {
+        let vertex = from.max(to);
+        if vertex >= self.num_vertices {
+            return Err(GraphError::VertexOutOfBounds {
+                vertex,
+                num_vertices: self.num_vertices,
+            }
+            .into());
+        }
+        self.edges.push(Edge::new(from, to, cost));
+        Ok(())
+    }
+
+    #[getter]
+    fn num_vertices(&self) -> usize {
+        self.num_vertices
+    }
+
+    #[getter]
+    fn num_edges(&self) -> usize {
+        self.edges.len()
+    }
+}
+
+// Computes the minimum spanning tree with the named variant and returns
+// `(edges, cost)` where `edges` is a list of `(from, to, weight)` triples,
+// ready for `numpy.array(edges)`.
+#[pyfunction]
+#[pyo3(signature = (graph, algorithm = "filter_kruskal", seed = 0))]
+fn minimum_spanning_tree(
+    graph: &PyGraph,
+    algorithm: &str,
+    seed: u64,
+) -> PyResult<(Vec<EdgeTriple>, Cost)> {
+    let algorithm: Algorithm = algorithm.parse()?;
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mst = algorithm.run_on_edges(graph.num_vertices, &graph.edges, &mut rng)?;
+    let edges = mst
+        .edges
+        .iter()
+        .map(|edge| (edge.from, edge.to, edge.weight))
+        .collect();
+    Ok((edges, mst.cost))
+}
+
+#[pymodule]
+fn mst_kruskal_variants(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyGraph>()?;
+    module.add_function(wrap_pyfunction!(minimum_spanning_tree, module)?)?;
+    module.add(
+        "ALGORITHMS",
+        Algorithm::ALL.map(|algorithm| algorithm.name()).to_vec(),
+    )?;
+    Ok(())
+}
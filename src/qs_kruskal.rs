@@ -2,23 +2,35 @@
 //
 // Implementation of the Kruskal algorithm using a quickselect approach.
 use crate::constants::*;
+use crate::error::GraphError;
 use crate::graph::{Edge, Graph};
 use crate::graph_matrix::GraphMatrix;
+use crate::memory::HeapBytes;
+use crate::mst_result::MstResult;
+use crate::partition::{partition, partition_three_way, PivotStrategy};
+use crate::reuse::{Reusable, TreeBuffer};
+use crate::rng::SplitMix64;
 use crate::union_find::UnionFind;
-use rand::Rng;
+use rand_core::Rng;
 
 pub struct QuickSortKruskal {
     num_vertices: usize,
     num_edges: usize,
     edges: Vec<Edge>,
+    pivot: PivotStrategy,
+    three_way: bool,
     union_find: UnionFind,
-    mst_edges: Vec<Edge>,
-    mst_cost: Cost,
+    tree: TreeBuffer,
 }
 
 impl QuickSortKruskal {
-    // Constructs the algorithm structures
+    // Constructs the algorithm structures with random pivots.
     pub fn new(graph: &GraphMatrix<usize>) -> Self {
+        Self::with_pivot(graph, PivotStrategy::Random)
+    }
+
+    // Constructs the algorithm structures with the given pivot strategy.
+    pub fn with_pivot(graph: &GraphMatrix<usize>, pivot: PivotStrategy) -> Self {
         let num_vertices = graph.num_vertices();
         let edges = graph.all_edges();
         let num_edges = edges.len();
@@ -26,82 +38,130 @@ impl QuickSortKruskal {
             num_vertices,
             num_edges,
             edges,
+            pivot,
+            three_way: false,
             union_find: UnionFind::new(num_vertices),
-            mst_edges: Vec::new(),
-            mst_cost: 0,
+            tree: TreeBuffer::default(),
         }
     }
 
-    // Runs the algorithm and returns a set of edges representing the minimum
-    // spanning tree and its associated total cost.
-    pub fn run<R: Rng>(&mut self, rng: &mut R) -> (Vec<Edge>, Cost) {
+    // Sets whether ranges are split in three around the pivot's weight, see
+    // `FilterKruskal::set_three_way`.
+    pub fn set_three_way(&mut self, three_way: bool) {
+        self.three_way = three_way;
+    }
+
+    // Partitions the edges recursively, returning the number of tree edges
+    // found. The edges themselves are only kept if `keep_edges` is set.
+    fn grow<R: Rng + ?Sized>(&mut self, rng: &mut R, keep_edges: bool) -> usize {
         if self.num_edges == 0 {
-            return (Vec::new(), 0);
+            return 0;
         }
 
         let mut count = 0;
         let m: usize = self.num_edges;
-        // Stack stores inclusive ranges (start, end)
-        let mut mem: Vec<(usize, usize)> = Vec::new();
+        // Stack stores inclusive ranges (start, end), flagged if they may
+        // hold several weights
+        let mut mem: Vec<(usize, usize, bool)> = Vec::new();
 
-        mem.push((0, m - 1));
+        mem.push((0, m - 1, self.three_way));
 
-        while let Some((p, q)) = mem.pop() {
-            if count >= self.num_vertices - 1 {
+        while let Some((p, q, split_by_weight)) = mem.pop() {
+            if count + 1 >= self.num_vertices {
                 break;
             }
 
             if p == q {
                 let edge = self.edges[p];
                 if self.union_find.union(edge.from, edge.to) {
-                    self.mst_edges.push(edge);
-                    self.mst_cost += edge.weight;
+                    self.tree.add(edge, keep_edges);
                     count += 1;
                 }
                 continue;
             }
 
-            let mut e_plus = q;
-            if p < q {
-                // Randomize pivot to avoid worst-case O(N^2)
-                let pivot_idx = rng.random_range(p..=q);
-                self.edges.swap(p, pivot_idx);
-
-                let mut e_minus = p;
-
-                // Partition around edges[p]
-                while e_minus <= e_plus {
-                    while self.edges[e_plus].weight > self.edges[p].weight {
-                        if e_plus == 0 {
-                            break;
-                        }
-                        e_plus -= 1;
-                    }
-                    while (e_minus <= e_plus)
-                        && (self.edges[e_minus].weight <= self.edges[p].weight)
-                    {
-                        e_minus += 1;
+            // Move the chosen pivot in front and partition around it
+            let pivot_idx = self.pivot.select(&self.edges, p, q, rng);
+            self.edges.swap(p, pivot_idx);
+
+            // Split in three by weight while the range holds several, see
+            // `filter_kruskal`.
+            if split_by_weight {
+                let (lt, gt) = partition_three_way(&mut self.edges, p, q);
+                if lt > p || gt < q {
+                    if gt < q {
+                        mem.push((gt + 1, q, true));
                     }
-                    if e_minus < e_plus {
-                        self.edges.swap(e_minus, e_plus);
-                        e_minus += 1;
-                        e_plus = e_plus.saturating_sub(1);
+                    mem.push((lt, gt, false));
+                    if lt > p {
+                        mem.push((p, lt - 1, true));
                     }
+                    continue;
                 }
-                self.edges.swap(p, e_plus);
+            }
+            let e_plus = partition(&mut self.edges, p, q);
 
-                if e_plus < q {
-                    mem.push((e_plus + 1, q));
-                }
+            if e_plus < q {
+                mem.push((e_plus + 1, q, false));
+            }
 
-                mem.push((e_plus, e_plus));
+            mem.push((e_plus, e_plus, false));
 
-                if e_plus > p {
-                    mem.push((p, e_plus - 1));
-                }
+            if e_plus > p {
+                mem.push((p, e_plus - 1, false));
             }
         }
 
-        (self.mst_edges.clone(), self.mst_cost)
+        count
+    }
+
+    // Runs the algorithm on another graph, reusing the buffers of the
+    // previous run, see `Reusable`.
+    pub fn run_on<R: Rng + ?Sized>(
+        &mut self,
+        graph: &GraphMatrix<usize>,
+        rng: &mut R,
+    ) -> Result<MstResult, GraphError> {
+        self.reset_with(graph);
+        self.grow(rng, true);
+        self.tree.result(self.num_vertices)
+    }
+
+    // Returns an estimate of the heap bytes held by the algorithm: the edge
+    // buffer, the union-find and the tree.
+    pub fn memory_footprint(&self) -> usize {
+        self.edges.heap_bytes() + self.union_find.memory_footprint() + self.tree.memory_footprint()
+    }
+
+    // Runs the algorithm and returns a set of edges representing the minimum
+    // spanning tree and its associated total cost, consuming the algorithm
+    // (see `Reusable`).
+    pub fn run<R: Rng + ?Sized>(mut self, rng: &mut R) -> Result<MstResult, GraphError> {
+        self.grow(rng, true);
+        self.tree.into_result(self.num_vertices)
+    }
+
+    // Runs the algorithm and returns only the total cost of the minimum
+    // spanning tree, its edges are never stored.
+    pub fn run_cost<R: Rng + ?Sized>(mut self, rng: &mut R) -> Result<Cost, GraphError> {
+        let count = self.grow(rng, false);
+        self.tree.total(self.num_vertices, count)
+    }
+
+    // Same as `run` with a `SplitMix64` seeded with `seed`, for reproducible
+    // runs without picking a generator.
+    pub fn run_seeded(self, seed: u64) -> Result<MstResult, GraphError> {
+        self.run(&mut SplitMix64::new(seed))
+    }
+}
+
+impl Reusable<&GraphMatrix<usize>> for QuickSortKruskal {
+    fn reset_with(&mut self, graph: &GraphMatrix<usize>) {
+        self.num_vertices = graph.num_vertices();
+        self.edges.clear();
+        self.edges.extend_from_slice(graph.cached_edges());
+        self.num_edges = self.edges.len();
+        self.union_find.reset(self.num_vertices);
+        self.tree.clear();
     }
 }
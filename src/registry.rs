@@ -0,0 +1,155 @@
+// # Registry
+//
+// Name to variant lookup for the CLI and config-driven pipelines, which pick
+// the algorithm from a string at runtime. The built-in variants are
+// registered under their `Algorithm::name` and a short alias, others can be
+// added with `register` or the `register_algorithms!` macro.
+use crate::algorithm::Algorithm;
+use crate::error::GraphError;
+use crate::graph::Edge;
+use crate::mst_result::MstResult;
+use rand_core::Rng;
+use std::collections::BTreeMap;
+
+// A variant runnable on a plain edge list, see `Algorithm::run_on_edges`.
+// # Note: Implemented by `Algorithm` and by closures with the same
+// #       signature as `run`.
+pub trait MstAlgorithm {
+    fn run(
+        &self,
+        num_vertices: usize,
+        edges: &[Edge],
+        rng: &mut dyn Rng,
+    ) -> Result<MstResult, GraphError>;
+}
+
+impl MstAlgorithm for Algorithm {
+    fn run(
+        &self,
+        num_vertices: usize,
+        edges: &[Edge],
+        rng: &mut dyn Rng,
+    ) -> Result<MstResult, GraphError> {
+        self.run_on_edges(num_vertices, edges, rng)
+    }
+}
+
+impl<F> MstAlgorithm for F
+where
+    F: Fn(usize, &[Edge], &mut dyn Rng) -> Result<MstResult, GraphError>,
+{
+    fn run(
+        &self,
+        num_vertices: usize,
+        edges: &[Edge],
+        rng: &mut dyn Rng,
+    ) -> Result<MstResult, GraphError> {
+        self(num_vertices, edges, rng)
+    }
+}
+
+// Builds a fresh instance of a registered variant.
+pub type Factory = Box<dyn Fn() -> Box<dyn MstAlgorithm>>;
+
+// Registers each `name => variant` pair, the expression being evaluated
+// anew by every `create`.
+// # Example: register_algorithms!(registry, "fk" => Algorithm::FilterKruskal);
+#[macro_export]
+macro_rules! register_algorithms {
+    ($registry:expr, $($name:expr => $variant:expr),+ $(,)?) => {
+        $(
+            $registry.register($name, move || -> Box<dyn $crate::registry::MstAlgorithm> {
+                Box::new($variant)
+            });
+        )+
+    };
+}
+
+// Short aliases of the built-in variants whose full name isn't short
+// already, e.g. "kruskal" or "chazelle".
+const ALIASES: [(&str, Algorithm); 10] = [
+    ("qs", Algorithm::QuickSortKruskal),
+    ("filter", Algorithm::FilterKruskal),
+    ("skewed", Algorithm::SkewedFilterKruskal),
+    ("sqsk", Algorithm::StarQuickSortKruskal),
+    ("bucket", Algorithm::BucketKruskal),
+    ("radix", Algorithm::RadixKruskal),
+    ("prim", Algorithm::DensePrim),
+    ("boruvka", Algorithm::BoruvkaFilterKruskal),
+    ("filter_plus", Algorithm::FilterKruskalPlus),
+    ("sort", Algorithm::SortKruskal),
+];
+
+// Factories by name, in alphabetical order.
+pub struct Registry {
+    factories: BTreeMap<String, Factory>,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Registry {
+    // Constructs a registry holding the built-in variants under their full
+    // names and their aliases.
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        for algorithm in Algorithm::ALL {
+            register_algorithms!(registry, algorithm.name() => algorithm);
+        }
+        for (alias, algorithm) in ALIASES {
+            register_algorithms!(registry, alias => algorithm);
+        }
+        registry
+    }
+
+    // Constructs a registry without any variant.
+    pub fn empty() -> Self {
+        Registry {
+            factories: BTreeMap::new(),
+        }
+    }
+
+    // Registers a factory under `name`, replacing any previous one, and
+    // returns true if the name was new.
+    pub fn register<F>(&mut self, name: impl Into<String>, factory: F) -> bool
+    where
+        F: Fn() -> Box<dyn MstAlgorithm> + 'static,
+    {
+        self.factories
+            .insert(name.into(), Box::new(factory))
+            .is_none()
+    }
+
+    // Returns true if a variant is registered under `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.factories.contains_key(name)
+    }
+
+    // Returns the registered names in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.factories.keys().map(String::as_str)
+    }
+
+    // Builds the variant registered under `name`.
+    // # Example: let algo = Registry::new().create("filter")?;
+    pub fn create(&self, name: &str) -> Result<Box<dyn MstAlgorithm>, GraphError> {
+        self.factories
+            .get(name)
+            .map(|factory| factory())
+            .ok_or_else(|| GraphError::UnknownAlgorithm(name.to_string()))
+    }
+
+    // Builds the variant registered under `name` and runs it on the edges.
+    pub fn run(
+        &self,
+        name: &str,
+        num_vertices: usize,
+        edges: &[Edge],
+        rng: &mut dyn Rng,
+    ) -> Result<MstResult, GraphError> {
+        self.create(name)?.run(num_vertices, edges, rng)
+    }
+}
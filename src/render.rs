@@ -0,0 +1,155 @@
+// # Render
+//
+// Zero-dependency SVG drawing of a graph and, optionally, its spanning
+// tree: vertices labelled with their ids, edges with their weights, tree
+// edges highlighted. Meant for small graphs.
+use crate::constants::{Cost, VertexId};
+use crate::graph::Graph;
+use crate::mst_result::MstResult;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::fmt::Write;
+
+// Side of the square drawing, in pixels.
+const SIZE: f64 = 600.0;
+const MARGIN: f64 = 40.0;
+const RADIUS: f64 = 12.0;
+
+// Placement of the vertices.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Layout {
+    // Vertices evenly spaced on a circle, in id order.
+    Circular,
+    // Fruchterman-Reingold spring embedding started from the circular
+    // layout, deterministic for a given graph.
+    ForceDirected { iterations: usize },
+}
+
+// Returns the position of each vertex in the unit square.
+fn positions(
+    num_vertices: usize,
+    edges: &[(VertexId, VertexId)],
+    layout: Layout,
+) -> Vec<(f64, f64)> {
+    let n = num_vertices;
+    let mut points: Vec<(f64, f64)> = (0..n)
+        .map(|v| {
+            let angle = 2.0 * PI * v as f64 / n as f64;
+            (0.5 + 0.5 * angle.cos(), 0.5 + 0.5 * angle.sin())
+        })
+        .collect();
+    let Layout::ForceDirected { iterations } = layout else {
+        return points;
+    };
+    if n < 2 {
+        return points;
+    }
+
+    // Ideal distance between vertices, the temperature caps the moves and
+    // cools down linearly.
+    let k = (1.0 / n as f64).sqrt();
+    for iteration in 0..iterations {
+        let temperature = 0.1 * (1.0 - iteration as f64 / iterations as f64);
+        let mut moves = vec![(0.0, 0.0); n];
+        for u in 0..n {
+            for v in u + 1..n {
+                let (dx, dy) = (points[u].0 - points[v].0, points[u].1 - points[v].1);
+                let distance = (dx * dx + dy * dy).sqrt().max(1e-6);
+                let force = k * k / distance;
+                moves[u].0 += dx / distance * force;
+                moves[u].1 += dy / distance * force;
+                moves[v].0 -= dx / distance * force;
+                moves[v].1 -= dy / distance * force;
+            }
+        }
+        for &(u, v) in edges {
+            let (dx, dy) = (points[u].0 - points[v].0, points[u].1 - points[v].1);
+            let distance = (dx * dx + dy * dy).sqrt().max(1e-6);
+            let force = distance * distance / k;
+            moves[u].0 -= dx / distance * force;
+            moves[u].1 -= dy / distance * force;
+            moves[v].0 += dx / distance * force;
+            moves[v].1 += dy / distance * force;
+        }
+        for (point, (mx, my)) in points.iter_mut().zip(moves) {
+            let length = (mx * mx + my * my).sqrt().max(1e-6);
+            let step = length.min(temperature);
+            point.0 = (point.0 + mx / length * step).clamp(0.0, 1.0);
+            point.1 = (point.1 + my / length * step).clamp(0.0, 1.0);
+        }
+    }
+    points
+}
+
+// Draws `graph` as an SVG document, highlighting the edges of `tree`.
+// # Example: std::fs::write("mst.svg", render::svg(&graph, Some(&mst), Layout::Circular))?;
+// # Note: Tree edges match graph edges by weight and endpoints, whatever
+// #       their orientation. The force-directed layout is O(n^2) per
+// #       iteration.
+pub fn svg<T, G: Graph<T>>(graph: &G, tree: Option<&MstResult>, layout: Layout) -> String {
+    let n = graph.num_vertices();
+    let edges = graph.all_edges();
+    let pairs: Vec<(VertexId, VertexId)> = edges.iter().map(|e| (e.from, e.to)).collect();
+    let scale = SIZE - 2.0 * MARGIN;
+    let points: Vec<(f64, f64)> = positions(n, &pairs, layout)
+        .into_iter()
+        .map(|(x, y)| (MARGIN + x * scale, MARGIN + y * scale))
+        .collect();
+
+    let mut in_tree: HashMap<(Cost, VertexId, VertexId), usize> = HashMap::new();
+    for edge in tree.map_or(&[][..], |t| &t.edges) {
+        *in_tree.entry(edge.key()).or_default() += 1;
+    }
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{0}\" viewBox=\"0 0 {0} {0}\">",
+        SIZE
+    );
+    let _ = writeln!(svg, "<rect width=\"100%\" height=\"100%\" fill=\"white\"/>");
+    for edge in edges.iter().filter(|e| e.from != e.to) {
+        let highlighted = match in_tree.get_mut(&edge.key()) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            }
+            _ => false,
+        };
+        let (stroke, width) = if highlighted {
+            ("crimson", 3)
+        } else {
+            ("#999", 1)
+        };
+        let ((x1, y1), (x2, y2)) = (points[edge.from], points[edge.to]);
+        let _ = writeln!(
+            svg,
+            "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"{}\" stroke-width=\"{}\"/>",
+            x1, y1, x2, y2, stroke, width
+        );
+        let _ = writeln!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"11\" fill=\"{}\" text-anchor=\"middle\">{}</text>",
+            (x1 + x2) / 2.0,
+            (y1 + y2) / 2.0 - 3.0,
+            stroke,
+            edge.weight
+        );
+    }
+    for (v, (x, y)) in points.iter().enumerate() {
+        let _ = writeln!(
+            svg,
+            "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{}\" fill=\"steelblue\"/>",
+            x, y, RADIUS
+        );
+        let _ = writeln!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"11\" fill=\"white\" text-anchor=\"middle\">{}</text>",
+            x,
+            y + 4.0,
+            v
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
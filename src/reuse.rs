@@ -0,0 +1,68 @@
+// # Reuse
+//
+// The run contract shared by the variants. `run` and `run_cost` consume the
+// variant, since a run spends its state (the heap drained, the union-find
+// merged, the edges permuted), so running an instance twice is a compile
+// error rather than a wrong tree. To solve many graphs in turn without
+// allocating, `reset_with` loads the next graph into the buffers of the
+// previous run (edges, heaps, union-find and tree, cleared but keeping their
+// capacity) and `run_on` resets and runs in one call, the settings such as
+// the pivot strategy being kept.
+use crate::constants::{Cost, CostSum};
+use crate::error::GraphError;
+use crate::graph::Edge;
+use crate::memory::HeapBytes;
+use crate::mst_result::{check_spanning, to_cost, MstResult};
+
+// A variant that can be loaded with another graph `G`, usually a reference.
+// # Example: for g in &graphs { algo.reset_with(g); ... }
+pub trait Reusable<G> {
+    // Loads `graph` into the buffers of the previous run, see the module.
+    fn reset_with(&mut self, graph: G);
+}
+
+// Tree edges and cost found by a run, kept by the variant so that the edge
+// buffer is reused by the next run.
+#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct TreeBuffer {
+    pub(crate) edges: Vec<Edge>,
+    pub(crate) cost: CostSum,
+}
+
+impl TreeBuffer {
+    // Adds a tree edge to the cost, storing it only if `keep_edges` is set.
+    pub(crate) fn add(&mut self, edge: Edge, keep_edges: bool) {
+        if keep_edges {
+            self.edges.push(edge);
+        }
+        self.cost += edge.weight as CostSum;
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.edges.clear();
+        self.cost = 0;
+    }
+
+    // Returns the tree of a run on `num_vertices` vertices, its edges copied
+    // so that the buffer stays with the variant.
+    pub(crate) fn result(&self, num_vertices: usize) -> Result<MstResult, GraphError> {
+        MstResult::from_forest(num_vertices, self.edges.clone(), self.cost)
+    }
+
+    // Same as `result` handing the edges over, for consuming runs.
+    pub(crate) fn into_result(self, num_vertices: usize) -> Result<MstResult, GraphError> {
+        MstResult::from_forest(num_vertices, self.edges, self.cost)
+    }
+
+    // Returns the total cost of a run that found `count` tree edges, failing
+    // if they don't span the `num_vertices` vertices.
+    pub(crate) fn total(&self, num_vertices: usize, count: usize) -> Result<Cost, GraphError> {
+        check_spanning(num_vertices, count)?;
+        to_cost(self.cost)
+    }
+
+    pub(crate) fn memory_footprint(&self) -> usize {
+        self.edges.heap_bytes()
+    }
+}
@@ -0,0 +1,79 @@
+// # Random
+//
+// The draws the randomized variants need, on top of the `rand_core::Rng`
+// trait alone, and a small seeded generator for builds without the `rand`
+// feature.
+use rand_core::{Infallible, Rng, SeedableRng, TryRng};
+
+// SplitMix64, a 64-bit state generator passing BigCrush, enough for pivots
+// and samples but not for anything cryptographic.
+// # Example: QuickSortKruskal::new(&g).run(&mut SplitMix64::new(42));
+// # Note: The stream differs from `StdRng` for the same seed, the trees
+// #       don't since the variants break ties by edge key.
+#[derive(Clone, Debug)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+}
+
+impl TryRng for SplitMix64 {
+    type Error = Infallible;
+
+    fn try_next_u32(&mut self) -> Result<u32, Infallible> {
+        Ok((self.try_next_u64()? >> 32) as u32)
+    }
+
+    fn try_next_u64(&mut self) -> Result<u64, Infallible> {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        Ok(z ^ (z >> 31))
+    }
+
+    fn try_fill_bytes(&mut self, dst: &mut [u8]) -> Result<(), Infallible> {
+        rand_core::utils::fill_bytes_via_next_word(dst, || self.try_next_u64())
+    }
+}
+
+impl SeedableRng for SplitMix64 {
+    type Seed = [u8; 8];
+
+    fn from_seed(seed: [u8; 8]) -> Self {
+        Self::new(u64::from_le_bytes(seed))
+    }
+
+    // Uses the seed as the state, so `seed_from_u64(s)` equals `new(s)`.
+    fn seed_from_u64(state: u64) -> Self {
+        Self::new(state)
+    }
+}
+
+// Uniform index in `0..n`, by multiply-shift with rejection (Lemire).
+// # Panic: if n == 0
+pub(crate) fn below<R: Rng + ?Sized>(rng: &mut R, n: usize) -> usize {
+    assert!(n > 0, "empty range");
+    let n = n as u64;
+    let threshold = n.wrapping_neg() % n;
+    loop {
+        let product = (rng.next_u64() as u128) * (n as u128);
+        if (product as u64) >= threshold {
+            return (product >> 64) as usize;
+        }
+    }
+}
+
+// Uniform index in the inclusive range `p..=q`.
+pub(crate) fn between<R: Rng + ?Sized>(rng: &mut R, p: usize, q: usize) -> usize {
+    p + below(rng, q - p + 1)
+}
+
+// Uniform float in [0, 1) from the top 53 bits.
+pub(crate) fn unit<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    (rng.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+}
@@ -0,0 +1,203 @@
+// # Rooted Tree
+//
+// A spanning tree hanging from a root, as parent and depth arrays, the form
+// needed by path queries and tree dynamic programming.
+use crate::constants::{Cost, VertexId};
+use crate::graph::Edge;
+
+#[derive(Clone, Debug)]
+pub struct RootedTree {
+    root: VertexId,
+    // Parent of each vertex with the weight of the edge to it, None for
+    // the root.
+    parents: Vec<Option<(VertexId, Cost)>>,
+    depths: Vec<usize>,
+    // Vertices in BFS order, each after its parent.
+    order: Vec<VertexId>,
+    // Children of vertex v in `children[child_offsets[v]..child_offsets[v + 1]]`.
+    children: Vec<VertexId>,
+    child_offsets: Vec<usize>,
+}
+
+impl RootedTree {
+    // Roots the spanning tree made of `edges` on `num_vertices` vertices at
+    // `root` with a BFS.
+    // # Panic: `root` must be a vertex and `edges` a spanning tree.
+    pub fn new(num_vertices: usize, edges: &[Edge], root: VertexId) -> Self {
+        let n = num_vertices;
+        assert!(root < n, "root {root} out of bounds for {n} vertices");
+        let mut adjacency = vec![Vec::new(); n];
+        for edge in edges {
+            adjacency[edge.from].push((edge.to, edge.weight));
+            adjacency[edge.to].push((edge.from, edge.weight));
+        }
+
+        let mut parents = vec![None; n];
+        let mut depths = vec![0; n];
+        let mut visited = vec![false; n];
+        let mut order = Vec::with_capacity(n);
+        visited[root] = true;
+        order.push(root);
+        let mut head = 0;
+        while let Some(&u) = order.get(head) {
+            head += 1;
+            for &(v, weight) in &adjacency[u] {
+                if !visited[v] {
+                    visited[v] = true;
+                    parents[v] = Some((u, weight));
+                    depths[v] = depths[u] + 1;
+                    order.push(v);
+                }
+            }
+        }
+        assert!(order.len() == n, "the edges don't span the vertices");
+
+        // Children grouped by parent, each group in BFS order.
+        let mut child_offsets = vec![0; n + 1];
+        for &(parent, _) in parents.iter().flatten() {
+            child_offsets[parent + 1] += 1;
+        }
+        for v in 0..n {
+            child_offsets[v + 1] += child_offsets[v];
+        }
+        let mut next = child_offsets.clone();
+        let mut children = vec![0; n.saturating_sub(1)];
+        for &v in &order[1..] {
+            let (parent, _) = parents[v].expect("only the root has no parent");
+            children[next[parent]] = v;
+            next[parent] += 1;
+        }
+
+        RootedTree {
+            root,
+            parents,
+            depths,
+            order,
+            children,
+            child_offsets,
+        }
+    }
+
+    pub fn root(&self) -> VertexId {
+        self.root
+    }
+
+    // Returns the total number of vertices.
+    pub fn num_vertices(&self) -> usize {
+        self.order.len()
+    }
+
+    // Returns the parent of a vertex, None for the root.
+    pub fn parent(&self, v: VertexId) -> Option<VertexId> {
+        self.parents[v].map(|(parent, _)| parent)
+    }
+
+    // Returns the weight of the edge from a vertex to its parent, None for
+    // the root.
+    pub fn parent_weight(&self, v: VertexId) -> Option<Cost> {
+        self.parents[v].map(|(_, weight)| weight)
+    }
+
+    // Returns the number of edges between a vertex and the root.
+    pub fn depth(&self, v: VertexId) -> usize {
+        self.depths[v]
+    }
+
+    // Returns the parent array, None for the root.
+    pub fn parents(&self) -> Vec<Option<VertexId>> {
+        self.parents
+            .iter()
+            .map(|p| p.map(|(parent, _)| parent))
+            .collect()
+    }
+
+    // Returns the depth array without a copy.
+    pub fn depths(&self) -> &[usize] {
+        &self.depths
+    }
+
+    // Returns the vertices in BFS order, each after its parent.
+    pub fn bfs_order(&self) -> &[VertexId] {
+        &self.order
+    }
+
+    // Returns the children of a vertex without a copy.
+    pub fn children(&self, v: VertexId) -> &[VertexId] {
+        &self.children[self.child_offsets[v]..self.child_offsets[v + 1]]
+    }
+
+    // Returns the vertices in depth-first preorder, each before its
+    // children.
+    pub fn preorder(&self) -> impl Iterator<Item = VertexId> + '_ {
+        let mut stack = vec![self.root];
+        std::iter::from_fn(move || {
+            let v = stack.pop()?;
+            stack.extend(self.children(v).iter().rev());
+            Some(v)
+        })
+    }
+
+    // Returns the vertices in depth-first postorder, each after its
+    // children.
+    pub fn postorder(&self) -> impl Iterator<Item = VertexId> + '_ {
+        // Vertices with the number of their children already visited.
+        let mut stack = vec![(self.root, 0)];
+        std::iter::from_fn(move || {
+            while let Some((v, visited)) = stack.pop() {
+                match self.children(v).get(visited) {
+                    Some(&child) => {
+                        stack.push((v, visited + 1));
+                        stack.push((child, 0));
+                    }
+                    None => return Some(v),
+                }
+            }
+            None
+        })
+    }
+
+    // Returns the largest depth, 0 for a single vertex.
+    pub fn height(&self) -> usize {
+        self.order.last().map_or(0, |&v| self.depths[v])
+    }
+
+    // Returns the number of vertices in the subtree of each vertex.
+    pub fn subtree_sizes(&self) -> Vec<usize> {
+        let mut sizes = vec![1; self.num_vertices()];
+        for &v in self.order.iter().rev() {
+            if let Some(parent) = self.parent(v) {
+                sizes[parent] += sizes[v];
+            }
+        }
+        sizes
+    }
+
+    // Returns the total weight of the heaviest path between two vertices
+    // and its endpoints.
+    // # Note: O(n) combining the two deepest weighted branches below each
+    // #       vertex.
+    pub fn diameter(&self) -> (Cost, VertexId, VertexId) {
+        // Heaviest path going down from each vertex and where it ends.
+        let mut down: Vec<(Cost, VertexId)> = (0..self.num_vertices()).map(|v| (0, v)).collect();
+        let mut best = (0, self.root, self.root);
+        for &v in self.order.iter().rev() {
+            let mut first = (0, v);
+            let mut second = (0, v);
+            for &child in self.children(v) {
+                let weight = self.parent_weight(child).unwrap_or(0);
+                let branch = (down[child].0 + weight, down[child].1);
+                if branch.0 > first.0 {
+                    second = first;
+                    first = branch;
+                } else if branch.0 > second.0 {
+                    second = branch;
+                }
+            }
+            down[v] = first;
+            if first.0 + second.0 > best.0 {
+                best = (first.0 + second.0, first.1, second.1);
+            }
+        }
+        best
+    }
+}
@@ -0,0 +1,78 @@
+// # Sensitivity
+//
+// How much the minimum spanning trees depend on each edge of the graph.
+use crate::connectivity::lowlink;
+use crate::constants::VertexId;
+use crate::graph::{Edge, Graph};
+use crate::union_find::UnionFind;
+use std::collections::HashMap;
+
+// Role of an edge among all the minimum spanning trees (forests) of a graph.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum EdgeClass {
+    // In every minimum spanning tree.
+    Critical,
+    // In some minimum spanning trees but not all.
+    PseudoCritical,
+    // In no minimum spanning tree.
+    Never,
+}
+
+// Classifies every edge of the graph, in the order of `all_edges`.
+// # Note: O(m log m). Edges of a weight class that join the same component
+// #       of the lighter edges are never taken, the remaining ones are
+// #       critical if they are bridges of the class contracted on those
+// #       components and pseudo-critical otherwise. Self-loops are never
+// #       taken, parallel edges of the same weight are pseudo-critical.
+pub fn classify_edges<T, G: Graph<T>>(graph: &G) -> Vec<(Edge, EdgeClass)> {
+    let edges = graph.all_edges();
+    let mut classes = vec![EdgeClass::Never; edges.len()];
+    let mut order: Vec<usize> = (0..edges.len()).collect();
+    order.sort_unstable_by_key(|&i| edges[i].weight);
+
+    let mut union_find = UnionFind::new(graph.num_vertices());
+    for class in order.chunk_by(|&a, &b| edges[a].weight == edges[b].weight) {
+        // Edges of the class between distinct components, on the components.
+        let mut candidates = Vec::new();
+        for &i in class {
+            let from = union_find.find(edges[i].from);
+            let to = union_find.find(edges[i].to);
+            if from != to {
+                candidates.push((i, from, to));
+            }
+        }
+        let pairs: Vec<_> = candidates.iter().map(|&(_, u, v)| (u, v)).collect();
+        for (&(i, _, _), bridge) in candidates.iter().zip(bridges(&pairs)) {
+            classes[i] = if bridge {
+                EdgeClass::Critical
+            } else {
+                EdgeClass::PseudoCritical
+            };
+        }
+        for &(_, from, to) in &candidates {
+            union_find.union(from, to);
+        }
+    }
+    edges.into_iter().zip(classes).collect()
+}
+
+// Flags the bridges of the multigraph made of `edges`, whose vertices are
+// only the endpoints appearing in them.
+// # Note: O(m), see `connectivity::lowlink`. Parallel edges are never
+// #       bridges.
+pub(crate) fn bridges(edges: &[(VertexId, VertexId)]) -> Vec<bool> {
+    let mut ids: HashMap<VertexId, usize> = HashMap::new();
+    let mut adjacency: Vec<Vec<(usize, usize)>> = Vec::new();
+    let mut local = |v: VertexId, adjacency: &mut Vec<Vec<(usize, usize)>>| {
+        *ids.entry(v).or_insert_with(|| {
+            adjacency.push(Vec::new());
+            adjacency.len() - 1
+        })
+    };
+    for (e, &(u, v)) in edges.iter().enumerate() {
+        let (u, v) = (local(u, &mut adjacency), local(v, &mut adjacency));
+        adjacency[u].push((v, e));
+        adjacency[v].push((u, e));
+    }
+    lowlink(&adjacency, edges.len()).0
+}
@@ -0,0 +1,88 @@
+// # Shortest Paths
+//
+// Dijkstra's single-source shortest paths on the adjacency lists of
+// `GraphStars`, with the indexed binary heap of the Prim variants.
+use crate::constants::{CostSum, VertexId};
+use crate::error::GraphError;
+use crate::graph::{check_bounds, Graph};
+use crate::graph_stars::GraphStars;
+use crate::heaps::IndexedBinaryHeap;
+
+// Distances from a source and the shortest path tree reaching them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShortestPaths {
+    pub source: VertexId,
+    // Length of a shortest path to each vertex, None if unreachable.
+    pub distances: Vec<Option<CostSum>>,
+    // Previous vertex on the shortest path, None for the source and the
+    // unreachable vertices.
+    pub parents: Vec<Option<VertexId>>,
+}
+
+impl ShortestPaths {
+    // Returns the length of a shortest path to `target`, if reachable.
+    pub fn distance(&self, target: VertexId) -> Option<CostSum> {
+        self.distances.get(target).copied().flatten()
+    }
+
+    // Returns the vertices of a shortest path from the source to `target`,
+    // both included, if reachable.
+    pub fn path_to(&self, target: VertexId) -> Option<Vec<VertexId>> {
+        self.distance(target)?;
+        let mut path = vec![target];
+        let mut vertex = target;
+        while let Some(parent) = self.parents[vertex] {
+            path.push(parent);
+            vertex = parent;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+// Computes the shortest paths from `source` to every vertex, failing with
+// `VertexOutOfBounds` if it isn't a vertex.
+// # Note: O((n + m) log n). Distances are summed as `CostSum`, so they
+// #       can't overflow.
+pub fn dijkstra<T: Clone + Eq, E: Default>(
+    graph: &GraphStars<T, E>,
+    source: VertexId,
+) -> Result<ShortestPaths, GraphError> {
+    let n = graph.num_vertices();
+    check_bounds(source, source, n)?;
+
+    let mut distances: Vec<Option<CostSum>> = vec![None; n];
+    let mut parents = vec![None; n];
+    let mut settled = vec![false; n];
+    let mut heap = IndexedBinaryHeap::new(n);
+    distances[source] = Some(0);
+    heap.push(source, 0);
+    while let Some((u, distance)) = heap.pop() {
+        settled[u] = true;
+        for edge in graph.star(u) {
+            let v = edge.to;
+            if settled[v] {
+                continue;
+            }
+            let candidate = distance + edge.weight as CostSum;
+            match distances[v] {
+                Some(known) if known <= candidate => {}
+                Some(_) => {
+                    distances[v] = Some(candidate);
+                    parents[v] = Some(u);
+                    heap.decrease_key(v, candidate);
+                }
+                None => {
+                    distances[v] = Some(candidate);
+                    parents[v] = Some(u);
+                    heap.push(v, candidate);
+                }
+            }
+        }
+    }
+    Ok(ShortestPaths {
+        source,
+        distances,
+        parents,
+    })
+}
@@ -0,0 +1,59 @@
+// # Signed Weights
+//
+// Minimum spanning trees of graphs with negative weights, e.g. log
+// probabilities. The trees only depend on the order of the weights, so each
+// weight is replaced by its rank among the distinct ones, any variant runs
+// on the ranks and the tree is mapped back.
+use crate::algorithm::Algorithm;
+use crate::constants::{ICost, VertexId};
+use crate::error::GraphError;
+use crate::graph::Edge;
+use rand_core::Rng;
+
+// A spanning tree with signed weights.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignedMst {
+    pub edges: Vec<(VertexId, VertexId, ICost)>,
+    // Total weight, wide enough that it never overflows.
+    pub cost: i128,
+}
+
+// Replaces each weight by its rank among the distinct weights, returning
+// the ranked edges and the weight of each rank.
+// # Note: O(m log m), equal weights get equal ranks so ties are still
+// #       broken by endpoints as in `Edge::key`.
+pub(crate) fn rank_edges<W: Ord + Copy>(edges: &[(VertexId, VertexId, W)]) -> (Vec<Edge>, Vec<W>) {
+    let mut weights: Vec<W> = edges.iter().map(|&(_, _, w)| w).collect();
+    weights.sort_unstable();
+    weights.dedup();
+    let ranked = edges
+        .iter()
+        .map(|&(from, to, w)| {
+            let rank = weights.binary_search(&w).expect("every weight is ranked");
+            Edge::new(from, to, rank)
+        })
+        .collect();
+    (ranked, weights)
+}
+
+// Runs `algorithm` on a graph with signed weights given as an edge list,
+// `rng` is only used by the randomized variants.
+// # Example: let mst = signed::run(Algorithm::Kruskal, 3, &[(0, 1, -4), (1, 2, 2)], &mut rng)?;
+// # Note: Self-loops are ignored, parallel edges collapse to the cheapest
+// #       as in `Algorithm::run_on_edges`.
+pub fn run<R: Rng + ?Sized>(
+    algorithm: Algorithm,
+    num_vertices: usize,
+    edges: &[(VertexId, VertexId, ICost)],
+    rng: &mut R,
+) -> Result<SignedMst, GraphError> {
+    let (ranked, weights) = rank_edges(edges);
+    let tree = algorithm.run_on_edges(num_vertices, &ranked, rng)?;
+    let edges: Vec<_> = tree
+        .edges
+        .iter()
+        .map(|e| (e.from, e.to, weights[e.weight]))
+        .collect();
+    let cost = edges.iter().map(|&(_, _, w)| w as i128).sum();
+    Ok(SignedMst { edges, cost })
+}
@@ -1,23 +1,35 @@
 // # Skewed Filter Kruskal (Quick Sort Kruskal)
 //
 use crate::constants::*;
+use crate::error::GraphError;
 use crate::graph::{Edge, Graph};
 use crate::graph_matrix::GraphMatrix;
+use crate::memory::HeapBytes;
+use crate::mst_result::MstResult;
+use crate::partition::{partition, PivotStrategy};
+use crate::reuse::{Reusable, TreeBuffer};
+use crate::rng::SplitMix64;
 use crate::union_find::UnionFind;
-use rand::Rng;
+use rand_core::Rng;
 
 pub struct SkewedFilterKruskal {
     num_vertices: usize,
     num_edges: usize,
     edges: Vec<Edge>,
+    pivot: PivotStrategy,
     union_find: UnionFind,
-    mst_edges: Vec<Edge>,
-    mst_cost: Cost,
+    tree: TreeBuffer,
 }
 
 impl SkewedFilterKruskal {
-    // Constructs the algorithm structures
+    // Constructs the algorithm structures with the skewed pivot, the lightest
+    // of up to 5 random samples.
     pub fn new(graph: &GraphMatrix<usize>) -> Self {
+        Self::with_pivot(graph, PivotStrategy::SkewedMinOf(5))
+    }
+
+    // Constructs the algorithm structures with the given pivot strategy.
+    pub fn with_pivot(graph: &GraphMatrix<usize>, pivot: PivotStrategy) -> Self {
         let num_vertices = graph.num_vertices();
         let edges = graph.all_edges();
         let num_edges = edges.len();
@@ -26,18 +38,17 @@ impl SkewedFilterKruskal {
             num_vertices,
             num_edges,
             edges,
+            pivot,
             union_find: UnionFind::new(num_vertices),
-            mst_edges: Vec::new(),
-            mst_cost: 0,
+            tree: TreeBuffer::default(),
         }
     }
 
-    // Runs the algorithm and returns a set of edges representing the minimum
-    // spanning tree and its associated total cost.
-    //
-    pub fn run<R: Rng>(&mut self, rng: &mut R) -> (Vec<Edge>, Cost) {
+    // Partitions the edges recursively, returning the number of tree edges
+    // found. The edges themselves are only kept if `keep_edges` is set.
+    fn grow<R: Rng + ?Sized>(&mut self, rng: &mut R, keep_edges: bool) -> usize {
         if self.num_edges == 0 {
-            return (Vec::new(), 0);
+            return 0;
         }
 
         let mut count = 0;
@@ -46,64 +57,24 @@ impl SkewedFilterKruskal {
         stack.push((0, self.num_edges - 1));
 
         while let Some((p, q)) = stack.pop() {
-            if count >= self.num_vertices - 1 {
+            if count + 1 >= self.num_vertices {
                 break;
             }
 
             if p == q {
                 let edge = self.edges[p];
                 if self.union_find.union(edge.from, edge.to) {
-                    self.mst_edges.push(edge);
-                    self.mst_cost += edge.weight;
+                    self.tree.add(edge, keep_edges);
                     count += 1;
                 }
                 continue;
             }
 
-            if p < q {
-                let len = q - p + 1;
-                // max (1,  min (len / 100, 5))
-                let r = (len / 100).clamp(1, 5);
-
-                let mut best_pivot_idx = p;
-
-                for _ in 0..r {
-                    let candidate = rng.random_range(p..=q);
-                    if self.edges[candidate].weight < self.edges[best_pivot_idx].weight {
-                        best_pivot_idx = candidate;
-                    }
-                }
-
-                self.edges.swap(p, best_pivot_idx);
-            }
-
-            let mut e_plus = q;
-            let mut e_minus = p;
-
-            let pivot_weight = self.edges[p].weight;
-
-            while e_minus <= e_plus {
-                while e_plus >= p && self.edges[e_plus].weight > pivot_weight {
-                    if e_plus == 0 {
-                        break;
-                    }
-                    e_plus -= 1;
-                }
-
-                while e_minus <= e_plus && self.edges[e_minus].weight <= pivot_weight {
-                    e_minus += 1;
-                }
-
-                if e_minus < e_plus {
-                    self.edges.swap(e_minus, e_plus);
-                    e_minus += 1;
-                    e_plus = e_plus.saturating_sub(1);
-                }
-            }
-
-            self.edges.swap(p, e_plus);
+            let pivot_idx = self.pivot.select(&self.edges, p, q, rng);
+            self.edges.swap(p, pivot_idx);
+            let e_plus = partition(&mut self.edges, p, q);
 
-            if (count < self.num_vertices - 1) && (e_plus < q) {
+            if (count + 1 < self.num_vertices) && (e_plus < q) {
                 stack.push((e_plus + 1, q));
             }
 
@@ -114,6 +85,56 @@ impl SkewedFilterKruskal {
             }
         }
 
-        (self.mst_edges.clone(), self.mst_cost)
+        count
+    }
+
+    // Runs the algorithm on another graph, reusing the buffers of the
+    // previous run, see `Reusable`.
+    pub fn run_on<R: Rng + ?Sized>(
+        &mut self,
+        graph: &GraphMatrix<usize>,
+        rng: &mut R,
+    ) -> Result<MstResult, GraphError> {
+        self.reset_with(graph);
+        self.grow(rng, true);
+        self.tree.result(self.num_vertices)
+    }
+
+    // Returns an estimate of the heap bytes held by the algorithm: the edge
+    // buffer, the union-find and the tree.
+    pub fn memory_footprint(&self) -> usize {
+        self.edges.heap_bytes() + self.union_find.memory_footprint() + self.tree.memory_footprint()
+    }
+
+    // Runs the algorithm and returns a set of edges representing the minimum
+    // spanning tree and its associated total cost, consuming the algorithm
+    // (see `Reusable`).
+    pub fn run<R: Rng + ?Sized>(mut self, rng: &mut R) -> Result<MstResult, GraphError> {
+        self.grow(rng, true);
+        self.tree.into_result(self.num_vertices)
+    }
+
+    // Runs the algorithm and returns only the total cost of the minimum
+    // spanning tree, its edges are never stored.
+    pub fn run_cost<R: Rng + ?Sized>(mut self, rng: &mut R) -> Result<Cost, GraphError> {
+        let count = self.grow(rng, false);
+        self.tree.total(self.num_vertices, count)
+    }
+
+    // Same as `run` with a `SplitMix64` seeded with `seed`, for reproducible
+    // runs without picking a generator.
+    pub fn run_seeded(self, seed: u64) -> Result<MstResult, GraphError> {
+        self.run(&mut SplitMix64::new(seed))
+    }
+}
+
+impl Reusable<&GraphMatrix<usize>> for SkewedFilterKruskal {
+    fn reset_with(&mut self, graph: &GraphMatrix<usize>) {
+        self.num_vertices = graph.num_vertices();
+        self.edges.clear();
+        self.edges.extend_from_slice(graph.cached_edges());
+        self.num_edges = self.edges.len();
+        self.union_find.reset(self.num_vertices);
+        self.tree.clear();
     }
 }
@@ -0,0 +1,292 @@
+// # Spanning Trees
+//
+// Tools on the whole set of spanning trees of a graph rather than the
+// minimum one, e.g. random trees to use as baselines.
+use crate::constants::{CostSum, VertexId};
+use crate::error::GraphError;
+use crate::graph::{check_edge, Edge, Graph};
+use crate::mst_result::{check_spanning, MstResult};
+use crate::rng;
+use crate::union_find::UnionFind;
+use rand_core::Rng;
+
+// Returns the edges leaving each vertex, oriented from it.
+// # Note: Self-loops are dropped, they belong to no spanning tree.
+fn adjacency(num_vertices: usize, edges: &[Edge]) -> Vec<Vec<Edge>> {
+    let mut adjacency = vec![Vec::new(); num_vertices];
+    for edge in edges.iter().filter(|e| e.from != e.to) {
+        adjacency[edge.from].push(*edge);
+        adjacency[edge.to].push(Edge::new(edge.to, edge.from, edge.weight));
+    }
+    adjacency
+}
+
+// Fails if the edges don't connect all the vertices.
+fn check_connected(num_vertices: usize, edges: &[Edge]) -> Result<(), GraphError> {
+    let mut union_find = UnionFind::new(num_vertices);
+    let joined = edges
+        .iter()
+        .filter(|e| union_find.union(e.from, e.to))
+        .count();
+    match num_vertices.saturating_sub(joined) {
+        0 | 1 => Ok(()),
+        components => Err(GraphError::Disconnected { components }),
+    }
+}
+
+// Samples a spanning tree uniformly at random among all the spanning trees
+// of the graph, with Wilson's loop-erased random walks.
+// # Note: Weights are ignored for the choice and only summed in the cost.
+// #       Parallel edges count as distinct trees. O(mean hitting time).
+pub fn sample_uniform<T, G, R>(graph: &G, rng: &mut R) -> Result<MstResult, GraphError>
+where
+    G: Graph<T>,
+    R: Rng + ?Sized,
+{
+    let n = graph.num_vertices();
+    let edges = graph.all_edges();
+    check_connected(n, &edges)?;
+    let adjacency = adjacency(n, &edges);
+
+    let mut in_tree = vec![false; n];
+    // Last edge taken out of each vertex by the current walk, overwriting
+    // it erases the loops.
+    let mut next: Vec<Option<Edge>> = vec![None; n];
+    let mut tree = Vec::with_capacity(n.saturating_sub(1));
+    if n > 0 {
+        in_tree[0] = true;
+    }
+    for start in 0..n {
+        let mut u = start;
+        while !in_tree[u] {
+            let edge = adjacency[u][rng::below(rng, adjacency[u].len())];
+            next[u] = Some(edge);
+            u = edge.to;
+        }
+        // Add the loop-erased path to the tree.
+        let mut u = start;
+        while !in_tree[u] {
+            in_tree[u] = true;
+            let edge = next[u].expect("walked vertices have a next edge");
+            tree.push(edge);
+            u = edge.to;
+        }
+    }
+    let cost = tree.iter().map(|e| e.weight as CostSum).sum();
+    MstResult::from_forest(n, tree, cost)
+}
+
+// Exact counts are attempted up to this many vertices.
+pub const EXACT_COUNT_MAX_VERTICES: usize = 256;
+
+// Number of spanning trees of a graph.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TreeCount {
+    Exact(u128),
+    // Natural logarithm of a count too large for an exact computation.
+    Log(f64),
+}
+
+impl TreeCount {
+    // Returns the natural logarithm of the count, -inf for no tree.
+    pub fn ln(&self) -> f64 {
+        match *self {
+            TreeCount::Exact(count) => (count as f64).ln(),
+            TreeCount::Log(ln) => ln,
+        }
+    }
+}
+
+// Counts the spanning trees of the graph with the matrix-tree theorem, as
+// the determinant of the Laplacian without its first row and column.
+// # Note: Weights are ignored, parallel edges count as distinct trees.
+// #       O(n^3) with fraction-free (Bareiss) elimination on i128, falling
+// #       back to the log-determinant in floating point when the graph has
+// #       more than `EXACT_COUNT_MAX_VERTICES` vertices or the elimination
+// #       overflows, whose products reach about the square of the count.
+pub fn count<T, G: Graph<T>>(graph: &G) -> TreeCount {
+    let n = graph.num_vertices();
+    let edges = graph.all_edges();
+    if check_connected(n, &edges).is_err() {
+        return TreeCount::Exact(0);
+    }
+    if n <= 1 {
+        return TreeCount::Exact(1);
+    }
+
+    // Laplacian minor over the vertices 1..n.
+    let m = n - 1;
+    let mut laplacian = vec![0i128; m * m];
+    for edge in edges.iter().filter(|e| e.from != e.to) {
+        let (u, v) = (edge.from, edge.to);
+        for w in [u, v] {
+            if w > 0 {
+                laplacian[(w - 1) * m + (w - 1)] += 1;
+            }
+        }
+        if u > 0 && v > 0 {
+            laplacian[(u - 1) * m + (v - 1)] -= 1;
+            laplacian[(v - 1) * m + (u - 1)] -= 1;
+        }
+    }
+
+    if n <= EXACT_COUNT_MAX_VERTICES
+        && let Some(det) = bareiss_determinant(laplacian.clone(), m)
+    {
+        return TreeCount::Exact(det as u128);
+    }
+    TreeCount::Log(log_determinant(
+        laplacian.into_iter().map(|x| x as f64).collect(),
+        m,
+    ))
+}
+
+// Determinant of a row-major m x m integer matrix with positive leading
+// minors by fraction-free elimination, None on overflow.
+fn bareiss_determinant(mut a: Vec<i128>, m: usize) -> Option<i128> {
+    let mut previous = 1;
+    for k in 0..m - 1 {
+        let pivot = a[k * m + k];
+        for i in (k + 1)..m {
+            let factor = a[i * m + k];
+            for j in (k + 1)..m {
+                // Exact division, the quotient is a minor of the matrix.
+                let value = a[i * m + j]
+                    .checked_mul(pivot)?
+                    .checked_sub(factor.checked_mul(a[k * m + j])?)?;
+                a[i * m + j] = value / previous;
+            }
+        }
+        previous = pivot;
+    }
+    Some(a[m * m - 1])
+}
+
+// Natural logarithm of the determinant of a row-major m x m symmetric
+// positive definite matrix, by Gaussian elimination without pivoting.
+fn log_determinant(mut a: Vec<f64>, m: usize) -> f64 {
+    let mut ln = 0.0;
+    for k in 0..m {
+        let pivot = a[k * m + k];
+        ln += pivot.ln();
+        for i in (k + 1)..m {
+            let factor = a[i * m + k] / pivot;
+            if factor == 0.0 {
+                continue;
+            }
+            for j in (k + 1)..m {
+                a[i * m + j] -= factor * a[k * m + j];
+            }
+        }
+    }
+    ln
+}
+
+// Returns the Prüfer sequence of a spanning tree on `num_vertices` vertices,
+// the n - 2 neighbours of the smallest leaves removed one at a time.
+// # Note: O(n), weights are dropped. Trees on fewer than 2 vertices have
+// #       an empty sequence.
+pub fn to_prufer(num_vertices: usize, tree: &MstResult) -> Result<Vec<VertexId>, GraphError> {
+    let n = num_vertices;
+    let mut adjacency = vec![Vec::new(); n];
+    let mut union_find = UnionFind::new(n);
+    for edge in &tree.edges {
+        check_edge(edge.from, edge.to, n)?;
+        if !union_find.union(edge.from, edge.to) {
+            return Err(GraphError::NotATree);
+        }
+        adjacency[edge.from].push(edge.to);
+        adjacency[edge.to].push(edge.from);
+    }
+    check_spanning(n, tree.edges.len())?;
+    if n < 2 {
+        return Ok(Vec::new());
+    }
+
+    // Parents towards the last vertex, which is never removed.
+    let mut parent = vec![n - 1; n];
+    let mut stack = vec![n - 1];
+    let mut visited = vec![false; n];
+    visited[n - 1] = true;
+    while let Some(u) = stack.pop() {
+        for &v in &adjacency[u] {
+            if !visited[v] {
+                visited[v] = true;
+                parent[v] = u;
+                stack.push(v);
+            }
+        }
+    }
+
+    let mut degree: Vec<usize> = adjacency.iter().map(Vec::len).collect();
+    let mut sequence = Vec::with_capacity(n - 2);
+    let mut cursor = first_leaf(&degree, 0);
+    let mut leaf = cursor;
+    for _ in 0..n - 2 {
+        let next = parent[leaf];
+        sequence.push(next);
+        degree[next] -= 1;
+        (leaf, cursor) = next_leaf(&degree, next, cursor);
+    }
+    Ok(sequence)
+}
+
+// Returns the edges of the tree on `sequence.len() + 2` vertices whose
+// Prüfer sequence is `sequence`.
+// # Note: O(n), every sequence of ids below n encodes exactly one tree.
+pub fn from_prufer(sequence: &[VertexId]) -> Result<Vec<(VertexId, VertexId)>, GraphError> {
+    let n = sequence.len() + 2;
+    let mut degree = vec![1; n];
+    for &v in sequence {
+        if v >= n {
+            return Err(GraphError::VertexOutOfBounds {
+                vertex: v,
+                num_vertices: n,
+            });
+        }
+        degree[v] += 1;
+    }
+
+    let mut edges = Vec::with_capacity(n - 1);
+    let mut cursor = first_leaf(&degree, 0);
+    let mut leaf = cursor;
+    for &v in sequence {
+        edges.push((leaf, v));
+        degree[v] -= 1;
+        (leaf, cursor) = next_leaf(&degree, v, cursor);
+    }
+    edges.push((leaf, n - 1));
+    Ok(edges)
+}
+
+// Returns the edges of a uniformly random labeled tree on `num_vertices`
+// vertices, decoded from a random Prüfer sequence.
+// # Example: let g = GraphStars::from_edges(n, random_tree(n, &mut rng).into_iter().map(|(u, v)| (u, v, 1)))?;
+pub fn random_tree<R: Rng + ?Sized>(num_vertices: usize, rng: &mut R) -> Vec<(VertexId, VertexId)> {
+    if num_vertices < 2 {
+        return Vec::new();
+    }
+    let sequence: Vec<VertexId> = (0..num_vertices - 2)
+        .map(|_| rng::below(rng, num_vertices))
+        .collect();
+    from_prufer(&sequence).expect("random ids are in range")
+}
+
+// Returns the first vertex from `start` with degree 1.
+fn first_leaf(degree: &[usize], start: usize) -> usize {
+    (start..degree.len())
+        .find(|&v| degree[v] == 1)
+        .expect("a tree always has a leaf left")
+}
+
+// Picks the smallest leaf after `v` lost its removed neighbour: `v` itself
+// if it became a leaf below the cursor, else the next leaf after it.
+// Returns the leaf and the new cursor.
+fn next_leaf(degree: &[usize], v: VertexId, cursor: usize) -> (usize, usize) {
+    if degree[v] == 1 && v < cursor {
+        (v, cursor)
+    } else {
+        let cursor = first_leaf(degree, cursor + 1);
+        (cursor, cursor)
+    }
+}
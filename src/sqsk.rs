@@ -3,88 +3,118 @@
 // Implementation of the QuickSort Kruskal algorithm for
 // adjacency list graphs.
 use crate::constants::Cost;
+use crate::error::GraphError;
 use crate::graph::{Edge, Graph};
 use crate::graph_stars::GraphStars;
+use crate::heaps::IndexedBinaryHeap;
+use crate::memory::{nested_heap_bytes, HeapBytes};
+use crate::mst_result::MstResult;
+use crate::partition::PivotStrategy;
+use crate::reuse::{Reusable, TreeBuffer};
+use crate::rng::SplitMix64;
 use crate::union_find::UnionFind;
 use crate::VertexId;
-use std::cmp::Ordering;
-use std::collections::BinaryHeap;
-
-#[derive(Eq, PartialEq)]
-struct SqskHeapItem {
-    cost: Cost,
-    vertex_id: VertexId,
-    // Track the index of the edge for lazy deletion
-    edge_index: usize,
-}
-impl Ord for SqskHeapItem {
-    // Lowest cost has highest priority
-    fn cmp(&self, other: &Self) -> Ordering {
-        other
-            .cost
-            .cmp(&self.cost)
-            .then_with(|| self.vertex_id.cmp(&other.vertex_id))
-    }
-}
-impl PartialOrd for SqskHeapItem {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
+use rand_core::Rng;
 
 // Structures to apply the SQSK algorithm on a generic graph.
+// # Note: With the `serde` feature the structure can be saved between
+// #       `run_steps` calls and resumed after a restart.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StarQuickSortKruskal {
     union_find: UnionFind,
-    heap: BinaryHeap<SqskHeapItem>,
+    // Current candidate of each star, by `Edge::key`.
+    heap: IndexedBinaryHeap<(Cost, VertexId, VertexId)>,
     stacks: Vec<Vec<(usize, usize)>>, // (start, end) indices
     stars: Vec<Vec<Edge>>,
     last_sorted_pos: Vec<usize>,
-    mst_edges: Vec<Edge>,
-    mst_cost: Cost,
+    tree: TreeBuffer,
+    // Tree edges found so far, a run resumes from there.
+    found: usize,
+    pivot: PivotStrategy,
+    // Whether the first quickselect step of every star is done, which
+    // happens at the start of the first run.
+    started: bool,
 }
 
 impl StarQuickSortKruskal {
-    // Constructs the algorithm's structures and initializes it.
-    pub fn new<T: Clone + Eq>(graph: &GraphStars<T>) -> Self {
-        let num_vertices = graph.num_vertices();
+    // Constructs the algorithm's structures with random pivots.
+    // # Note: The stars are copied since the run reorders them, see
+    // #       `from_graph` to reuse the graph's own.
+    pub fn new<T: Clone + Eq, E: Default>(graph: &GraphStars<T, E>) -> Self {
+        Self::with_pivot(graph, PivotStrategy::Random)
+    }
 
-        let stars_as_vecs = graph.stars();
+    // Constructs the algorithm's structures with the given pivot strategy.
+    pub fn with_pivot<T: Clone + Eq, E: Default>(
+        graph: &GraphStars<T, E>,
+        pivot: PivotStrategy,
+    ) -> Self {
+        Self::from_stars(graph.stars(), pivot)
+    }
+
+    // Same as `new` but takes the stars of the graph instead of copying
+    // them, so that the graph's edges are held only once.
+    pub fn from_graph<T: Clone + Eq, E: Default>(graph: GraphStars<T, E>) -> Self {
+        Self::from_stars(graph.into_stars(), PivotStrategy::Random)
+    }
 
-        let mut sqsk = StarQuickSortKruskal {
+    fn from_stars(stars: Vec<Vec<Edge>>, pivot: PivotStrategy) -> Self {
+        let num_vertices = stars.len();
+        StarQuickSortKruskal {
             union_find: UnionFind::new(num_vertices),
-            heap: BinaryHeap::with_capacity(num_vertices),
+            heap: IndexedBinaryHeap::new(num_vertices),
             stacks: vec![Vec::new(); num_vertices],
-            stars: stars_as_vecs,
+            stars,
             last_sorted_pos: vec![0; num_vertices],
-            mst_edges: Vec::new(),
-            mst_cost: 0,
-        };
+            tree: TreeBuffer::default(),
+            found: 0,
+            pivot,
+            started: false,
+        }
+    }
 
-        for id in 0..num_vertices {
-            if !sqsk.stars[id].is_empty() {
+    // Sets the pivot strategy of the quickselect steps.
+    // # Note: Applies from the next quickselect step on, the stars already
+    // #       partitioned keep their pivots.
+    pub fn set_pivot(&mut self, pivot: PivotStrategy) {
+        self.pivot = pivot;
+    }
+
+    // Runs the algorithm on another graph, reusing the buffers of the
+    // previous run, see `Reusable`.
+    pub fn run_on<T: Clone + Eq, E: Default, R: Rng + ?Sized>(
+        &mut self,
+        graph: &GraphStars<T, E>,
+        rng: &mut R,
+    ) -> Result<MstResult, GraphError> {
+        self.reset_with(graph);
+        self.grow(rng, true, usize::MAX);
+        self.tree.result(self.stars.len())
+    }
+
+    // Pushes the cheapest edge of every star into the heap.
+    fn init<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.started = true;
+        for id in 0..self.stars.len() {
+            if !self.stars[id].is_empty() {
                 // Insert the initial interval in the stack
-                let initial_interval = (0, sqsk.stars[id].len() - 1);
-                sqsk.stacks[id].push(initial_interval);
+                let initial_interval = (0, self.stars[id].len() - 1);
+                self.stacks[id].push(initial_interval);
 
                 // First quickselect step
-                sqsk.qs_step(id);
+                self.qs_step(id, rng);
 
                 // Add to the heap the best candidate for each node
-                let cost = sqsk.stars[id][0].weight;
+                let key = self.stars[id][0].key();
 
-                sqsk.heap.push(SqskHeapItem {
-                    cost,
-                    vertex_id: id,
-                    edge_index: 0,
-                });
+                self.heap.push(id, key);
             }
         }
-        sqsk
     }
 
     // Executes a single step of the quickselect algorithm
     //
-    pub fn qs_step(&mut self, id: usize) {
+    pub fn qs_step<R: Rng + ?Sized>(&mut self, id: usize, rng: &mut R) {
         if self.last_sorted_pos[id] >= self.stars[id].len() {
             return;
         }
@@ -94,15 +124,15 @@ impl StarQuickSortKruskal {
 
             // Quickselect
             while p < q {
-                let pivot = p + (q - p) / 2; // pivot in the middle
+                let pivot = self.pivot.select(&self.stars[id], p, q, rng);
 
                 self.stars[id].swap(pivot, q);
 
-                let pivot_weight = self.stars[id][q].weight;
+                let pivot_key = self.stars[id][q].key();
 
                 let mut i = p;
                 for j in p..q {
-                    if self.stars[id][j].weight < pivot_weight {
+                    if self.stars[id][j].key() < pivot_key {
                         self.stars[id].swap(i, j);
                         i += 1;
                     }
@@ -134,35 +164,31 @@ impl StarQuickSortKruskal {
         }
     }
 
-    // Runs the algorithm and returns a set of edges representing the minimum
-    // spanning tree and its associated total cost.
-    pub fn run(&mut self) -> (Vec<Edge>, Cost) {
+    // Pops the star minima in order until `max_edges` more tree edges are
+    // found, returning the number of tree edges found so far. The edges
+    // themselves are only kept if `keep_edges` is set.
+    fn grow<R: Rng + ?Sized>(&mut self, rng: &mut R, keep_edges: bool, max_edges: usize) -> usize {
         let num_vertices = self.stars.len();
         if num_vertices == 0 {
-            return (self.mst_edges.clone(), self.mst_cost);
+            return 0;
         }
-        let mut count = 0;
+        if !self.started {
+            self.init(rng);
+        }
+        let limit = self.found.saturating_add(max_edges).min(num_vertices - 1);
 
         // Loop until there are n-1 nodes in the minimum spanning tree
-        while count < num_vertices - 1 {
+        while self.found < limit {
             // Get the best candidate from the heap
-            if let Some(heap_item) = self.heap.pop() {
-                let i = heap_item.vertex_id; // Get the node id from the heap item
-
-                // Verify validity (lazy insertion)
-                if heap_item.edge_index != self.last_sorted_pos[i] {
-                    continue;
-                }
-
+            if let Some((i, _)) = self.heap.pop() {
                 let edge = self.stars[i][self.last_sorted_pos[i]];
                 let j = edge.to;
                 let w = edge.weight;
 
                 // Union between the two MST with representative i and j
                 if self.union_find.union(i, j) {
-                    self.mst_edges.push(Edge::new(i, j, w));
-                    self.mst_cost += w;
-                    count += 1;
+                    self.tree.add(Edge::new(i, j, w), keep_edges);
+                    self.found += 1;
                 }
                 // Next candidate
                 self.last_sorted_pos[i] += 1;
@@ -170,21 +196,83 @@ impl StarQuickSortKruskal {
                 // If there are more arcs not yet ordered wake up
                 // quickselect again.
                 if self.last_sorted_pos[i] < self.stars[i].len() {
-                    self.qs_step(i);
-                    let new_cost = self.stars[i][self.last_sorted_pos[i]].weight;
-
-                    // Push the new candidate without removing the old one (lazy insertion).
-                    self.heap.push(SqskHeapItem {
-                        cost: new_cost,
-                        vertex_id: i,
-                        edge_index: self.last_sorted_pos[i],
-                    });
+                    self.qs_step(i, rng);
+                    let new_key = self.stars[i][self.last_sorted_pos[i]].key();
+
+                    // The star is back in the heap with its next candidate.
+                    self.heap.push(i, new_key);
                 }
             } else {
                 // If the heap is empty we got the solution (graph is disconnected).
                 break;
             }
         }
-        (self.mst_edges.clone(), self.mst_cost)
+        self.found
+    }
+
+    // Finds at most `max_edges` more tree edges, keeping them, and returns
+    // true once the run is over. A later `run` completes the tree.
+    // # Example: while !sqsk.run_steps(1 << 20, &mut rng) { save(&sqsk)?; }
+    pub fn run_steps<R: Rng + ?Sized>(&mut self, max_edges: usize, rng: &mut R) -> bool {
+        let found = self.found;
+        self.grow(rng, true, max_edges);
+        self.found - found < max_edges || self.found + 1 >= self.stars.len()
+    }
+
+    // Returns an estimate of the heap bytes held by the algorithm: the stars
+    // with their stacks of unsorted ranges, the heap of candidates, the
+    // union-find and the tree.
+    pub fn memory_footprint(&self) -> usize {
+        self.union_find.memory_footprint()
+            + self.heap.memory_footprint()
+            + self.stacks.heap_bytes()
+            + nested_heap_bytes(&self.stacks)
+            + self.stars.heap_bytes()
+            + nested_heap_bytes(&self.stars)
+            + self.last_sorted_pos.heap_bytes()
+            + self.tree.memory_footprint()
+    }
+
+    // Runs the algorithm and returns a set of edges representing the minimum
+    // spanning tree and its associated total cost, consuming the algorithm
+    // (see `Reusable`).
+    pub fn run<R: Rng + ?Sized>(mut self, rng: &mut R) -> Result<MstResult, GraphError> {
+        self.grow(rng, true, usize::MAX);
+        self.tree.into_result(self.stars.len())
+    }
+
+    // Runs the algorithm and returns only the total cost of the minimum
+    // spanning tree, its edges are never stored.
+    pub fn run_cost<R: Rng + ?Sized>(mut self, rng: &mut R) -> Result<Cost, GraphError> {
+        let count = self.grow(rng, false, usize::MAX);
+        self.tree.total(self.stars.len(), count)
+    }
+
+    // Same as `run` with a `SplitMix64` seeded with `seed`, for reproducible
+    // runs without picking a generator.
+    pub fn run_seeded(self, seed: u64) -> Result<MstResult, GraphError> {
+        self.run(&mut SplitMix64::new(seed))
+    }
+}
+
+impl<T: Clone + Eq, E: Default> Reusable<&GraphStars<T, E>> for StarQuickSortKruskal {
+    // The stars are copied into the previous ones, the first quickselect
+    // steps are left to the run.
+    fn reset_with(&mut self, graph: &GraphStars<T, E>) {
+        let num_vertices = graph.num_vertices();
+        self.union_find.reset(num_vertices);
+        self.heap.reset(num_vertices);
+        self.stacks.resize_with(num_vertices, Vec::new);
+        self.stars.resize_with(num_vertices, Vec::new);
+        for id in 0..num_vertices {
+            self.stacks[id].clear();
+            self.stars[id].clear();
+            self.stars[id].extend_from_slice(graph.star(id));
+        }
+        self.last_sorted_pos.clear();
+        self.last_sorted_pos.resize(num_vertices, 0);
+        self.tree.clear();
+        self.found = 0;
+        self.started = false;
     }
 }
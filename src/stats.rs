@@ -0,0 +1,107 @@
+// # Statistics
+//
+// Summary of a graph's size, degrees and weights, a sanity report before
+// choosing which variant to run.
+use crate::constants::Cost;
+use crate::graph::Graph;
+use crate::union_find::UnionFind;
+use std::fmt;
+
+// Number of equal width bins of the weight histogram.
+pub const WEIGHT_BINS: usize = 16;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphStats {
+    pub num_vertices: usize,
+    pub num_edges: usize,
+    // Fraction of the n(n - 1)/2 possible edges, 0 under two vertices.
+    pub density: f64,
+    pub min_degree: usize,
+    pub max_degree: usize,
+    pub mean_degree: f64,
+    // Number of vertices of each degree, up to the maximum.
+    pub degree_histogram: Vec<usize>,
+    // Lightest and heaviest weight, None without edges.
+    pub weight_range: Option<(Cost, Cost)>,
+    // Number of edges in each of the `WEIGHT_BINS` equal slices of the
+    // weight range, empty without edges.
+    pub weight_histogram: Vec<usize>,
+    pub components: usize,
+}
+
+// Collects the statistics of a graph.
+// # Example: println!("{}", stats::summarize(&graph));
+// # Note: O(n + m). Degrees are counted on `all_edges`, a self-loop adds 2.
+pub fn summarize<T, G: Graph<T>>(graph: &G) -> GraphStats {
+    let n = graph.num_vertices();
+    let edges = graph.all_edges();
+    let m = edges.len();
+
+    let mut degrees = vec![0; n];
+    let mut union_find = UnionFind::new(n);
+    let mut components = n;
+    for edge in &edges {
+        degrees[edge.from] += 1;
+        degrees[edge.to] += 1;
+        if union_find.union(edge.from, edge.to) {
+            components -= 1;
+        }
+    }
+    let min_degree = degrees.iter().copied().min().unwrap_or(0);
+    let max_degree = degrees.iter().copied().max().unwrap_or(0);
+    let mut degree_histogram = vec![0; if n == 0 { 0 } else { max_degree + 1 }];
+    for &degree in &degrees {
+        degree_histogram[degree] += 1;
+    }
+
+    let weight_range = edges
+        .iter()
+        .map(|e| e.weight)
+        .min()
+        .zip(edges.iter().map(|e| e.weight).max());
+    let mut weight_histogram = Vec::new();
+    if let Some((min, max)) = weight_range {
+        weight_histogram = vec![0; WEIGHT_BINS];
+        let span = (max - min) as u128 + 1;
+        for edge in &edges {
+            let bin = (edge.weight - min) as u128 * WEIGHT_BINS as u128 / span;
+            weight_histogram[bin as usize] += 1;
+        }
+    }
+
+    let pairs = n as f64 * n.saturating_sub(1) as f64 / 2.0;
+    GraphStats {
+        num_vertices: n,
+        num_edges: m,
+        density: if n < 2 { 0.0 } else { m as f64 / pairs },
+        min_degree,
+        max_degree,
+        mean_degree: if n == 0 {
+            0.0
+        } else {
+            2.0 * m as f64 / n as f64
+        },
+        degree_histogram,
+        weight_range,
+        weight_histogram,
+        components,
+    }
+}
+
+impl fmt::Display for GraphStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "vertices:   {}", self.num_vertices)?;
+        writeln!(f, "edges:      {}", self.num_edges)?;
+        writeln!(f, "density:    {:.4}", self.density)?;
+        writeln!(
+            f,
+            "degree:     min {} / mean {:.2} / max {}",
+            self.min_degree, self.mean_degree, self.max_degree
+        )?;
+        if let Some((min, max)) = self.weight_range {
+            writeln!(f, "weights:    [{}, {}]", min, max)?;
+            writeln!(f, "histogram:  {:?}", self.weight_histogram)?;
+        }
+        write!(f, "components: {}", self.components)
+    }
+}
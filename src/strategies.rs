@@ -0,0 +1,280 @@
+// # Strategies
+//
+// Random valid graphs for property tests, with shrinking to a minimal
+// failing case. With the `proptest` feature a `GraphStrategy` is a proptest
+// `Strategy` shrinking through `shrink`, other runners can draw a seed and
+// build the case with `generate_seeded`, or the loop in `check` can be used
+// directly.
+// # Example: strategy.check(256, 42, |case| all_costs_equal(case))
+// # Example: proptest!(|(case in GraphStrategy::default())| all_costs_equal(&case))
+use crate::constants::{Cost, VertexId};
+use crate::graph::{Edge, Graph};
+use crate::union_find::UnionFind;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngExt, SeedableRng};
+
+// A generated graph as a plain edge list, so that it can be shrunk and
+// then built in any representation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GraphCase {
+    pub num_vertices: usize,
+    pub edges: Vec<Edge>,
+}
+
+impl GraphCase {
+    // Builds the graph with vertices labelled with their ids.
+    pub fn to_graph<T: From<VertexId>, G: Graph<T> + Default>(&self) -> G {
+        let triples = self.edges.iter().map(|e| (e.from, e.to, e.weight));
+        G::from_edges(self.num_vertices, triples).expect("the case is a valid graph")
+    }
+
+    // Returns true if the edges connect every vertex.
+    pub fn is_connected(&self) -> bool {
+        let mut union_find = UnionFind::new(self.num_vertices);
+        let merged = self
+            .edges
+            .iter()
+            .filter(|e| union_find.union(e.from, e.to))
+            .count();
+        merged + 1 >= self.num_vertices
+    }
+
+    // Returns true if no two edges have the same weight.
+    fn has_distinct_weights(&self) -> bool {
+        let mut weights: Vec<Cost> = self.edges.iter().map(|e| e.weight).collect();
+        weights.sort_unstable();
+        weights.windows(2).all(|w| w[0] != w[1])
+    }
+
+    // Removes a vertex and its edges, the vertices after it move down.
+    fn without_vertex(&self, vertex: VertexId) -> GraphCase {
+        let relabel = |v: VertexId| if v > vertex { v - 1 } else { v };
+        GraphCase {
+            num_vertices: self.num_vertices - 1,
+            edges: self
+                .edges
+                .iter()
+                .filter(|e| e.from != vertex && e.to != vertex)
+                .map(|e| Edge::new(relabel(e.from), relabel(e.to), e.weight))
+                .collect(),
+        }
+    }
+}
+
+// Parameters of the generated graphs.
+// # Note: Without ties the weights are a permutation of 1..=m, so the
+// #       minimum spanning tree is unique.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GraphStrategy {
+    pub max_vertices: usize,
+    // Probability of each edge beyond the spanning tree of a connected case.
+    pub density: f64,
+    pub connected: bool,
+    // Weights are drawn from 1..=3, so that most edges tie.
+    pub ties: bool,
+}
+
+impl Default for GraphStrategy {
+    fn default() -> Self {
+        GraphStrategy {
+            max_vertices: 32,
+            density: 0.2,
+            connected: true,
+            ties: false,
+        }
+    }
+}
+
+impl GraphStrategy {
+    // Returns true if the case could have been generated by the strategy,
+    // shrinking only keeps such cases.
+    pub fn accepts(&self, case: &GraphCase) -> bool {
+        case.num_vertices <= self.max_vertices
+            && (!self.connected || case.is_connected())
+            && (self.ties || case.has_distinct_weights())
+    }
+
+    // Generates a case with up to `max_vertices` vertices.
+    // # Panic: If `density` isn't between 0.0 and 1.0.
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> GraphCase {
+        let n = rng.random_range(0..=self.max_vertices);
+        let mut pairs = Vec::new();
+        let mut labels: Vec<VertexId> = (0..n).collect();
+        labels.shuffle(rng);
+        if self.connected {
+            // Random recursive tree on shuffled labels.
+            for v in 1..n {
+                let u = rng.random_range(0..v);
+                pairs.push((labels[u], labels[v]));
+            }
+        }
+        for from in 0..n {
+            for to in from + 1..n {
+                if rng.random_bool(self.density) {
+                    pairs.push((from, to));
+                }
+            }
+        }
+        for pair in pairs.iter_mut() {
+            *pair = (pair.0.min(pair.1), pair.0.max(pair.1));
+        }
+        pairs.sort_unstable();
+        pairs.dedup();
+        pairs.shuffle(rng);
+
+        let mut weights: Vec<Cost> = if self.ties {
+            (0..pairs.len()).map(|_| rng.random_range(1..=3)).collect()
+        } else {
+            (1..=pairs.len()).collect()
+        };
+        weights.shuffle(rng);
+        let edges = pairs
+            .into_iter()
+            .zip(weights)
+            .map(|((from, to), w)| Edge::new(from, to, w))
+            .collect();
+        GraphCase {
+            num_vertices: n,
+            edges,
+        }
+    }
+
+    // Same as `generate` but draws from a `StdRng` seeded with `seed`.
+    pub fn generate_seeded(&self, seed: u64) -> GraphCase {
+        self.generate(&mut StdRng::seed_from_u64(seed))
+    }
+
+    // Returns the simpler cases accepted by the strategy, most aggressive
+    // first: without a vertex, without an edge, with lower weights.
+    pub fn shrink(&self, case: &GraphCase) -> Vec<GraphCase> {
+        let mut candidates = Vec::new();
+        for vertex in (0..case.num_vertices).rev() {
+            candidates.push(case.without_vertex(vertex));
+        }
+        for i in 0..case.edges.len() {
+            let mut smaller = case.clone();
+            smaller.edges.remove(i);
+            candidates.push(smaller);
+        }
+        if self.ties {
+            let mut flat = case.clone();
+            flat.edges.iter_mut().for_each(|e| e.weight = 1);
+            candidates.push(flat);
+        }
+        // Weights replaced by their ranks, the order of the edges is kept.
+        let mut ranked = case.clone();
+        let mut weights: Vec<Cost> = case.edges.iter().map(|e| e.weight).collect();
+        weights.sort_unstable();
+        weights.dedup();
+        for edge in ranked.edges.iter_mut() {
+            edge.weight = weights
+                .binary_search(&edge.weight)
+                .expect("every weight is ranked")
+                + 1;
+        }
+        candidates.push(ranked);
+        candidates.retain(|c| c != case && self.accepts(c));
+        candidates
+    }
+
+    // Shrinks a failing case while the property keeps failing on it.
+    pub fn minimize<P: FnMut(&GraphCase) -> bool>(
+        &self,
+        mut case: GraphCase,
+        mut property: P,
+    ) -> GraphCase {
+        'shrink: loop {
+            for candidate in self.shrink(&case) {
+                if !property(&candidate) {
+                    case = candidate;
+                    continue 'shrink;
+                }
+            }
+            return case;
+        }
+    }
+
+    // Checks the property on `cases` cases drawn from `seed`, returning the
+    // minimized counterexample of the first failure.
+    pub fn check<P: FnMut(&GraphCase) -> bool>(
+        &self,
+        cases: usize,
+        seed: u64,
+        mut property: P,
+    ) -> Result<(), GraphCase> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        for _ in 0..cases {
+            let case = self.generate(&mut rng);
+            if !property(&case) {
+                return Err(self.minimize(case, property));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Shrinking state of a generated case for proptest, trying the candidates
+// of `GraphStrategy::shrink` in turn as `minimize` does.
+#[cfg(feature = "proptest")]
+#[derive(Clone, Debug)]
+pub struct GraphCaseTree {
+    strategy: GraphStrategy,
+    current: GraphCase,
+    // Last case the test failed on.
+    failing: GraphCase,
+    // Simpler cases of `failing` not tried yet, the next one last.
+    candidates: Vec<GraphCase>,
+}
+
+#[cfg(feature = "proptest")]
+impl proptest::strategy::ValueTree for GraphCaseTree {
+    type Value = GraphCase;
+
+    fn current(&self) -> GraphCase {
+        self.current.clone()
+    }
+
+    // The current case failed, shrinks it further.
+    fn simplify(&mut self) -> bool {
+        self.failing = self.current.clone();
+        self.candidates = self.strategy.shrink(&self.failing);
+        self.candidates.reverse();
+        self.complicate()
+    }
+
+    // The current case passed, moves to the next candidate or back to the
+    // failing case once there are none left.
+    fn complicate(&mut self) -> bool {
+        match self.candidates.pop() {
+            Some(candidate) => {
+                self.current = candidate;
+                true
+            }
+            None => {
+                self.current = self.failing.clone();
+                false
+            }
+        }
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl proptest::strategy::Strategy for GraphStrategy {
+    type Tree = GraphCaseTree;
+    type Value = GraphCase;
+
+    fn new_tree(
+        &self,
+        runner: &mut proptest::test_runner::TestRunner,
+    ) -> proptest::strategy::NewTree<Self> {
+        let case = self.generate(runner.rng());
+        Ok(GraphCaseTree {
+            strategy: self.clone(),
+            current: case.clone(),
+            failing: case,
+            candidates: Vec::new(),
+        })
+    }
+}
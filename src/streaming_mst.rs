@@ -0,0 +1,136 @@
+// # Streaming MST
+//
+// Semi-streaming minimum spanning tree: a single pass over the edges keeping
+// only the current spanning forest, O(n) memory. Each edge closing a cycle
+// evicts the heaviest edge on that cycle.
+use crate::constants::*;
+use crate::error::GraphError;
+use crate::graph::Edge;
+use crate::memory::{nested_heap_bytes, HeapBytes};
+use crate::mst_result::MstResult;
+use crate::union_find::UnionFind;
+
+const NO_PARENT: VertexId = VertexId::MAX;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StreamingMst {
+    num_vertices: usize,
+    // Components never split: an eviction only replaces an edge on a cycle.
+    union_find: UnionFind,
+    forest: Vec<Vec<(VertexId, Cost)>>,
+    // Scratch buffers for the path searches, reset after each use.
+    parent: Vec<(VertexId, Cost)>,
+    visited: Vec<VertexId>,
+}
+
+impl StreamingMst {
+    // Constructs an empty forest on `num_vertices` vertices.
+    pub fn new(num_vertices: usize) -> Self {
+        StreamingMst {
+            num_vertices,
+            union_find: UnionFind::new(num_vertices),
+            forest: vec![Vec::new(); num_vertices],
+            parent: vec![(NO_PARENT, ZERO_COST); num_vertices],
+            visited: Vec::new(),
+        }
+    }
+
+    // Returns an estimate of the heap bytes held by the algorithm: the
+    // union-find, the forest and the search buffers.
+    pub fn memory_footprint(&self) -> usize {
+        self.union_find.memory_footprint()
+            + self.forest.heap_bytes()
+            + nested_heap_bytes(&self.forest)
+            + self.parent.heap_bytes()
+            + self.visited.heap_bytes()
+    }
+
+    // Inserts an edge of the stream into the forest.
+    // # Note: O(1) when it joins two trees, O(n) when it closes a cycle.
+    pub fn push_edge(&mut self, edge: Edge) -> Result<(), GraphError> {
+        if edge.from >= self.num_vertices || edge.to >= self.num_vertices {
+            return Err(GraphError::VertexOutOfBounds {
+                vertex: edge.from.max(edge.to),
+                num_vertices: self.num_vertices,
+            });
+        }
+        if edge.from == edge.to {
+            return Ok(());
+        }
+
+        if self.union_find.union(edge.from, edge.to) {
+            self.link(edge.from, edge.to, edge.weight);
+            return Ok(());
+        }
+
+        // Same tree: replace the heaviest edge on the path if it's heavier.
+        let heaviest = self.heaviest_on_path(edge.from, edge.to);
+        if heaviest > edge {
+            self.cut(heaviest.from, heaviest.to);
+            self.link(edge.from, edge.to, edge.weight);
+        }
+        Ok(())
+    }
+
+    // Returns the spanning tree of all the edges pushed so far.
+    pub fn finish(self) -> Result<MstResult, GraphError> {
+        let mut edges = Vec::new();
+        for (from, neighbors) in self.forest.iter().enumerate() {
+            for &(to, weight) in neighbors {
+                if from < to {
+                    edges.push(Edge::new(from, to, weight));
+                }
+            }
+        }
+        let cost = edges.iter().map(|e| e.weight as CostSum).sum();
+        MstResult::from_forest(self.num_vertices, edges, cost)
+    }
+
+    fn link(&mut self, u: VertexId, v: VertexId, weight: Cost) {
+        self.forest[u].push((v, weight));
+        self.forest[v].push((u, weight));
+    }
+
+    fn cut(&mut self, u: VertexId, v: VertexId) {
+        for (a, b) in [(u, v), (v, u)] {
+            let pos = self.forest[a].iter().position(|&(to, _)| to == b).unwrap();
+            self.forest[a].swap_remove(pos);
+        }
+    }
+
+    // Returns the heaviest edge (by `Edge::key`) on the tree path between two
+    // vertices of the same tree, found with a DFS rooted in `from`.
+    fn heaviest_on_path(&mut self, from: VertexId, to: VertexId) -> Edge {
+        let mut stack = vec![from];
+        self.parent[from] = (from, ZERO_COST);
+        self.visited.push(from);
+        while let Some(u) = stack.pop() {
+            if u == to {
+                break;
+            }
+            for &(v, weight) in &self.forest[u] {
+                if self.parent[v].0 == NO_PARENT {
+                    self.parent[v] = (u, weight);
+                    self.visited.push(v);
+                    stack.push(v);
+                }
+            }
+        }
+
+        let mut heaviest: Option<Edge> = None;
+        let mut curr = to;
+        while curr != from {
+            let (prev, weight) = self.parent[curr];
+            let edge = Edge::new(prev, curr, weight);
+            if heaviest.is_none_or(|h| edge > h) {
+                heaviest = Some(edge);
+            }
+            curr = prev;
+        }
+
+        for v in self.visited.drain(..) {
+            self.parent[v] = (NO_PARENT, ZERO_COST);
+        }
+        heaviest.unwrap()
+    }
+}
@@ -0,0 +1,130 @@
+// # Trace
+//
+// Step by step record of a Kruskal run, every edge considered with the
+// decision taken and the components it left, exported as JSON for a
+// visualizer. Meant for teaching-size graphs.
+use crate::constants::{CostSum, VertexId};
+use crate::graph::{Edge, Graph};
+use crate::union_find::UnionFind;
+use std::fmt;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Decision {
+    // The edge joins two components and enters the tree.
+    Accepted,
+    // The edge would close a cycle.
+    Rejected,
+}
+
+impl fmt::Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Decision::Accepted => write!(f, "accepted"),
+            Decision::Rejected => write!(f, "rejected"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub edge: Edge,
+    pub decision: Decision,
+    // Component of each vertex after the step, labelled by its smallest
+    // vertex so that labels only change on merges.
+    pub components: Vec<VertexId>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Trace {
+    pub num_vertices: usize,
+    pub steps: Vec<Step>,
+}
+
+// Runs Kruskal on `graph`, recording each edge considered in
+// `Edge::key` order until the tree spans the graph or no edge is left.
+// # Note: O(n m), the components are copied at every step.
+pub fn kruskal<T, G: Graph<T>>(graph: &G) -> Trace {
+    let n = graph.num_vertices();
+    let mut edges = graph.all_edges();
+    edges.sort_unstable_by_key(Edge::key);
+
+    let mut union_find = UnionFind::new(n);
+    let mut steps = Vec::new();
+    let mut accepted = 0;
+    for edge in edges {
+        if accepted + 1 >= n {
+            break;
+        }
+        let decision = if union_find.union(edge.from, edge.to) {
+            accepted += 1;
+            Decision::Accepted
+        } else {
+            Decision::Rejected
+        };
+        steps.push(Step {
+            edge,
+            decision,
+            components: labels(&mut union_find),
+        });
+    }
+    Trace {
+        num_vertices: n,
+        steps,
+    }
+}
+
+// Labels each element with the smallest element of its set.
+fn labels(union_find: &mut UnionFind) -> Vec<VertexId> {
+    let n = union_find.len();
+    let mut smallest = vec![usize::MAX; n];
+    let mut labels = Vec::with_capacity(n);
+    for v in 0..n {
+        let root = union_find.find(v);
+        if smallest[root] == usize::MAX {
+            smallest[root] = v;
+        }
+        labels.push(smallest[root]);
+    }
+    labels
+}
+
+impl Trace {
+    // Returns the edges accepted so far, the tree at the end of the trace.
+    pub fn tree(&self) -> Vec<Edge> {
+        self.steps
+            .iter()
+            .filter(|s| s.decision == Decision::Accepted)
+            .map(|s| s.edge)
+            .collect()
+    }
+
+    // Returns the trace as a JSON object:
+    // {"num_vertices": n, "cost": c, "steps": [{"edge": {"from", "to",
+    // "weight"}, "decision": "accepted" | "rejected", "components": [..]}]}
+    pub fn to_json(&self) -> String {
+        let cost: CostSum = self.tree().iter().map(|e| e.weight as CostSum).sum();
+        let steps: Vec<String> = self
+            .steps
+            .iter()
+            .map(|step| {
+                let components: Vec<String> =
+                    step.components.iter().map(|c| c.to_string()).collect();
+                format!(
+                    "{{\"edge\":{{\"from\":{},\"to\":{},\"weight\":{}}},\
+                     \"decision\":\"{}\",\"components\":[{}]}}",
+                    step.edge.from,
+                    step.edge.to,
+                    step.edge.weight,
+                    step.decision,
+                    components.join(",")
+                )
+            })
+            .collect();
+        format!(
+            "{{\"num_vertices\":{},\"cost\":{},\"steps\":[{}]}}",
+            self.num_vertices,
+            cost,
+            steps.join(",")
+        )
+    }
+}
@@ -1,10 +1,35 @@
 // # Union Find
 //
-// Union find structure implementation for the use with Kruskal algorithms.
+// Union find structure implementation for the use with Kruskal algorithms,
+// and a lock-free variant shared between threads. Both implement
+// `DisjointSets` so that algorithms can be written against either.
 
 use crate::constants::UnionFindRep;
+use crate::memory::HeapBytes;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Operations shared by the union-find structures.
+pub trait DisjointSets {
+    // Returns the representative of the set of `i`.
+    fn find(&mut self, i: UnionFindRep) -> UnionFindRep;
+    // Merges the sets of `i` and `j`, returning false if they were the same.
+    fn union(&mut self, i: UnionFindRep, j: UnionFindRep) -> bool;
+    // Returns the number of elements.
+    fn len(&self) -> usize;
+
+    // Returns true if `i` and `j` are in the same set.
+    fn same_set(&mut self, i: UnionFindRep, j: UnionFindRep) -> bool {
+        self.find(i) == self.find(j)
+    }
+
+    // Returns true if there are no elements.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
 
 // A structure to memorize the union find forest.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UnionFind {
     rep: Vec<UnionFindRep>,
     size: Vec<usize>,
@@ -21,6 +46,19 @@ impl UnionFind {
         }
     }
 
+    // Resets the forest to `num` single nodes, reusing its buffers.
+    pub fn reset(&mut self, num: usize) {
+        self.rep.clear();
+        self.rep.extend(0..num);
+        self.size.clear();
+        self.size.resize(num, 1);
+    }
+
+    // Returns an estimate of the heap bytes held by the forest.
+    pub fn memory_footprint(&self) -> usize {
+        self.rep.heap_bytes() + self.size.heap_bytes()
+    }
+
     // Returns the representative (root) of a given node
     // # Note: Takes O(n) to visit all nodes until the root is reached.
     pub fn find(&mut self, i: UnionFindRep) -> usize {
@@ -39,6 +77,35 @@ impl UnionFind {
         root
     }
 
+    // Returns the representative of a node without compressing the path,
+    // so it can be shared between threads.
+    // # Note: O(log n) since the trees are merged by size.
+    pub fn root(&self, i: UnionFindRep) -> usize {
+        let mut root = i;
+        while self.rep[root] != root {
+            root = self.rep[root];
+        }
+        root
+    }
+
+    // Compresses every path so that `root` takes a single step.
+    #[cfg(feature = "parallel")]
+    pub fn compress(&mut self) {
+        for i in 0..self.rep.len() {
+            self.find(i);
+        }
+    }
+
+    // Returns the number of elements.
+    pub fn len(&self) -> usize {
+        self.rep.len()
+    }
+
+    // Returns true if there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.rep.is_empty()
+    }
+
     // Merges the two union-find's tree with a policy that
     // unites the representative of the larger tree with the smaller
     // one and returns a boolean flag to signify success.
@@ -62,3 +129,154 @@ impl UnionFind {
         }
     }
 }
+
+impl DisjointSets for UnionFind {
+    fn find(&mut self, i: UnionFindRep) -> UnionFindRep {
+        UnionFind::find(self, i)
+    }
+
+    fn union(&mut self, i: UnionFindRep, j: UnionFindRep) -> bool {
+        UnionFind::union(self, i, j)
+    }
+
+    fn len(&self) -> usize {
+        UnionFind::len(self)
+    }
+}
+
+// Lock-free union-find shared between threads: parents are atomics, roots
+// are hooked with a compare-and-swap and paths are halved on the way up.
+// # Note: A root is hooked under the root of higher priority, a fixed
+// #       pseudo-random permutation of the elements, which keeps the trees
+// #       O(log n) deep in expectation without tracking sizes.
+pub struct ConcurrentUnionFind {
+    rep: Vec<AtomicUsize>,
+}
+
+impl ConcurrentUnionFind {
+    // Constructs the union find with every element in its own set.
+    pub fn new(num: usize) -> Self {
+        ConcurrentUnionFind {
+            rep: (0..num).map(AtomicUsize::new).collect(),
+        }
+    }
+
+    // Priority of an element, a bijection of the indices.
+    #[inline]
+    fn priority(i: UnionFindRep) -> u64 {
+        (i as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+    }
+
+    // Returns the current representative of the set of `i`.
+    // # Note: The representative may change concurrently, see `same_set`.
+    pub fn find(&self, i: UnionFindRep) -> UnionFindRep {
+        let mut curr = i;
+        loop {
+            let parent = self.rep[curr].load(Ordering::Acquire);
+            if parent == curr {
+                return curr;
+            }
+            let grandparent = self.rep[parent].load(Ordering::Acquire);
+            if parent != grandparent {
+                // Path halving, losing the race only skips the shortcut.
+                let _ = self.rep[curr].compare_exchange_weak(
+                    parent,
+                    grandparent,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                );
+            }
+            curr = grandparent;
+        }
+    }
+
+    // Merges the sets of `i` and `j`, returning false if they were the
+    // same. Exactly one of the threads merging two sets gets true.
+    pub fn union(&self, i: UnionFindRep, j: UnionFindRep) -> bool {
+        loop {
+            let mut root_i = self.find(i);
+            let mut root_j = self.find(j);
+            if root_i == root_j {
+                return false;
+            }
+            if Self::priority(root_i) > Self::priority(root_j) {
+                std::mem::swap(&mut root_i, &mut root_j);
+            }
+            // Fails if `root_i` was hooked meanwhile, then retry.
+            if self.rep[root_i]
+                .compare_exchange(root_i, root_j, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                return true;
+            }
+        }
+    }
+
+    // Returns true if `i` and `j` are in the same set, consistent with
+    // concurrent unions.
+    pub fn same_set(&self, i: UnionFindRep, j: UnionFindRep) -> bool {
+        loop {
+            let root_i = self.find(i);
+            let root_j = self.find(j);
+            if root_i == root_j {
+                return true;
+            }
+            // Still a root: the sets were distinct when `root_j` was found.
+            if self.rep[root_i].load(Ordering::Acquire) == root_i {
+                return false;
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.rep.len()
+    }
+
+    // Returns true if there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.rep.is_empty()
+    }
+
+    // Returns an estimate of the heap bytes held by the forest.
+    pub fn memory_footprint(&self) -> usize {
+        self.rep.heap_bytes()
+    }
+}
+
+impl DisjointSets for ConcurrentUnionFind {
+    fn find(&mut self, i: UnionFindRep) -> UnionFindRep {
+        ConcurrentUnionFind::find(self, i)
+    }
+
+    fn union(&mut self, i: UnionFindRep, j: UnionFindRep) -> bool {
+        ConcurrentUnionFind::union(self, i, j)
+    }
+
+    fn len(&self) -> usize {
+        ConcurrentUnionFind::len(self)
+    }
+
+    fn same_set(&mut self, i: UnionFindRep, j: UnionFindRep) -> bool {
+        ConcurrentUnionFind::same_set(self, i, j)
+    }
+}
+
+// A shared reference is enough to use the structure, so each thread can
+// hold its own `&ConcurrentUnionFind` as a `DisjointSets`.
+impl DisjointSets for &ConcurrentUnionFind {
+    fn find(&mut self, i: UnionFindRep) -> UnionFindRep {
+        ConcurrentUnionFind::find(self, i)
+    }
+
+    fn union(&mut self, i: UnionFindRep, j: UnionFindRep) -> bool {
+        ConcurrentUnionFind::union(self, i, j)
+    }
+
+    fn len(&self) -> usize {
+        ConcurrentUnionFind::len(self)
+    }
+
+    fn same_set(&mut self, i: UnionFindRep, j: UnionFindRep) -> bool {
+        ConcurrentUnionFind::same_set(self, i, j)
+    }
+}
@@ -0,0 +1,74 @@
+// # Integration
+//
+// Every variant on the graphs without a tree edge to find, and the expected
+// weight of the tree of the complete graph with uniform weights.
+use mst_kruskal_variants::*;
+
+// Graphs on 0 and 1 vertices, the last with a self-loop.
+fn trivial_graphs() -> Vec<(usize, Vec<Edge>)> {
+    vec![(0, vec![]), (1, vec![]), (1, vec![Edge::new(0, 0, 5)])]
+}
+
+// Runs the `run_cost` of the variant itself on the graph of the edges.
+fn run_cost(algorithm: Algorithm, num_vertices: usize, edges: &[Edge]) -> Result<Cost, GraphError> {
+    let mut matrix = GraphMatrix::new_from_collection(0..num_vertices);
+    let mut stars = GraphStars::new_from_collection(0..num_vertices);
+    for edge in edges {
+        matrix.add_edge(edge.from, edge.to, edge.weight)?;
+        stars.add_edge(edge.from, edge.to, edge.weight)?;
+    }
+    let rng = &mut SplitMix64::new(0);
+    match algorithm {
+        Algorithm::Kruskal => Kruskal::new(&matrix).run_cost(),
+        Algorithm::QuickSortKruskal => QuickSortKruskal::new(&matrix).run_cost(rng),
+        Algorithm::FilterKruskal => FilterKruskal::new(&matrix).run_cost(rng),
+        Algorithm::SkewedFilterKruskal => SkewedFilterKruskal::new(&matrix).run_cost(rng),
+        Algorithm::StarQuickSortKruskal => StarQuickSortKruskal::new(&stars).run_cost(rng),
+        Algorithm::BucketKruskal => BucketKruskal::new(&matrix).run_cost(),
+        Algorithm::RadixKruskal => RadixKruskal::new(&matrix).run_cost(),
+        Algorithm::DensePrim => DensePrim::new(&matrix).run_cost(),
+        Algorithm::BoruvkaFilterKruskal => BoruvkaFilterKruskal::new(&matrix).run_cost(rng),
+        Algorithm::FilterKruskalPlus => FilterKruskalPlus::new(&matrix).run_cost(rng),
+        Algorithm::SparsePrim => SparsePrim::new(&stars).run_cost(),
+        Algorithm::Chazelle => Chazelle::new(&matrix).run_cost(),
+        Algorithm::SortKruskal => SortKruskal::new(&matrix).run_cost(),
+    }
+}
+
+#[test]
+fn trivial_graphs_have_empty_trees() {
+    for algorithm in Algorithm::ALL {
+        for (n, edges) in trivial_graphs() {
+            let tree = algorithm
+                .run_on_edges(n, &edges, &mut SplitMix64::new(0))
+                .unwrap();
+            assert!(tree.edges.is_empty(), "{algorithm:?} on {n} vertices");
+            assert_eq!(tree.cost, 0, "{algorithm:?} on {n} vertices");
+
+            assert_eq!(run_cost(algorithm, n, &edges).unwrap(), 0, "{algorithm:?}");
+
+            let tree = Mst::builder()
+                .algorithm(algorithm)
+                .direction(Direction::Max)
+                .solve_edges(n, &edges)
+                .unwrap();
+            assert!(tree.edges.is_empty(), "{algorithm:?} on {n} vertices");
+            assert_eq!(tree.cost, 0, "{algorithm:?} on {n} vertices");
+        }
+    }
+}
+
+// The expected weight of the MST of K_n with uniform [0, 1] weights tends
+// to ζ(3) ≈ 1.202 (Frieze, 1985), here with integer weights scaled back.
+#[cfg(feature = "rand")]
+#[test]
+fn complete_graph_tree_weight_tends_to_zeta_3() {
+    const ZETA_3: f64 = 1.2020569;
+    const MAX_WEIGHT: usize = 1 << 20;
+    let summary = experiments::run(Algorithm::DensePrim, 0..100, |seed| {
+        GraphMatrix::<usize>::new_random_seeded(0..500, 1.0, 1, MAX_WEIGHT, true, seed).unwrap()
+    })
+    .unwrap();
+    let mean = summary.mean_cost / MAX_WEIGHT as f64;
+    assert!((mean - ZETA_3).abs() < 0.01, "mean tree weight {mean}");
+}
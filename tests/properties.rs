@@ -0,0 +1,52 @@
+// # Properties
+//
+// Every variant agrees on the cost of the tree of the generated graphs.
+#![cfg(feature = "proptest")]
+use mst_kruskal_variants::strategies::{GraphCase, GraphStrategy};
+use mst_kruskal_variants::*;
+use proptest::prelude::*;
+
+fn costs(case: &GraphCase) -> Vec<Cost> {
+    Algorithm::ALL
+        .iter()
+        .map(|algorithm| {
+            algorithm
+                .run_seeded(case.num_vertices, &case.edges, 0)
+                .unwrap()
+                .cost
+        })
+        .collect()
+}
+
+proptest! {
+    #[test]
+    fn variants_agree_on_cost(case in GraphStrategy::default()) {
+        let costs = costs(&case);
+        prop_assert!(costs.windows(2).all(|w| w[0] == w[1]), "{:?}", costs);
+    }
+
+    #[test]
+    fn variants_agree_on_cost_with_ties(case in GraphStrategy { ties: true, ..Default::default() }) {
+        let costs = costs(&case);
+        prop_assert!(costs.windows(2).all(|w| w[0] == w[1]), "{:?}", costs);
+    }
+}
+
+// A failing property shrinks to a smallest accepted case.
+#[test]
+fn shrinks_to_minimal_case() {
+    let strategy = GraphStrategy::default();
+    let mut runner = proptest::test_runner::TestRunner::deterministic();
+    let result = runner.run(&strategy, |case| {
+        prop_assert!(case.edges.len() < 3);
+        Ok(())
+    });
+    match result {
+        Err(proptest::test_runner::TestError::Fail(_, case)) => {
+            // A triangle, or a tree on 4 vertices.
+            assert_eq!(case.edges.len(), 3);
+            assert!(case.num_vertices <= 4, "{case:?}");
+        }
+        other => panic!("expected a failure, got {other:?}"),
+    }
+}